@@ -0,0 +1,61 @@
+#![allow(dead_code)]
+
+use std::fmt::Write;
+
+use rosu_pp::Beatmap;
+
+/// Amount of hit objects of the synthetic long map.
+pub const LONG_MAP_OBJECTS: usize = 50_000;
+
+/// The bundled maps, one for each mode.
+pub fn maps() -> [(&'static str, Beatmap); 4] {
+    let map = |path| Beatmap::from_path(path).unwrap();
+
+    [
+        ("osu", map("./resources/2785319.osu")),
+        ("taiko", map("./resources/1028484.osu")),
+        ("catch", map("./resources/2118524.osu")),
+        ("mania", map("./resources/1638954.osu")),
+    ]
+}
+
+/// A synthetic osu!standard map with [`LONG_MAP_OBJECTS`] alternating
+/// circles and sliders.
+pub fn long_map() -> Beatmap {
+    let mut content = String::from(
+        "osu file format v14\n\n[General]\nStackLeniency: 0.7\nMode: 0\n\n\
+        [Difficulty]\nHPDrainRate:5\nCircleSize:4\nOverallDifficulty:8\nApproachRate:9\n\
+        SliderMultiplier:1.4\nSliderTickRate:1\n\n[TimingPoints]\n0,300,4,1,0,100,1,0\n\n\
+        [HitObjects]\n",
+    );
+
+    for i in 0..LONG_MAP_OBJECTS {
+        let x = 64 + (i * 97) % 384;
+        let y = 48 + (i * 61) % 288;
+        let time = 1000 + i * 150;
+
+        if i % 2 == 0 {
+            let _ = writeln!(content, "{x},{y},{time},1,0,0:0:0:0:");
+        } else {
+            let _ = writeln!(
+                content,
+                "{x},{y},{time},2,0,B|{}:{}|{}:{},1,70",
+                x + 40,
+                y + 20,
+                x + 60,
+                y - 10
+            );
+        }
+    }
+
+    Beatmap::from_bytes(content.as_bytes()).unwrap()
+}
+
+/// Which strain storage is enabled through the `compact_strains` feature.
+pub const fn strains_kind() -> &'static str {
+    if cfg!(feature = "compact_strains") {
+        "compact"
+    } else {
+        "full"
+    }
+}
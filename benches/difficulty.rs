@@ -0,0 +1,31 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rosu_pp::Difficulty;
+
+mod common;
+
+fn difficulty(c: &mut Criterion) {
+    let mut group = c.benchmark_group("difficulty");
+
+    for (mode, map) in common::maps() {
+        group.bench_function(mode, |b| {
+            b.iter(|| black_box(Difficulty::new().mods(64).calculate(&map)));
+        });
+    }
+
+    group.finish();
+}
+
+fn difficulty_long(c: &mut Criterion) {
+    let map = common::long_map();
+    let mut group = c.benchmark_group("difficulty_long");
+    group.sample_size(10);
+
+    group.bench_function("osu", |b| {
+        b.iter(|| black_box(Difficulty::new().calculate(&map)));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, difficulty, difficulty_long);
+criterion_main!(benches);
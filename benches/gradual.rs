@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rosu_pp::{any::ScoreState, Difficulty, GradualDifficulty, GradualPerformance};
+
+mod common;
+
+fn gradual_difficulty(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gradual_difficulty");
+
+    for (mode, map) in common::maps() {
+        group.bench_function(mode, |b| {
+            b.iter(|| {
+                for attrs in GradualDifficulty::new(Difficulty::new(), &map) {
+                    black_box(attrs);
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn gradual_performance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gradual_performance");
+
+    for (mode, map) in common::maps() {
+        group.bench_function(mode, |b| {
+            b.iter(|| {
+                let mut gradual = GradualPerformance::new(Difficulty::new(), &map);
+                let mut state = ScoreState::new();
+
+                loop {
+                    state.max_combo += 1;
+                    state.n_geki += 1;
+                    state.n300 += 1;
+
+                    let Some(attrs) = gradual.next(state.clone()) else {
+                        break;
+                    };
+
+                    black_box(attrs);
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, gradual_difficulty, gradual_performance);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rosu_pp::{Difficulty, Performance};
+
+mod common;
+
+fn performance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("performance");
+
+    for (mode, map) in common::maps() {
+        let attrs = Difficulty::new().mods(8 + 64).calculate(&map);
+
+        group.bench_function(mode, |b| {
+            b.iter(|| {
+                let performance = Performance::new(attrs.clone())
+                    .mods(8 + 64)
+                    .accuracy(97.5)
+                    .misses(3)
+                    .calculate();
+
+                black_box(performance)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, performance);
+criterion_main!(benches);
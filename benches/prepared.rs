@@ -0,0 +1,62 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rosu_map::section::general::GameMode;
+use rosu_pp::{
+    osu::{Osu, OsuPreparedMap},
+    Beatmap, Difficulty,
+};
+
+/// NM, HD, HR, DT, HT, EZ, HDHR, HDDT, HRDT, and HDHRDT
+const MODS: [u32; 10] = [0, 8, 16, 64, 256, 2, 24, 72, 80, 88];
+
+fn osu_maps() -> Vec<(String, Beatmap)> {
+    let mut maps: Vec<_> = std::fs::read_dir("./resources")
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            let map = Beatmap::from_path(&path).ok()?;
+
+            Some((name, map))
+        })
+        .filter(|(_, map)| map.mode == GameMode::Osu)
+        .collect();
+
+    maps.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+    maps
+}
+
+fn mod_combos(c: &mut Criterion) {
+    let mut group = c.benchmark_group("osu_mod_combos");
+
+    for (name, map) in osu_maps() {
+        let converted = map.unchecked_into_converted::<Osu>();
+
+        group.bench_with_input(
+            BenchmarkId::new("difficulty", &name),
+            &converted,
+            |b, map| {
+                b.iter(|| {
+                    for mods in MODS {
+                        black_box(Difficulty::new().mods(mods).with_mode().calculate(map));
+                    }
+                });
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("prepared", &name), &converted, |b, map| {
+            b.iter(|| {
+                let mut prepared = OsuPreparedMap::new(map);
+
+                for mods in MODS {
+                    black_box(prepared.calculate(&Difficulty::new().mods(mods)));
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, mod_combos);
+criterion_main!(benches);
@@ -0,0 +1,124 @@
+//! Compare the latest criterion results against `benches/baseline.json`.
+//!
+//! Run the other benchmarks first, then
+//! - `cargo bench --bench regression` to print the comparison
+//! - `cargo bench --bench regression -- --save` to overwrite the baseline
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+const BASELINE_PATH: &str = "./benches/baseline.json";
+
+/// Relative slowdown that is reported as regression.
+const THRESHOLD: f64 = 0.1;
+
+fn main() {
+    let target_dir = env::var_os("CARGO_TARGET_DIR").map_or_else(|| "target".into(), PathBuf::from);
+
+    let mut latest = BTreeMap::new();
+    collect_estimates(&target_dir.join("criterion"), &mut latest);
+
+    if latest.is_empty() {
+        println!("No criterion results found, run the other benchmarks first");
+
+        return;
+    }
+
+    if env::args().any(|arg| arg == "--save") {
+        let json = serde_json::to_string_pretty(&latest).unwrap();
+        fs::write(BASELINE_PATH, json + "\n").unwrap();
+        println!("Saved {} results to {BASELINE_PATH}", latest.len());
+
+        return;
+    }
+
+    let baseline: BTreeMap<String, f64> = fs::read_to_string(BASELINE_PATH)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    let mut n_regressions = 0;
+
+    for (id, &mean) in &latest {
+        let Some(&base) = baseline.get(id) else {
+            println!("{id:<40} {:>12}   (new)", fmt_ns(mean));
+
+            continue;
+        };
+
+        let change = mean / base - 1.0;
+
+        let note = if change > THRESHOLD {
+            n_regressions += 1;
+
+            "   REGRESSION"
+        } else {
+            ""
+        };
+
+        println!(
+            "{id:<40} {:>12} {:>+8.1}%{note}",
+            fmt_ns(mean),
+            change * 100.0
+        );
+    }
+
+    println!(
+        "\n{n_regressions} regression(s) above {}%",
+        THRESHOLD * 100.0
+    );
+}
+
+/// Recursively find the mean estimates of all benchmarks.
+///
+/// Criterion stores them in `<id>/new/estimates.json` alongside the full id
+/// in `<id>/new/benchmark.json`.
+fn collect_estimates(dir: &Path, estimates: &mut BTreeMap<String, f64>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if !path.is_dir() {
+            continue;
+        }
+
+        if path.ends_with("new") {
+            if let Some((id, mean)) = read_estimate(&path) {
+                estimates.insert(id, mean);
+            }
+        } else {
+            collect_estimates(&path, estimates);
+        }
+    }
+}
+
+fn read_estimate(dir: &Path) -> Option<(String, f64)> {
+    let read = |name| -> Option<Value> {
+        let json = fs::read_to_string(dir.join(name)).ok()?;
+
+        serde_json::from_str(&json).ok()
+    };
+
+    let id = read("benchmark.json")?["full_id"].as_str()?.to_owned();
+    let mean = read("estimates.json")?["mean"]["point_estimate"].as_f64()?;
+
+    Some((id, mean))
+}
+
+fn fmt_ns(ns: f64) -> String {
+    if ns >= 1e6 {
+        format!("{:.3} ms", ns / 1e6)
+    } else if ns >= 1e3 {
+        format!("{:.3} µs", ns / 1e3)
+    } else {
+        format!("{ns:.3} ns")
+    }
+}
@@ -0,0 +1,36 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rosu_pp::Difficulty;
+
+mod common;
+
+// The `compact_strains` feature is compared by running this benchmark with
+// and without `--no-default-features` which stores results under different
+// names.
+fn strains(c: &mut Criterion) {
+    let mut group = c.benchmark_group("strains");
+
+    for (mode, map) in common::maps() {
+        let id = BenchmarkId::new(common::strains_kind(), mode);
+
+        group.bench_function(id, |b| {
+            b.iter(|| black_box(Difficulty::new().strains(&map)));
+        });
+    }
+
+    group.finish();
+}
+
+fn strains_long(c: &mut Criterion) {
+    let map = common::long_map();
+    let mut group = c.benchmark_group("strains_long");
+    group.sample_size(10);
+
+    group.bench_function(BenchmarkId::new(common::strains_kind(), "osu"), |b| {
+        b.iter(|| black_box(Difficulty::new().strains(&map)));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, strains, strains_long);
+criterion_main!(benches);
@@ -4,6 +4,7 @@ use std::{
 };
 
 use crate::{
+    any::HardestSection,
     model::{beatmap::Converted, mode::IGameMode},
     util::generic_fmt::GenericFormatter,
     Difficulty,
@@ -67,6 +68,14 @@ impl<M: IGameMode> ConvertedDifficulty<'_, M> {
         M::strains(self.inner, map)
     }
 
+    /// Find the contiguous section of a [`Converted`] beatmap with the
+    /// highest combined strain.
+    ///
+    /// See [`Difficulty::hardest_section`] for more information.
+    pub fn hardest_section(self, map: &Converted<'_, M>, duration: f64) -> Option<HardestSection> {
+        M::hardest_section(self.inner, map, duration)
+    }
+
     /// Create a gradual difficulty calculator for a [`Converted`] beatmap.
     pub fn gradual_difficulty(self, map: &Converted<'_, M>) -> M::GradualDifficulty {
         M::gradual_difficulty(self.inner.to_owned(), map)
@@ -3,11 +3,11 @@ use std::borrow::Cow;
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::DifficultyAttributes,
+    any::{DifficultyAttributes, GradualSnapshot},
     catch::{Catch, CatchBeatmap, CatchGradualDifficulty},
     mania::{Mania, ManiaBeatmap, ManiaGradualDifficulty},
-    model::mode::IGameMode,
-    osu::{Osu, OsuBeatmap, OsuGradualDifficulty},
+    model::{hit_object::ObjectTime, mode::IGameMode},
+    osu::{Osu, OsuBeatmap, OsuGradualDifficulty, OsuStreamStats},
     taiko::{Taiko, TaikoBeatmap, TaikoGradualDifficulty},
     Beatmap, Converted, Difficulty,
 };
@@ -40,6 +40,8 @@ use crate::{
 /// }
 /// ```
 ///
+/// See [`GradualPerformance`] regarding memory layout and thread safety.
+///
 /// [`GradualPerformance`]: crate::GradualPerformance
 // 504 vs 184 bytes is an acceptable difference and the Osu variant (424 bytes)
 // is likely the most used one anyway.
@@ -83,6 +85,130 @@ impl GradualDifficulty {
     from_converted!(from_taiko_map, Taiko, TaikoBeatmap);
     from_converted!(from_catch_map, Catch, CatchBeatmap);
     from_converted!(from_mania_map, Mania, ManiaBeatmap);
+
+    /// Resume a calculation on a map of any mode from a [`GradualSnapshot`].
+    ///
+    /// Returns `None` if the map cannot be converted to the snapshot's mode
+    /// or if its amount of objects differs from the snapshot's.
+    pub fn from_snapshot(snapshot: &GradualSnapshot, map: &Beatmap) -> Option<Self> {
+        match snapshot.mode() {
+            GameMode::Osu => OsuBeatmap::try_from_ref(map)
+                .and_then(|converted| OsuGradualDifficulty::from_snapshot(snapshot, &converted))
+                .map(Self::Osu),
+            GameMode::Taiko => TaikoBeatmap::try_from_ref(map)
+                .and_then(|converted| TaikoGradualDifficulty::from_snapshot(snapshot, &converted))
+                .map(Self::Taiko),
+            GameMode::Catch => CatchBeatmap::try_from_ref(map)
+                .and_then(|converted| CatchGradualDifficulty::from_snapshot(snapshot, &converted))
+                .map(Self::Catch),
+            GameMode::Mania => {
+                let mods = snapshot.difficulty().get_mods();
+
+                ManiaBeatmap::try_from_ref_with_mods(map, mods)
+                    .and_then(|converted| {
+                        ManiaGradualDifficulty::from_snapshot(snapshot, &converted)
+                    })
+                    .map(Self::Mania)
+            }
+        }
+    }
+
+    /// Capture the progress of the calculation in a compact
+    /// [`GradualSnapshot`].
+    pub fn snapshot(&self) -> GradualSnapshot {
+        match self {
+            Self::Osu(gradual) => gradual.snapshot(),
+            Self::Taiko(gradual) => gradual.snapshot(),
+            Self::Catch(gradual) => gradual.snapshot(),
+            Self::Mania(gradual) => gradual.snapshot(),
+        }
+    }
+
+    /// Store a snapshot of the state after every `interval` processed
+    /// objects so that [`seek`] can jump back and forth quickly.
+    ///
+    /// Smaller intervals make seeking faster but require more memory.
+    /// Without an interval, no snapshots are stored and seeking backwards
+    /// restarts from the first object.
+    ///
+    /// [`seek`]: Self::seek
+    #[must_use]
+    pub fn checkpoint_interval(self, interval: usize) -> Self {
+        match self {
+            Self::Osu(gradual) => Self::Osu(gradual.checkpoint_interval(interval)),
+            Self::Taiko(gradual) => Self::Taiko(gradual.checkpoint_interval(interval)),
+            Self::Catch(gradual) => Self::Catch(gradual.checkpoint_interval(interval)),
+            Self::Mania(gradual) => Self::Mania(gradual.checkpoint_interval(interval)),
+        }
+    }
+
+    /// Specify whether [`advance_to_time`] compares timestamps against the
+    /// start or the end time of objects.
+    ///
+    /// Defaults to [`ObjectTime::Start`].
+    ///
+    /// [`advance_to_time`]: Self::advance_to_time
+    #[must_use]
+    pub fn object_time(self, object_time: ObjectTime) -> Self {
+        match self {
+            Self::Osu(gradual) => Self::Osu(gradual.object_time(object_time)),
+            Self::Taiko(gradual) => Self::Taiko(gradual.object_time(object_time)),
+            Self::Catch(gradual) => Self::Catch(gradual.object_time(object_time)),
+            Self::Mania(gradual) => Self::Mania(gradual.object_time(object_time)),
+        }
+    }
+
+    /// Calculate the difficulty attributes after the object at index `idx`,
+    /// regardless of how many objects were processed so far.
+    ///
+    /// The result is the same as calling `nth(idx)` on a new iterator and
+    /// afterwards, iteration continues with the object at index `idx + 1`.
+    /// Returns `None` if `idx` is out of bounds.
+    pub fn seek(&mut self, idx: usize) -> Option<DifficultyAttributes> {
+        match self {
+            GradualDifficulty::Osu(gradual) => gradual.seek(idx).map(DifficultyAttributes::Osu),
+            GradualDifficulty::Taiko(gradual) => gradual.seek(idx).map(DifficultyAttributes::Taiko),
+            GradualDifficulty::Catch(gradual) => gradual.seek(idx).map(DifficultyAttributes::Catch),
+            GradualDifficulty::Mania(gradual) => gradual.seek(idx).map(DifficultyAttributes::Mania),
+        }
+    }
+
+    /// Process all objects whose time is at or before the timestamp `time`
+    /// in milliseconds and return the resulting difficulty attributes.
+    ///
+    /// The timestamp is adjusted by the clock rate so it should be the
+    /// elapsed time of a play, e.g. with DT an object at `1500` ms in the
+    /// map is reached after `1000` ms.
+    ///
+    /// Returns `None` if no new object was processed.
+    pub fn advance_to_time(&mut self, time: f64) -> Option<DifficultyAttributes> {
+        match self {
+            GradualDifficulty::Osu(gradual) => {
+                gradual.advance_to_time(time).map(DifficultyAttributes::Osu)
+            }
+            GradualDifficulty::Taiko(gradual) => gradual
+                .advance_to_time(time)
+                .map(DifficultyAttributes::Taiko),
+            GradualDifficulty::Catch(gradual) => gradual
+                .advance_to_time(time)
+                .map(DifficultyAttributes::Catch),
+            GradualDifficulty::Mania(gradual) => gradual
+                .advance_to_time(time)
+                .map(DifficultyAttributes::Mania),
+        }
+    }
+
+    /// Statistics about the streams of all objects processed so far.
+    ///
+    /// Returns `None` if the map is not an osu!standard map.
+    pub fn stream_stats(&self) -> Option<OsuStreamStats> {
+        match self {
+            GradualDifficulty::Osu(gradual) => Some(gradual.stream_stats()),
+            GradualDifficulty::Taiko(_)
+            | GradualDifficulty::Catch(_)
+            | GradualDifficulty::Mania(_) => None,
+        }
+    }
 }
 
 impl Iterator for GradualDifficulty {
@@ -17,13 +17,14 @@ use crate::{
 
 use self::converted::ConvertedDifficulty;
 
-use super::{attributes::DifficultyAttributes, InspectDifficulty, Strains};
+use super::{attributes::DifficultyAttributes, HardestSection, InspectDifficulty, Strains};
 
 pub mod converted;
 pub mod gradual;
 pub mod inspect;
 pub mod object;
 pub mod skills;
+pub mod snapshot;
 
 use crate::{model::mode::IGameMode, util::mods::Mods};
 
@@ -290,6 +291,26 @@ impl Difficulty {
         }
     }
 
+    /// Find the contiguous section of a [`Beatmap`] with the highest
+    /// combined strain.
+    ///
+    /// `duration` is the length of the section in ms and is adjusted by the
+    /// clock rate i.e. with DT a duration of 30 seconds covers 45 seconds of
+    /// the map. The section is never longer than the map itself.
+    ///
+    /// Returns `None` if the map has no strain at all, e.g. if it has fewer
+    /// than two hit objects.
+    pub fn hardest_section(&self, map: &Beatmap, duration: f64) -> Option<HardestSection> {
+        let map = Cow::Borrowed(map);
+
+        match map.mode {
+            GameMode::Osu => Osu::hardest_section(self, &Converted::new(map), duration),
+            GameMode::Taiko => Taiko::hardest_section(self, &Converted::new(map), duration),
+            GameMode::Catch => Catch::hardest_section(self, &Converted::new(map), duration),
+            GameMode::Mania => Mania::hardest_section(self, &Converted::new(map), duration),
+        }
+    }
+
     /// Create a gradual difficulty calculator for a [`Beatmap`].
     pub fn gradual_difficulty(self, map: &Beatmap) -> GradualDifficulty {
         GradualDifficulty::new(self, map)
@@ -0,0 +1,349 @@
+use std::num::NonZeroU32;
+
+use rosu_map::section::general::GameMode;
+
+use crate::model::hit_object::ObjectTime;
+
+use super::{Difficulty, ModsDependent};
+
+/// Version of the binary format of [`GradualSnapshot`].
+const VERSION: u8 = 1;
+
+/// The progress of a gradual calculation.
+///
+/// Instead of the skill state, only the settings and the amount of processed
+/// objects are stored so that a snapshot stays small regardless of the map.
+/// Resuming from a snapshot re-processes all objects up to that point which
+/// produces the exact same state.
+///
+/// Hence, resuming is not free: it costs as much skill processing as the
+/// amount of objects that were processed before the snapshot was taken, i.e.
+/// O(n_processed), and should not be done on every object.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty, GradualPerformance};
+/// use rosu_pp::any::{GradualSnapshot, ScoreState};
+///
+/// let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+/// let mut gradual = GradualPerformance::new(Difficulty::new().mods(64), &map);
+/// let _ = gradual.nth(ScoreState::new(), 99);
+///
+/// // Suspend the calculation...
+/// let bytes = gradual.snapshot().to_bytes();
+///
+/// // ... and resume it later on
+/// let snapshot = GradualSnapshot::from_bytes(&bytes).unwrap();
+/// let mut resumed = GradualPerformance::from_snapshot(&snapshot, &map).unwrap();
+///
+/// assert_eq!(gradual.len(), resumed.len());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradualSnapshot {
+    mode: GameMode,
+    difficulty: Difficulty,
+    checkpoint_interval: Option<u32>,
+    object_time: ObjectTime,
+    n_objects: u32,
+    n_processed: u32,
+}
+
+impl GradualSnapshot {
+    pub(crate) fn new(
+        mode: GameMode,
+        difficulty: &Difficulty,
+        n_objects: usize,
+        n_processed: usize,
+        object_time: ObjectTime,
+        checkpoint_interval: Option<usize>,
+    ) -> Self {
+        // Saturating the amount of objects is fine because resuming on a map
+        // with that many objects fails due to the mismatch anyway.
+        let to_u32 = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+
+        Self {
+            mode,
+            difficulty: difficulty.clone(),
+            checkpoint_interval: checkpoint_interval.map(to_u32),
+            object_time,
+            n_objects: to_u32(n_objects),
+            n_processed: to_u32(n_processed),
+        }
+    }
+
+    /// The mode of the calculation.
+    pub const fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// The amount of objects of the map that the snapshot was created for.
+    pub const fn n_objects(&self) -> usize {
+        self.n_objects as usize
+    }
+
+    /// The amount of objects that were processed.
+    pub const fn n_processed(&self) -> usize {
+        self.n_processed as usize
+    }
+
+    pub(crate) const fn difficulty(&self) -> &Difficulty {
+        &self.difficulty
+    }
+
+    pub(crate) fn checkpoint_interval(&self) -> Option<usize> {
+        self.checkpoint_interval.map(|interval| interval as usize)
+    }
+
+    pub(crate) const fn object_time(&self) -> ObjectTime {
+        self.object_time
+    }
+
+    /// Encode the snapshot into bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let Difficulty {
+            mods,
+            passed_objects,
+            clock_rate,
+            ar,
+            cs,
+            hp,
+            od,
+            hardrock_offsets,
+        } = &self.difficulty;
+
+        let mut writer = Writer(Vec::with_capacity(48));
+
+        writer.u8(VERSION);
+        writer.u8(self.mode as u8);
+        writer.u32(*mods);
+        writer.option(*passed_objects, Writer::u32);
+        writer.option(clock_rate.map(NonZeroU32::get), Writer::u32);
+
+        for attr in [ar, cs, hp, od] {
+            writer.option(*attr, |writer, attr| {
+                writer.u32(attr.value.to_bits());
+                writer.bool(attr.with_mods);
+            });
+        }
+
+        writer.option(*hardrock_offsets, Writer::bool);
+        writer.bool(self.object_time == ObjectTime::End);
+        writer.option(self.checkpoint_interval, Writer::u32);
+        writer.u32(self.n_objects);
+        writer.u32(self.n_processed);
+
+        writer.0
+    }
+
+    /// Decode a snapshot from bytes that were created through
+    /// [`GradualSnapshot::to_bytes`].
+    ///
+    /// Returns `None` if the bytes are not a valid snapshot.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+
+        if reader.u8()? != VERSION {
+            return None;
+        }
+
+        let mode = match reader.u8()? {
+            0 => GameMode::Osu,
+            1 => GameMode::Taiko,
+            2 => GameMode::Catch,
+            3 => GameMode::Mania,
+            _ => return None,
+        };
+
+        let mods = reader.u32()?;
+        let passed_objects = reader.option(Reader::u32)?;
+        let clock_rate = reader.option(|reader| reader.u32().and_then(NonZeroU32::new))?;
+
+        let mut attr = || {
+            reader.option(|reader| {
+                Some(ModsDependent {
+                    value: f32::from_bits(reader.u32()?),
+                    with_mods: reader.bool()?,
+                })
+            })
+        };
+
+        let [ar, cs, hp, od] = [attr()?, attr()?, attr()?, attr()?];
+        let hardrock_offsets = reader.option(Reader::bool)?;
+
+        let object_time = if reader.bool()? {
+            ObjectTime::End
+        } else {
+            ObjectTime::Start
+        };
+
+        let checkpoint_interval = reader.option(Reader::u32)?;
+        let n_objects = reader.u32()?;
+        let n_processed = reader.u32()?;
+
+        if !reader.0.is_empty() {
+            return None;
+        }
+
+        let difficulty = Difficulty {
+            mods,
+            passed_objects,
+            clock_rate,
+            ar,
+            cs,
+            hp,
+            od,
+            hardrock_offsets,
+        };
+
+        Some(Self {
+            mode,
+            difficulty,
+            checkpoint_interval,
+            object_time,
+            n_objects,
+            n_processed,
+        })
+    }
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, n: u8) {
+        self.0.push(n);
+    }
+
+    fn bool(&mut self, b: bool) {
+        self.u8(u8::from(b));
+    }
+
+    fn u32(&mut self, n: u32) {
+        self.0.extend_from_slice(&n.to_le_bytes());
+    }
+
+    fn option<T>(&mut self, value: Option<T>, write: impl FnOnce(&mut Self, T)) {
+        self.bool(value.is_some());
+
+        if let Some(value) = value {
+            write(self, value);
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn u8(&mut self) -> Option<u8> {
+        let (n, rest) = self.0.split_first()?;
+        self.0 = rest;
+
+        Some(*n)
+    }
+
+    fn bool(&mut self) -> Option<bool> {
+        match self.u8()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let (n, rest) = self.0.split_first_chunk()?;
+        self.0 = rest;
+
+        Some(u32::from_le_bytes(*n))
+    }
+
+    /// Returns `None` if the bytes are invalid and `Some(None)` if the value
+    /// is not present.
+    #[allow(clippy::option_option)]
+    fn option<T>(&mut self, read: impl FnOnce(&mut Self) -> Option<T>) -> Option<Option<T>> {
+        if self.bool()? {
+            read(self).map(Some)
+        } else {
+            Some(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        any::ScoreState,
+        mania::{Mania, ManiaGradualPerformance},
+        Beatmap, GradualPerformance,
+    };
+
+    use super::*;
+
+    #[test]
+    fn bytes_roundtrip() {
+        let difficulty = Difficulty::new()
+            .mods(64 + 16)
+            .passed_objects(100)
+            .clock_rate(1.3)
+            .ar(9.5, true)
+            .od(7.0, false)
+            .hardrock_offsets(false);
+
+        let snapshot = GradualSnapshot::new(
+            GameMode::Catch,
+            &difficulty,
+            50,
+            42,
+            ObjectTime::End,
+            Some(7),
+        );
+        let bytes = snapshot.to_bytes();
+
+        assert_eq!(GradualSnapshot::from_bytes(&bytes), Some(snapshot));
+        assert!(GradualSnapshot::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+        assert!(GradualSnapshot::from_bytes(&[]).is_none());
+    }
+
+    #[test]
+    fn resume() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+        let converted = map.unchecked_as_converted::<Mania>();
+
+        let difficulty = Difficulty::new().mods(64);
+        let state = ScoreState::new();
+
+        let mut gradual = GradualPerformance::new(difficulty.clone(), &map);
+        let _ = gradual.nth(state.clone(), 149);
+
+        let bytes = gradual.snapshot().to_bytes();
+        let snapshot = GradualSnapshot::from_bytes(&bytes).unwrap();
+        assert_eq!(snapshot.n_processed(), 150);
+
+        let mut resumed = GradualPerformance::from_snapshot(&snapshot, &map).unwrap();
+        assert_eq!(
+            gradual.nth(state.clone(), 10),
+            resumed.nth(state.clone(), 10)
+        );
+
+        let mut resumed = ManiaGradualPerformance::from_snapshot(&snapshot, &converted).unwrap();
+        let mut expected = ManiaGradualPerformance::new(difficulty, &converted);
+        let _ = expected.nth(state.clone().into(), 149);
+        assert_eq!(
+            resumed.last(state.clone().into()),
+            expected.last(state.into())
+        );
+    }
+
+    #[test]
+    fn different_map() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+        let mut gradual = GradualPerformance::new(Difficulty::new(), &map);
+        let _ = gradual.nth(ScoreState::new(), 9);
+
+        let snapshot = gradual.snapshot();
+        assert!(GradualPerformance::from_snapshot(&snapshot, &map).is_some());
+
+        // Converts to osu!mania but has a different amount of objects
+        let other = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        assert!(GradualPerformance::from_snapshot(&snapshot, &other).is_none());
+    }
+}
@@ -0,0 +1,203 @@
+use crate::Beatmap;
+
+/// The contiguous section of a map with the highest combined strain.
+///
+/// Created through [`Difficulty::hardest_section`].
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty};
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// // The hardest 30 seconds of the map
+/// let section = Difficulty::new()
+///     .hardest_section(&map, 30_000.0)
+///     .unwrap();
+///
+/// let practice_map = section.trim(&map);
+/// ```
+///
+/// [`Difficulty::hardest_section`]: crate::Difficulty::hardest_section
+#[derive(Clone, Debug, PartialEq)]
+pub struct HardestSection {
+    /// Start time of the section in ms.
+    ///
+    /// The time is **not** adjusted by the clock rate.
+    pub start_time: f64,
+    /// End time of the section in ms.
+    ///
+    /// The time is **not** adjusted by the clock rate.
+    pub end_time: f64,
+    /// The sum of the combined strain peaks within the section.
+    pub strain: f64,
+}
+
+impl HardestSection {
+    /// Find the hardest section based on the combined strain peaks of a map.
+    ///
+    /// `last_section_end` is the end time of the last peak's section and,
+    /// just like `section_len` and `duration`, is adjusted by the clock rate.
+    pub(crate) fn new(
+        peaks: &[f64],
+        section_len: f64,
+        last_section_end: f64,
+        clock_rate: f64,
+        duration: f64,
+    ) -> Option<Self> {
+        if peaks.iter().all(|&peak| peak <= 0.0) {
+            return None;
+        }
+
+        let n_sections = ((duration / section_len).ceil() as usize).clamp(1, peaks.len());
+
+        let mut sum: f64 = peaks[..n_sections].iter().sum();
+        let mut max_sum = sum;
+        let mut max_start = 0;
+
+        for (start, (&prev, &next)) in peaks.iter().zip(&peaks[n_sections..]).enumerate() {
+            sum += next - prev;
+
+            if sum > max_sum {
+                max_sum = sum;
+                max_start = start + 1;
+            }
+        }
+
+        // The section of the peak at index `i` ends at
+        // `last_section_end - (peaks.len() - 1 - i) * section_len`.
+        let first_section_start = last_section_end - peaks.len() as f64 * section_len;
+        let start_time = (first_section_start + max_start as f64 * section_len).max(0.0);
+        let end_time = first_section_start + (max_start + n_sections) as f64 * section_len;
+
+        Some(Self {
+            start_time: start_time * clock_rate,
+            end_time: end_time * clock_rate,
+            strain: max_sum,
+        })
+    }
+
+    /// Create a new [`Beatmap`] that only contains the hit objects within
+    /// this section.
+    ///
+    /// Timing points, difficulty points, and effect points are preserved so
+    /// the hit objects stay the same. Breaks are only kept if they lie
+    /// within the section.
+    pub fn trim(&self, map: &Beatmap) -> Beatmap {
+        let Beatmap {
+            version,
+            is_convert,
+            stack_leniency,
+            mode,
+            ar,
+            cs,
+            hp,
+            od,
+            slider_multiplier,
+            slider_tick_rate,
+            ref breaks,
+            ref timing_points,
+            ref difficulty_points,
+            ref effect_points,
+            ref hit_objects,
+            ref hit_sounds,
+        } = *map;
+
+        let in_section = |time: f64| self.start_time <= time && time <= self.end_time;
+
+        let (hit_objects, hit_sounds) = hit_objects
+            .iter()
+            .zip(hit_sounds.iter())
+            .filter(|(h, _)| in_section(h.start_time))
+            .map(|(h, sound)| (h.clone(), *sound))
+            .unzip();
+
+        let breaks = breaks
+            .iter()
+            .filter(|b| in_section(b.start_time) && in_section(b.end_time))
+            .copied()
+            .collect();
+
+        Beatmap {
+            version,
+            is_convert,
+            stack_leniency,
+            mode,
+            ar,
+            cs,
+            hp,
+            od,
+            slider_multiplier,
+            slider_tick_rate,
+            breaks,
+            timing_points: timing_points.clone(),
+            difficulty_points: difficulty_points.clone(),
+            effect_points: effect_points.clone(),
+            hit_objects,
+            hit_sounds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Difficulty;
+
+    use super::*;
+
+    #[test]
+    fn finds_densest_window() {
+        let peaks = [1.0, 0.0, 3.0, 4.0, 1.0, 0.0];
+        let section = HardestSection::new(&peaks, 400.0, 2400.0, 1.0, 800.0).unwrap();
+
+        assert_eq!(section.start_time, 800.0);
+        assert_eq!(section.end_time, 1600.0);
+        assert_eq!(section.strain, 7.0);
+    }
+
+    #[test]
+    fn adjusts_by_clock_rate() {
+        let peaks = [1.0, 2.0];
+        let section = HardestSection::new(&peaks, 400.0, 800.0, 1.5, 400.0).unwrap();
+
+        assert_eq!(section.start_time, 600.0);
+        assert_eq!(section.end_time, 1200.0);
+    }
+
+    #[test]
+    fn all_modes() {
+        let paths = [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ];
+
+        for path in paths {
+            let map = Beatmap::from_path(path).unwrap();
+
+            let section = Difficulty::new().hardest_section(&map, 10_000.0).unwrap();
+            let dt_section = Difficulty::new()
+                .mods(64)
+                .hardest_section(&map, 10_000.0)
+                .unwrap();
+
+            assert!(section.start_time < section.end_time, "{path}");
+            assert!((section.end_time - section.start_time - 10_000.0).abs() < 1000.0);
+            assert!(dt_section.end_time - dt_section.start_time > 14_000.0);
+
+            let trimmed = section.trim(&map);
+
+            assert!(!trimmed.hit_objects.is_empty(), "{path}");
+            assert!(trimmed.hit_objects.len() < map.hit_objects.len(), "{path}");
+            assert_eq!(trimmed.hit_objects.len(), trimmed.hit_sounds.len());
+            assert_eq!(trimmed.timing_points, map.timing_points);
+        }
+    }
+
+    #[test]
+    fn no_strain() {
+        assert!(HardestSection::new(&[0.0], 400.0, 0.0, 1.0, 30_000.0).is_none());
+    }
+}
@@ -2,19 +2,24 @@ pub use self::{
     attributes::{DifficultyAttributes, PerformanceAttributes},
     difficulty::{
         converted::ConvertedDifficulty, gradual::GradualDifficulty, inspect::InspectDifficulty,
-        Difficulty, ModsDependent,
+        snapshot::GradualSnapshot, Difficulty, ModsDependent,
     },
+    hardest_section::HardestSection,
+    mod_search::{ModSearch, ModSearchMatch},
     performance::{
+        batch::PerformanceBatch,
         gradual::GradualPerformance,
         into::{IntoModePerformance, IntoPerformance},
         HitResultPriority, Performance,
     },
     score_state::ScoreState,
-    strains::Strains,
+    strains::{StrainTimeline, Strains, TimelineScale},
 };
 
 mod attributes;
 pub(crate) mod difficulty;
+mod hardest_section;
+mod mod_search;
 mod performance;
 mod score_state;
 mod strains;
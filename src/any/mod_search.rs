@@ -0,0 +1,282 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::{DifficultyAttributes, Performance},
+    osu::OsuPreparedMap,
+    util::mods::Mods,
+    Beatmap, Difficulty,
+};
+
+/// Find mod combinations and clock rates that put a map's star rating or pp
+/// into a given range.
+///
+/// Each candidate combines one of the [`mods`] with its own clock rate. Mod
+/// combinations without DT or HT are additionally combined with each of the
+/// [`clock_rates`].
+///
+/// Difficulty attributes are cached across searches. Candidates that only
+/// differ in mods which don't affect difficulty, e.g. NF or HD on
+/// non-osu!standard maps, or that reach the same clock rate in different ways,
+/// e.g. DT and a custom rate of 1.5, share a single difficulty calculation.
+///
+/// For osu!standard maps, the remaining calculations additionally share their
+/// conversion work through an [`OsuPreparedMap`].
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::Beatmap;
+/// use rosu_pp::any::ModSearch;
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let mut search = ModSearch::new(&map).clock_rates([1.1, 1.2, 1.3, 1.4]);
+///
+/// for found in search.stars(6.0..=6.5) {
+///     println!(
+///         "mods={} rate={} stars={:.2}",
+///         found.mods, found.clock_rate, found.stars
+///     );
+/// }
+///
+/// // The second search on the same map reuses the difficulty attributes
+/// let _ = search.pp(400.0..=500.0);
+/// ```
+///
+/// [`mods`]: ModSearch::mods
+/// [`clock_rates`]: ModSearch::clock_rates
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct ModSearch<'map> {
+    map: &'map Beatmap,
+    mods: Vec<u32>,
+    clock_rates: Vec<f64>,
+    prepared: Option<OsuPreparedMap<'map>>,
+    cache: HashMap<DifficultyKey, DifficultyAttributes>,
+}
+
+/// A candidate of a [`ModSearch`] that landed within the range.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModSearchMatch {
+    /// The mods of the candidate.
+    pub mods: u32,
+    /// The clock rate of the candidate.
+    pub clock_rate: f64,
+    /// The star rating of the candidate.
+    pub stars: f64,
+    /// The pp of an SS with the candidate.
+    pub pp: f64,
+}
+
+impl<'map> ModSearch<'map> {
+    /// Mod combinations that are considered by default.
+    ///
+    /// NM, HD, HR, DT, HT, EZ, FL, HDHR, HDDT, HRDT, HDHRDT, HDHT, HRHT, EZHD,
+    /// EZDT, EZHT, HDFL, and HDHRFL
+    pub const DEFAULT_MODS: [u32; 18] = [
+        0, 8, 16, 64, 256, 2, 1024, 24, 72, 80, 88, 264, 272, 10, 66, 258, 1032, 1048,
+    ];
+
+    /// Create a new search on the given map.
+    pub fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mods: Self::DEFAULT_MODS.to_vec(),
+            clock_rates: Vec::new(),
+            prepared: None,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Specify the mod combinations to consider.
+    ///
+    /// Defaults to [`ModSearch::DEFAULT_MODS`].
+    pub fn mods(mut self, mods: impl IntoIterator<Item = u32>) -> Self {
+        self.mods = mods.into_iter().collect();
+
+        self
+    }
+
+    /// Specify additional clock rates to combine with each mod combination
+    /// that contains neither DT nor HT.
+    ///
+    /// Clock rates are clamped between 0.01 and 100.
+    pub fn clock_rates(mut self, clock_rates: impl IntoIterator<Item = f64>) -> Self {
+        self.clock_rates = clock_rates.into_iter().collect();
+
+        self
+    }
+
+    /// Find all candidates whose star rating is within the range.
+    pub fn stars(&mut self, range: RangeInclusive<f64>) -> Vec<ModSearchMatch> {
+        self.search(|found| range.contains(&found.stars))
+    }
+
+    /// Find all candidates for which an SS is worth pp within the range.
+    pub fn pp(&mut self, range: RangeInclusive<f64>) -> Vec<ModSearchMatch> {
+        self.search(|found| range.contains(&found.pp))
+    }
+
+    /// The amount of difficulty calculations that are currently cached.
+    pub fn cached(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn search(&mut self, mut in_range: impl FnMut(&ModSearchMatch) -> bool) -> Vec<ModSearchMatch> {
+        let own_rates = self.mods.iter().map(|&mods| (mods, None));
+
+        let custom_rates = self
+            .mods
+            .iter()
+            .filter(|mods| !mods.dt() && !mods.ht())
+            .flat_map(|&mods| self.clock_rates.iter().map(move |&rate| (mods, Some(rate))));
+
+        let candidates: Vec<_> = own_rates.chain(custom_rates).collect();
+
+        candidates
+            .into_iter()
+            .map(|(mods, clock_rate)| self.evaluate(mods, clock_rate))
+            .filter(|found| in_range(found))
+            .collect()
+    }
+
+    fn evaluate(&mut self, mods: u32, clock_rate: Option<f64>) -> ModSearchMatch {
+        let mut difficulty = Difficulty::new().mods(mods);
+
+        if let Some(clock_rate) = clock_rate {
+            difficulty = difficulty.clock_rate(clock_rate);
+        }
+
+        let clock_rate = difficulty.get_clock_rate();
+        let key = DifficultyKey::new(mods, clock_rate, self.map.mode);
+
+        let Self {
+            map,
+            ref mut prepared,
+            ref mut cache,
+            ..
+        } = *self;
+
+        let attrs = cache.entry(key).or_insert_with(|| {
+            if map.mode == GameMode::Osu {
+                let prepared = prepared.get_or_insert_with(|| OsuPreparedMap::from_map(map));
+
+                DifficultyAttributes::Osu(prepared.calculate(&difficulty))
+            } else {
+                difficulty.calculate(map)
+            }
+        });
+
+        let stars = attrs.stars();
+
+        let pp = Performance::new(attrs.clone())
+            .difficulty(difficulty)
+            .calculate()
+            .pp();
+
+        ModSearchMatch {
+            mods,
+            clock_rate,
+            stars,
+            pp,
+        }
+    }
+}
+
+/// The parts of a candidate that affect its difficulty attributes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct DifficultyKey {
+    ez: bool,
+    hr: bool,
+    hd: bool,
+    fl: bool,
+    co: bool,
+    key_count: Option<u8>,
+    clock_rate: u32,
+}
+
+impl DifficultyKey {
+    fn new(mods: u32, clock_rate: f64, mode: GameMode) -> Self {
+        // HD and FL only affect osu!standard difficulty
+        let osu = mode == GameMode::Osu;
+
+        Self {
+            ez: mods.ez(),
+            hr: mods.hr(),
+            hd: osu && mods.hd(),
+            fl: osu && mods.fl(),
+            co: mods.co(),
+            key_count: mods.key_count(),
+            clock_rate: (clock_rate as f32).to_bits(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_calculations() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let mut search = ModSearch::new(&map)
+            .mods([0, 8, 64, 64 + 512, 8 + 64, 1 + 8 + 64])
+            .clock_rates([1.5]);
+
+        let all = search.stars(0.0..=f64::MAX);
+
+        // 6 mods with own rates plus 2 without DT at a custom rate
+        assert_eq!(all.len(), 8);
+        // NM and DT on a taiko map
+        assert_eq!(search.cached(), 2);
+
+        let dt = Difficulty::new().mods(64).calculate(&map);
+        let custom = all.last().unwrap();
+
+        assert_eq!(custom.mods, 8);
+        assert_eq!(custom.stars, dt.stars());
+
+        let pp = search.pp(0.0..=f64::MAX);
+        assert_eq!(search.cached(), 2);
+        assert_eq!(all, pp);
+    }
+
+    #[test]
+    fn filters_range() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let mut search = ModSearch::new(&map).mods([0, 256, 64]);
+        let all = search.stars(0.0..=f64::MAX);
+        let nm = all[0].stars;
+
+        let harder = search.stars(nm + 0.01..=f64::MAX);
+        assert_eq!(harder.len(), 1);
+        assert_eq!(harder[0].mods, 64);
+
+        assert!(search.stars(-2.0..=-1.0).is_empty());
+    }
+
+    #[test]
+    fn osu_matches_difficulty() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let mut search = ModSearch::new(&map)
+            .mods([0, 1, 8, 16, 2 + 64, 8 + 16 + 64])
+            .clock_rates([1.2]);
+
+        for found in search.stars(0.0..=f64::MAX) {
+            let expected = Difficulty::new()
+                .mods(found.mods)
+                .clock_rate(found.clock_rate)
+                .calculate(&map);
+
+            assert_eq!(found.stars, expected.stars(), "{found:?}");
+        }
+
+        // NF shares its calculations with NM
+        assert_eq!(search.cached(), 8);
+    }
+}
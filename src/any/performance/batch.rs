@@ -0,0 +1,155 @@
+use crate::{
+    any::{DifficultyAttributes, PerformanceAttributes, ScoreState},
+    catch::CatchPerformanceBatch,
+    mania::ManiaPerformanceBatch,
+    osu::OsuPerformanceBatch,
+    taiko::TaikoPerformanceBatch,
+};
+
+/// Calculate the performance attributes of many scores on the same map of any
+/// mode.
+///
+/// All values that only depend on the map and mods are computed once upfront
+/// so this is considerably faster than creating a [`Performance`] for each
+/// score while producing the same results.
+///
+/// Note that score states are used as is, i.e. unlike [`Performance`],
+/// missing hitresults are not generated so every state should be complete.
+/// Since the map could have any mode, all fields of [`ScoreState`] could be of
+/// use.
+///
+/// Alternatively, you can match on the map's mode yourself and use the batch
+/// struct for the corresponding mode, i.e. [`OsuPerformanceBatch`],
+/// [`TaikoPerformanceBatch`], [`CatchPerformanceBatch`], or
+/// [`ManiaPerformanceBatch`].
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty, PerformanceBatch};
+/// use rosu_pp::any::ScoreState;
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let mods = 8 + 64; // HDDT
+/// let attrs = Difficulty::new().mods(mods).calculate(&map);
+/// let n_objects = map.hit_objects.len() as u32;
+///
+/// let states: Vec<_> = (0..10)
+///     .map(|misses| ScoreState {
+///         max_combo: attrs.max_combo() / (misses + 1),
+///         n300: n_objects - misses,
+///         misses,
+///         ..ScoreState::new()
+///     })
+///     .collect();
+///
+/// let batch = PerformanceBatch::new(attrs, mods);
+///
+/// for performance in batch.calculate(&states) {
+///     println!("PP: {}", performance.pp());
+/// }
+/// ```
+///
+/// [`Performance`]: crate::Performance
+pub enum PerformanceBatch {
+    Osu(OsuPerformanceBatch),
+    Taiko(TaikoPerformanceBatch),
+    Catch(CatchPerformanceBatch),
+    Mania(ManiaPerformanceBatch),
+}
+
+impl PerformanceBatch {
+    /// Prepare the batch calculation for the given difficulty attributes.
+    ///
+    /// `mods` must be the same mods that the attributes were calculated with.
+    pub fn new(attrs: DifficultyAttributes, mods: u32) -> Self {
+        match attrs {
+            DifficultyAttributes::Osu(attrs) => Self::Osu(OsuPerformanceBatch::new(attrs, mods)),
+            DifficultyAttributes::Taiko(attrs) => {
+                Self::Taiko(TaikoPerformanceBatch::new(attrs, mods))
+            }
+            DifficultyAttributes::Catch(attrs) => {
+                Self::Catch(CatchPerformanceBatch::new(attrs, mods))
+            }
+            DifficultyAttributes::Mania(attrs) => {
+                Self::Mania(ManiaPerformanceBatch::new(attrs, mods))
+            }
+        }
+    }
+
+    /// Calculate the performance attributes for each score state.
+    pub fn calculate(&self, states: &[ScoreState]) -> Vec<PerformanceAttributes> {
+        states
+            .iter()
+            .map(|state| self.calculate_single(state))
+            .collect()
+    }
+
+    /// Calculate the performance attributes for a single score state.
+    pub fn calculate_single(&self, state: &ScoreState) -> PerformanceAttributes {
+        let state = state.clone();
+
+        match self {
+            Self::Osu(batch) => PerformanceAttributes::Osu(batch.calculate_single(&state.into())),
+            Self::Taiko(batch) => {
+                PerformanceAttributes::Taiko(batch.calculate_single(&state.into()))
+            }
+            Self::Catch(batch) => {
+                PerformanceAttributes::Catch(batch.calculate_single(&state.into()))
+            }
+            Self::Mania(batch) => {
+                PerformanceAttributes::Mania(batch.calculate_single(&state.into()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Beatmap, Difficulty, Performance};
+
+    use super::*;
+
+    #[test]
+    fn matches_single() {
+        let maps = [
+            ("./resources/2785319.osu", [0, 8 + 1024, 16 + 64]),
+            ("./resources/1028484.osu", [0, 8 + 1024, 16 + 64]),
+            ("./resources/2118524.osu", [0, 8 + 1024, 16 + 64]),
+            ("./resources/1638954.osu", [0, 1 + 2, 64]),
+        ];
+
+        for (path, mods) in maps {
+            let map = Beatmap::from_path(path).unwrap();
+
+            for mods in mods {
+                let attrs = Difficulty::new().mods(mods).calculate(&map);
+
+                let states: Vec<_> = [(0, 0, 0), (20, 5, 0), (40, 10, 12), (0, 0, 300)]
+                    .into_iter()
+                    .map(|(n100, n50, misses)| {
+                        Performance::new(attrs.clone())
+                            .mods(mods)
+                            .combo(attrs.max_combo() / (misses + 1))
+                            .n100(n100)
+                            .n50(n50)
+                            .misses(misses)
+                            .generate_state()
+                    })
+                    .collect();
+
+                let batch = PerformanceBatch::new(attrs.clone(), mods).calculate(&states);
+
+                for (state, batched) in states.into_iter().zip(batch) {
+                    let single = Performance::new(attrs.clone())
+                        .mods(mods)
+                        .state(state)
+                        .calculate();
+
+                    assert_eq!(batched, single, "{path} mods={mods}");
+                }
+            }
+        }
+    }
+}
@@ -3,10 +3,10 @@ use std::borrow::Cow;
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::{PerformanceAttributes, ScoreState},
+    any::{GradualSnapshot, PerformanceAttributes, ScoreState},
     catch::{Catch, CatchBeatmap, CatchGradualPerformance},
     mania::{Mania, ManiaBeatmap, ManiaGradualPerformance},
-    model::mode::IGameMode,
+    model::{hit_object::ObjectTime, mode::IGameMode},
     osu::{Osu, OsuBeatmap, OsuGradualPerformance},
     taiko::{Taiko, TaikoBeatmap, TaikoGradualPerformance},
     Beatmap, Converted, Difficulty,
@@ -87,11 +87,38 @@ use crate::{
 /// assert!(gradual.next(state).is_none());
 /// ```
 ///
+/// # Memory layout
+///
+/// A gradual calculator does not borrow the [`Beatmap`] it was created from.
+/// Instead, it owns
+/// - the difficulty objects of all hit objects, computed upfront,
+/// - the current state of all skills,
+/// - if specified, a copy of the skill state every [`checkpoint_interval`]
+///   objects.
+///
+/// For osu!standard, difficulty objects refer to the converted hit objects.
+/// Those live in a separate heap allocation that is never moved or exposed
+/// so moving the calculator itself is fine.
+///
+/// To keep many calculations around for a long time, consider storing a
+/// [`snapshot`] instead which only consists of a few bytes.
+///
+/// # Thread safety
+///
+/// With the `sync` feature enabled, all gradual calculators are [`Send`] and
+/// [`Sync`] so they can be moved across threads or held across `.await`
+/// points. Without it, osu!taiko calculators and thus also
+/// [`GradualPerformance`] are neither because they use non-atomic reference
+/// counting.
+///
 /// [`next`]: GradualPerformance::next
+/// [`checkpoint_interval`]: GradualPerformance::checkpoint_interval
+/// [`snapshot`]: GradualPerformance::snapshot
 /// [`nth`]: GradualPerformance::nth
 /// [`GradualDifficulty`]: crate::GradualDifficulty
-// 504 vs 184 bytes is an acceptable difference and the Osu variant (424 bytes)
-// is likely the most used one anyway.
+// The Mania (968 bytes), Osu (928 bytes), and Taiko (888 bytes) variants are
+// all of similar size so only the Catch variant (392 bytes) wastes space which
+// is an acceptable difference compared to boxing the other three.
 #[allow(clippy::large_enum_variant)]
 pub enum GradualPerformance {
     Osu(OsuGradualPerformance),
@@ -133,6 +160,78 @@ impl GradualPerformance {
     from_converted!(from_catch_map, Catch, CatchBeatmap);
     from_converted!(from_mania_map, Mania, ManiaBeatmap);
 
+    /// Resume a calculation on a map of any mode from a [`GradualSnapshot`].
+    ///
+    /// Returns `None` if the map cannot be converted to the snapshot's mode
+    /// or if its amount of objects differs from the snapshot's.
+    pub fn from_snapshot(snapshot: &GradualSnapshot, map: &Beatmap) -> Option<Self> {
+        match snapshot.mode() {
+            GameMode::Osu => OsuBeatmap::try_from_ref(map)
+                .and_then(|converted| OsuGradualPerformance::from_snapshot(snapshot, &converted))
+                .map(Self::Osu),
+            GameMode::Taiko => TaikoBeatmap::try_from_ref(map)
+                .and_then(|converted| TaikoGradualPerformance::from_snapshot(snapshot, &converted))
+                .map(Self::Taiko),
+            GameMode::Catch => CatchBeatmap::try_from_ref(map)
+                .and_then(|converted| CatchGradualPerformance::from_snapshot(snapshot, &converted))
+                .map(Self::Catch),
+            GameMode::Mania => {
+                let mods = snapshot.difficulty().get_mods();
+
+                ManiaBeatmap::try_from_ref_with_mods(map, mods)
+                    .and_then(|converted| {
+                        ManiaGradualPerformance::from_snapshot(snapshot, &converted)
+                    })
+                    .map(Self::Mania)
+            }
+        }
+    }
+
+    /// Capture the progress of the calculation in a compact
+    /// [`GradualSnapshot`].
+    pub fn snapshot(&self) -> GradualSnapshot {
+        match self {
+            Self::Osu(gradual) => gradual.snapshot(),
+            Self::Taiko(gradual) => gradual.snapshot(),
+            Self::Catch(gradual) => gradual.snapshot(),
+            Self::Mania(gradual) => gradual.snapshot(),
+        }
+    }
+
+    /// Store a snapshot of the state after every `interval` processed
+    /// objects so that [`seek`] can jump back and forth quickly.
+    ///
+    /// Smaller intervals make seeking faster but require more memory.
+    /// Without an interval, no snapshots are stored and seeking backwards
+    /// restarts from the first object.
+    ///
+    /// [`seek`]: Self::seek
+    #[must_use]
+    pub fn checkpoint_interval(self, interval: usize) -> Self {
+        match self {
+            Self::Osu(gradual) => Self::Osu(gradual.checkpoint_interval(interval)),
+            Self::Taiko(gradual) => Self::Taiko(gradual.checkpoint_interval(interval)),
+            Self::Catch(gradual) => Self::Catch(gradual.checkpoint_interval(interval)),
+            Self::Mania(gradual) => Self::Mania(gradual.checkpoint_interval(interval)),
+        }
+    }
+
+    /// Specify whether [`advance_to_time`] compares timestamps against the
+    /// start or the end time of objects.
+    ///
+    /// Defaults to [`ObjectTime::Start`].
+    ///
+    /// [`advance_to_time`]: Self::advance_to_time
+    #[must_use]
+    pub fn object_time(self, object_time: ObjectTime) -> Self {
+        match self {
+            Self::Osu(gradual) => Self::Osu(gradual.object_time(object_time)),
+            Self::Taiko(gradual) => Self::Taiko(gradual.object_time(object_time)),
+            Self::Catch(gradual) => Self::Catch(gradual.object_time(object_time)),
+            Self::Mania(gradual) => Self::Mania(gradual.object_time(object_time)),
+        }
+    }
+
     /// Process the next hit object and calculate the performance attributes
     /// for the resulting score state.
     pub fn next(&mut self, state: ScoreState) -> Option<PerformanceAttributes> {
@@ -167,6 +266,58 @@ impl GradualPerformance {
         }
     }
 
+    /// Calculate the performance attributes after the object at index `idx`
+    /// for the given score state, regardless of how many objects were
+    /// processed so far.
+    ///
+    /// See [`GradualDifficulty::seek`] for more information.
+    ///
+    /// [`GradualDifficulty::seek`]: crate::GradualDifficulty::seek
+    pub fn seek(&mut self, state: ScoreState, idx: usize) -> Option<PerformanceAttributes> {
+        match self {
+            GradualPerformance::Osu(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Osu),
+            GradualPerformance::Taiko(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Taiko),
+            GradualPerformance::Catch(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Catch),
+            GradualPerformance::Mania(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Mania),
+        }
+    }
+
+    /// Process all objects whose time is at or before the timestamp `time`
+    /// in milliseconds and calculate the performance attributes for the
+    /// given score state.
+    ///
+    /// See [`GradualDifficulty::advance_to_time`] for more information.
+    ///
+    /// [`GradualDifficulty::advance_to_time`]: crate::GradualDifficulty::advance_to_time
+    pub fn advance_to_time(
+        &mut self,
+        state: ScoreState,
+        time: f64,
+    ) -> Option<PerformanceAttributes> {
+        match self {
+            GradualPerformance::Osu(gradual) => gradual
+                .advance_to_time(state.into(), time)
+                .map(PerformanceAttributes::Osu),
+            GradualPerformance::Taiko(gradual) => gradual
+                .advance_to_time(state.into(), time)
+                .map(PerformanceAttributes::Taiko),
+            GradualPerformance::Catch(gradual) => gradual
+                .advance_to_time(state.into(), time)
+                .map(PerformanceAttributes::Catch),
+            GradualPerformance::Mania(gradual) => gradual
+                .advance_to_time(state.into(), time)
+                .map(PerformanceAttributes::Mania),
+        }
+    }
+
     /// Returns the amount of remaining objects.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
@@ -9,6 +9,7 @@ use self::into::IntoPerformance;
 
 use super::{attributes::PerformanceAttributes, score_state::ScoreState};
 
+pub mod batch;
 pub mod gradual;
 pub mod into;
 
@@ -59,6 +60,55 @@ impl<'map> Performance<'map> {
         }
     }
 
+    /// Find the minimal accuracy, in percent, for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Since explicitly specified hitresults take precedence over the
+    /// accuracy, they should generally be left unspecified.
+    ///
+    /// Hitresults are generated according to the specified
+    /// [`HitResultPriority`] for each probed accuracy.
+    ///
+    /// Returns `None` if `target_pp` is not reached even with 100% accuracy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rosu_pp::{Beatmap, Performance};
+    ///
+    /// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+    ///
+    /// let acc = Performance::new(&map)
+    ///     .mods(8 + 64) // HDDT
+    ///     .accuracy_for_pp(500.0);
+    ///
+    /// match acc {
+    ///     Some(acc) => println!("Requires {acc:.2}% accuracy"),
+    ///     None => println!("Not possible"),
+    /// }
+    /// ```
+    pub fn accuracy_for_pp(self, target_pp: f64) -> Option<f64> {
+        match self {
+            Self::Osu(o) => o.accuracy_for_pp(target_pp),
+            Self::Taiko(t) => t.accuracy_for_pp(target_pp),
+            Self::Catch(f) => f.accuracy_for_pp(target_pp),
+            Self::Mania(m) => m.accuracy_for_pp(target_pp),
+        }
+    }
+
+    /// Find the maximal amount of misses for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Returns `None` if `target_pp` is not reached even without misses.
+    pub fn misses_for_pp(self, target_pp: f64) -> Option<u32> {
+        match self {
+            Self::Osu(o) => o.misses_for_pp(target_pp),
+            Self::Taiko(t) => t.misses_for_pp(target_pp),
+            Self::Catch(f) => f.misses_for_pp(target_pp),
+            Self::Mania(m) => m.misses_for_pp(target_pp),
+        }
+    }
+
     /// Attempt to convert the map to the specified mode.
     ///
     /// Returns `Err(self)` if the conversion is incompatible or no beatmap is
@@ -396,6 +446,49 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn pp_solver() {
+        let maps = [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ];
+
+        for path in maps {
+            let map = Beatmap::from_path(path).unwrap();
+            let attrs = Difficulty::new().calculate(&map);
+            let performance = || Performance::new(attrs.clone());
+
+            let target = performance().accuracy(95.0).calculate().pp();
+            let acc = performance().accuracy_for_pp(target).unwrap();
+            assert!(acc <= 95.0, "{path}");
+            assert!(
+                performance().accuracy(acc).calculate().pp() >= target,
+                "{path}"
+            );
+            assert!(
+                performance().accuracy(acc - 1e-5).calculate().pp() < target,
+                "{path}"
+            );
+
+            let target = performance().misses(10).calculate().pp();
+            let misses = performance().misses_for_pp(target).unwrap();
+            assert!(misses >= 10, "{path}");
+            assert!(
+                performance().misses(misses).calculate().pp() >= target,
+                "{path}"
+            );
+            assert!(
+                performance().misses(misses + 1).calculate().pp() < target,
+                "{path}"
+            );
+
+            assert!(performance().accuracy_for_pp(f64::MAX).is_none(), "{path}");
+            assert!(performance().misses_for_pp(f64::MAX).is_none(), "{path}");
+        }
+    }
+
     #[test]
     fn create() {
         let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
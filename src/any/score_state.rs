@@ -70,6 +70,8 @@ impl From<ScoreState> for OsuScoreState {
             n100: state.n100,
             n50: state.n50,
             misses: state.misses,
+            spinner_bonus: 0,
+            spun_out: 0,
         }
     }
 }
@@ -81,6 +83,8 @@ impl From<ScoreState> for TaikoScoreState {
             n300: state.n300,
             n100: state.n100,
             misses: state.misses,
+            drumroll_ticks: 0,
+            swells: 0,
         }
     }
 }
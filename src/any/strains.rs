@@ -21,6 +21,131 @@ impl Strains {
             Strains::Mania(_) => ManiaStrains::SECTION_LEN,
         }
     }
+
+    /// Turn the strain peaks into a smoothed difficulty curve.
+    ///
+    /// See [`StrainTimeline`] for more information.
+    #[must_use]
+    pub fn timeline(&self, timeline: &StrainTimeline) -> Self {
+        match self {
+            Strains::Osu(strains) => Strains::Osu(strains.timeline(timeline)),
+            Strains::Taiko(strains) => Strains::Taiko(strains.timeline(timeline)),
+            Strains::Catch(strains) => Strains::Catch(strains.timeline(timeline)),
+            Strains::Mania(strains) => Strains::Mania(strains.timeline(timeline)),
+        }
+    }
+}
+
+/// Settings to turn strain peaks into a smoothed difficulty curve.
+///
+/// Each value of the curve is calculated by weighing the strain peaks within a
+/// window around it the same way the difficulty calculation weighs all peaks
+/// of a map. The resulting values have the same section length as the strain
+/// peaks.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty};
+/// use rosu_pp::any::{StrainTimeline, TimelineScale};
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let timeline = StrainTimeline::new()
+///     .window(5000.0)
+///     .scale(TimelineScale::Stars);
+///
+/// let curve = Difficulty::new().strains(&map).timeline(&timeline);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[must_use]
+pub struct StrainTimeline {
+    window: f64,
+    scale: TimelineScale,
+}
+
+/// The scale of the values of a [`StrainTimeline`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TimelineScale {
+    /// The weighted sum of strain peaks.
+    #[default]
+    DifficultyValue,
+    /// The difficulty values scaled like a skill's star rating.
+    Stars,
+}
+
+impl StrainTimeline {
+    /// The default window length in ms.
+    pub const DEFAULT_WINDOW: f64 = 4000.0;
+
+    /// Create new timeline settings.
+    pub const fn new() -> Self {
+        Self {
+            window: Self::DEFAULT_WINDOW,
+            scale: TimelineScale::DifficultyValue,
+        }
+    }
+
+    /// Specify the length of the window around each section in ms.
+    ///
+    /// The window always contains at least one section.
+    pub const fn window(mut self, window: f64) -> Self {
+        self.window = window;
+
+        self
+    }
+
+    /// Specify the scale of the values.
+    pub const fn scale(mut self, scale: TimelineScale) -> Self {
+        self.scale = scale;
+
+        self
+    }
+
+    /// Smooth the given peaks.
+    ///
+    /// `to_stars` converts a difficulty value to the star rating scale.
+    pub(crate) fn smooth(
+        &self,
+        peaks: &[f64],
+        section_len: f64,
+        decay_weight: f64,
+        to_stars: impl Fn(f64) -> f64,
+    ) -> Vec<f64> {
+        let n_sections = ((self.window / section_len).round() as usize).max(1);
+        let half = n_sections / 2;
+        let mut buf = Vec::with_capacity(n_sections.min(peaks.len()));
+
+        (0..peaks.len())
+            .map(|i| {
+                let start = i.saturating_sub(half);
+                let end = i.saturating_add(n_sections - half).min(peaks.len());
+
+                buf.clear();
+                buf.extend(peaks[start..end].iter().copied().filter(|&peak| peak > 0.0));
+                buf.sort_by(|a, b| b.total_cmp(a));
+
+                let mut difficulty = 0.0;
+                let mut weight = 1.0;
+
+                for strain in buf.iter() {
+                    difficulty += strain * weight;
+                    weight *= decay_weight;
+                }
+
+                match self.scale {
+                    TimelineScale::DifficultyValue => difficulty,
+                    TimelineScale::Stars => to_stars(difficulty),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for StrainTimeline {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 macro_rules! from_mode_strains {
@@ -37,3 +162,83 @@ from_mode_strains!(Osu: OsuStrains);
 from_mode_strains!(Taiko: TaikoStrains);
 from_mode_strains!(Catch: CatchStrains);
 from_mode_strains!(Mania: ManiaStrains);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        catch::{Catch, CatchStrains},
+        mania::{Mania, ManiaStrains},
+        Beatmap, Difficulty,
+    };
+
+    use super::*;
+
+    #[test]
+    fn single_section_window_keeps_peaks() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let strains = Difficulty::new().strains(&map);
+
+        let timeline = StrainTimeline::new().window(strains.section_len());
+
+        let Strains::Taiko(smoothed) = strains.timeline(&timeline) else {
+            panic!("expected taiko strains");
+        };
+
+        let Strains::Taiko(strains) = strains else {
+            unreachable!()
+        };
+
+        assert_eq!(smoothed, strains);
+    }
+
+    #[test]
+    fn whole_map_window_matches_stars() {
+        let timeline = StrainTimeline::new()
+            .window(f64::MAX)
+            .scale(TimelineScale::Stars);
+
+        let converted = Beatmap::from_path("./resources/2118524.osu")
+            .unwrap()
+            .unchecked_into_converted::<Catch>();
+
+        let stars = Difficulty::new().with_mode().calculate(&converted).stars;
+        let CatchStrains { movement } = Difficulty::new()
+            .with_mode()
+            .strains(&converted)
+            .timeline(&timeline);
+
+        assert!(movement.iter().all(|value| (value - stars).abs() < 1e-9));
+
+        let converted = Beatmap::from_path("./resources/1638954.osu")
+            .unwrap()
+            .unchecked_into_converted::<Mania>();
+
+        let attrs = Difficulty::new().with_mode().calculate(&converted);
+        let ManiaStrains {
+            strains,
+            jack,
+            chord,
+            stream,
+            technical,
+            long_note,
+        } = Difficulty::new()
+            .with_mode()
+            .strains(&converted)
+            .timeline(&timeline);
+
+        assert!(strains
+            .iter()
+            .all(|value| (value - attrs.stars).abs() < 1e-9));
+        assert!(jack.iter().all(|value| (value - attrs.jack).abs() < 1e-9));
+        assert!(chord.iter().all(|value| (value - attrs.chord).abs() < 1e-9));
+        assert!(stream
+            .iter()
+            .all(|value| (value - attrs.stream).abs() < 1e-9));
+        assert!(technical
+            .iter()
+            .all(|value| (value - attrs.technical).abs() < 1e-9));
+        assert!(long_note
+            .iter()
+            .all(|value| (value - attrs.long_note).abs() < 1e-9));
+    }
+}
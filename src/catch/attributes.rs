@@ -15,6 +15,23 @@ pub struct CatchDifficultyAttributes {
     pub n_droplets: u32,
     /// The amount of tiny droplets.
     pub n_tiny_droplets: u32,
+    /// The amount of hyperdashes.
+    pub n_hyper_dashes: u32,
+    /// The amount of hyperdashes per minute.
+    ///
+    /// The time is adjusted by the clock rate.
+    pub hyper_dash_density: f64,
+    /// The amount of hyperdashes of the longest chain of consecutive
+    /// hyperdashes.
+    pub longest_hyper_dash_chain: u32,
+    /// The amount of edge dashes i.e. movements that barely don't require a
+    /// hyperdash.
+    pub n_edge_dashes: u32,
+    /// The highest distance in osu!pixels between two consecutive fruits or
+    /// droplets per ms.
+    ///
+    /// The time is adjusted by the clock rate.
+    pub max_dist_per_ms: f64,
     /// Whether the [`Beatmap`] was a convert i.e. an osu!standard map.
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
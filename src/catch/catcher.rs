@@ -1,9 +1,19 @@
+use crate::{
+    any::difficulty::converted::ConvertedDifficulty,
+    catch::{
+        attributes::ObjectCountBuilder, convert::convert_objects, object::palpable::PalpableObject,
+        Catch, CatchBeatmap, PLAYFIELD_WIDTH,
+    },
+    Difficulty,
+};
+
 pub struct Catcher;
 
 const AREA_CATCHER_SIZE: f32 = 106.75;
 
 impl Catcher {
     pub const BASE_SPEED: f64 = 1.0;
+    pub const BASE_WALK_SPEED: f64 = 0.5;
     pub const ALLOWED_CATCH_RANGE: f32 = 0.8;
 
     pub fn calculate_catch_width(cs: f32) -> f32 {
@@ -17,4 +27,400 @@ impl Catcher {
     fn calculate_scale(cs: f32) -> f32 {
         1.0 - 0.7 * (cs - 5.0) / 5.0
     }
+
+    /// Simulate the catcher on the given objects.
+    ///
+    /// Note that the catcher speeds are relative to the map's time so the
+    /// clock rate only affects the reported speeds.
+    pub fn simulate(
+        palpable_objects: &[PalpableObject],
+        cs: f32,
+        clock_rate: f64,
+    ) -> Vec<CatcherPosition> {
+        let half_catch_width = f64::from(Self::calculate_catch_width(cs) * 0.5);
+
+        let catch_range = |h: &PalpableObject| {
+            let x = f64::from(h.effective_x());
+
+            Range::new(x - half_catch_width, x + half_catch_width).clamp_to_playfield()
+        };
+
+        // Forward pass: all positions the catcher could be at when reaching
+        // an object. If none, the object is impossible to catch and we
+        // pretend it was caught anyway.
+        let mut reachable: Vec<Option<Range>> = Vec::with_capacity(palpable_objects.len());
+        let mut last: Option<(&PalpableObject, Range)> = None;
+
+        for h in palpable_objects {
+            let range = catch_range(h);
+
+            let Some((prev, prev_range)) = last else {
+                reachable.push(Some(range));
+                last = Some((h, range));
+
+                continue;
+            };
+
+            let reach = Self::reach(prev, prev_range, h).intersect(range);
+            reachable.push(reach);
+            last = Some((h, reach.unwrap_or(range)));
+        }
+
+        // Backward pass: only keep positions from which all following
+        // catchable objects can still be caught. If the next object can't be
+        // reached from any of them, it's uncatchable instead.
+        let mut feasible = reachable.clone();
+
+        for i in (0..palpable_objects.len().saturating_sub(1)).rev() {
+            let (Some(curr), Some(next)) = (feasible[i], feasible[i + 1]) else {
+                continue;
+            };
+
+            let (h, next_h) = (&palpable_objects[i], &palpable_objects[i + 1]);
+
+            match Self::reach_back(h, curr, next_h, next) {
+                Some(range) => feasible[i] = Some(range),
+                None => feasible[i + 1] = None,
+            }
+        }
+
+        // Move as little as possible while staying within feasible positions
+        let mut positions = Vec::with_capacity(palpable_objects.len());
+        let mut last: Option<(&PalpableObject, f64)> = None;
+
+        for (h, range) in palpable_objects.iter().zip(feasible) {
+            let catchable = range.is_some();
+            let range = range.unwrap_or_else(|| catch_range(h));
+
+            let (catcher_x, movement, speed) = match last {
+                Some((prev, prev_x)) => {
+                    let target = Self::reach(prev, Range::point(prev_x), h)
+                        .intersect(range)
+                        .unwrap_or(range);
+
+                    let catcher_x = target.clamp(prev_x);
+                    let dist = (catcher_x - prev_x).abs();
+                    let delta_time = h.start_time - prev.start_time;
+
+                    let speed = if delta_time > 0.0 {
+                        dist / delta_time
+                    } else if dist > 0.0 {
+                        f64::INFINITY
+                    } else {
+                        0.0
+                    };
+
+                    let movement = CatcherMovement::new(speed, prev.hyper_dash);
+
+                    (catcher_x, movement, speed * clock_rate)
+                }
+                None => (
+                    range.clamp(f64::from(PLAYFIELD_WIDTH) / 2.0),
+                    CatcherMovement::Stand,
+                    0.0,
+                ),
+            };
+
+            positions.push(CatcherPosition {
+                start_time: h.start_time,
+                x: h.effective_x(),
+                catcher_x: catcher_x as f32,
+                movement,
+                speed,
+                catchable,
+            });
+
+            last = Some((h, catcher_x));
+        }
+
+        positions
+    }
+
+    /// All positions the catcher can reach at `next` when being within
+    /// `range` at `curr`.
+    fn reach(curr: &PalpableObject, range: Range, next: &PalpableObject) -> Range {
+        let max_dist = (next.start_time - curr.start_time).max(0.0) * Self::BASE_SPEED;
+        let mut reach = range.expand(max_dist);
+
+        // Hyperdashes carry the catcher exactly onto the next object
+        if curr.hyper_dash {
+            reach = reach.hull(f64::from(next.effective_x()));
+        }
+
+        reach.clamp_to_playfield()
+    }
+
+    /// All positions within `range` at `curr` from which `next_range` can be
+    /// reached at `next`.
+    fn reach_back(
+        curr: &PalpableObject,
+        range: Range,
+        next: &PalpableObject,
+        next_range: Range,
+    ) -> Option<Range> {
+        let max_dist = (next.start_time - curr.start_time).max(0.0) * Self::BASE_SPEED;
+        let mut reach_back = next_range.expand(max_dist);
+
+        if curr.hyper_dash {
+            let target = f64::from(next.effective_x());
+
+            if target < next_range.min {
+                reach_back.max = f64::INFINITY;
+            } else if target > next_range.max {
+                reach_back.min = f64::NEG_INFINITY;
+            } else {
+                return Some(range);
+            }
+        }
+
+        range.intersect(reach_back)
+    }
+}
+
+/// How the catcher moves towards an object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CatcherMovement {
+    /// The catcher does not need to move.
+    Stand,
+    /// The catcher can walk.
+    Walk,
+    /// The catcher needs to dash.
+    Dash,
+    /// The catcher needs to be carried by a hyperdash.
+    HyperDash,
+    /// The catcher is too slow, even when dashing.
+    TooFast,
+}
+
+impl CatcherMovement {
+    fn new(speed: f64, hyper_dash: bool) -> Self {
+        if speed <= 0.0 {
+            Self::Stand
+        } else if speed <= Catcher::BASE_WALK_SPEED {
+            Self::Walk
+        } else if speed <= Catcher::BASE_SPEED {
+            Self::Dash
+        } else if hyper_dash {
+            Self::HyperDash
+        } else {
+            Self::TooFast
+        }
+    }
+}
+
+/// The position of the catcher when catching a fruit or droplet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatcherPosition {
+    /// Start time of the object in ms.
+    ///
+    /// The time is **not** adjusted by the clock rate.
+    pub start_time: f64,
+    /// The x-position of the object, including offsets of the HR mod.
+    pub x: f32,
+    /// The x-position of the catcher's center.
+    pub catcher_x: f32,
+    /// How the catcher moved towards the object.
+    pub movement: CatcherMovement,
+    /// The speed in osu!pixels per ms with which the catcher moved towards
+    /// the object.
+    ///
+    /// The time is adjusted by the clock rate.
+    pub speed: f64,
+    /// Whether the object can be caught.
+    ///
+    /// If `false`, the catcher still pretends to have caught the object for
+    /// the remaining simulation.
+    pub catchable: bool,
+}
+
+impl ConvertedDifficulty<'_, Catch> {
+    /// Simulate a catcher that moves as little as possible and still catches
+    /// all fruits and droplets of a [`CatchBeatmap`].
+    ///
+    /// Objects that can't be caught, e.g. due to the offsets of the HR mod,
+    /// are marked as not [`catchable`].
+    ///
+    /// [`catchable`]: CatcherPosition::catchable
+    pub fn simulate_catcher(self, map: &CatchBeatmap<'_>) -> Vec<CatcherPosition> {
+        simulate_catcher(self.inner(), map)
+    }
+}
+
+pub fn simulate_catcher(
+    difficulty: &Difficulty,
+    converted: &CatchBeatmap<'_>,
+) -> Vec<CatcherPosition> {
+    let take = difficulty.get_passed_objects();
+    let map_attrs = converted.attributes().difficulty(difficulty).build();
+
+    let hr_offsets = difficulty.get_hardrock_offsets();
+    let mut count = ObjectCountBuilder::new_regular(take);
+
+    let palpable_objects = convert_objects(converted, &mut count, hr_offsets, map_attrs.cs as f32);
+    let palpable_objects = &palpable_objects[..take.min(palpable_objects.len())];
+
+    Catcher::simulate(
+        palpable_objects,
+        map_attrs.cs as f32,
+        difficulty.get_clock_rate(),
+    )
+}
+
+/// A closed range of x-positions.
+#[derive(Copy, Clone, Debug)]
+struct Range {
+    min: f64,
+    max: f64,
+}
+
+impl Range {
+    const fn new(min: f64, max: f64) -> Self {
+        Self { min, max }
+    }
+
+    const fn point(x: f64) -> Self {
+        Self::new(x, x)
+    }
+
+    fn clamp_to_playfield(self) -> Self {
+        let width = f64::from(PLAYFIELD_WIDTH);
+
+        Self::new(self.min.max(0.0), self.max.min(width))
+    }
+
+    fn expand(self, dist: f64) -> Self {
+        Self::new(self.min - dist, self.max + dist)
+    }
+
+    const fn hull(self, x: f64) -> Self {
+        Self::new(self.min.min(x), self.max.max(x))
+    }
+
+    fn intersect(self, other: Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+
+        (min <= max).then_some(Self::new(min, max))
+    }
+
+    const fn clamp(self, x: f64) -> f64 {
+        x.clamp(self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Beatmap;
+
+    use super::*;
+
+    fn fruit(x: f32, start_time: f64, hyper_dash: bool) -> PalpableObject {
+        let mut fruit = PalpableObject::new(x, 0.0, start_time);
+        fruit.hyper_dash = hyper_dash;
+
+        fruit
+    }
+
+    #[test]
+    fn map_is_catchable() {
+        let converted = Beatmap::from_path("./resources/2118524.osu")
+            .unwrap()
+            .unchecked_into_converted::<Catch>();
+
+        let positions = Difficulty::new().with_mode().simulate_catcher(&converted);
+        let attrs = Difficulty::new().with_mode().calculate(&converted);
+
+        assert_eq!(positions.len() as u32, attrs.n_fruits + attrs.n_droplets);
+        assert!(positions.iter().all(|pos| pos.catchable));
+
+        let hyper_dashes = positions
+            .iter()
+            .filter(|pos| pos.movement == CatcherMovement::HyperDash)
+            .count() as u32;
+
+        assert!(hyper_dashes <= attrs.n_hyper_dashes);
+    }
+
+    #[test]
+    fn hardrock_offsets() {
+        let converted = Beatmap::from_path("./resources/2118524.osu")
+            .unwrap()
+            .unchecked_into_converted::<Catch>();
+
+        let nomod = Difficulty::new().with_mode().simulate_catcher(&converted);
+        let offsets = Difficulty::new()
+            .hardrock_offsets(true)
+            .with_mode()
+            .simulate_catcher(&converted);
+
+        assert_eq!(nomod.len(), offsets.len());
+        assert!(nomod.iter().zip(&offsets).any(|(a, b)| a.x != b.x));
+    }
+
+    #[test]
+    fn impossible_object() {
+        let objects = [
+            fruit(0.0, 0.0, false),
+            fruit(512.0, 100.0, false),
+            fruit(512.0, 200.0, false),
+        ];
+
+        let positions = Catcher::simulate(&objects, 5.0, 1.0);
+
+        assert!(positions[0].catchable);
+        assert!(!positions[1].catchable);
+        assert_eq!(positions[1].movement, CatcherMovement::TooFast);
+        assert!(positions[2].catchable);
+        assert_eq!(positions[2].movement, CatcherMovement::Stand);
+    }
+
+    #[test]
+    fn far_apart_fruits() {
+        let objects = [
+            fruit(256.0, 0.0, false),
+            fruit(300.0, 100.0, false),
+            fruit(20.0, 150.0, false),
+            fruit(480.0, 200.0, false),
+            fruit(470.0, 400.0, false),
+        ];
+
+        let positions = Catcher::simulate(&objects, 5.0, 1.0);
+        let half_catch_width = Catcher::calculate_catch_width(5.0) * 0.5;
+
+        let catchable: Vec<_> = positions.iter().map(|pos| pos.catchable).collect();
+        assert_eq!(catchable, [true, true, false, false, true]);
+
+        // The path through the catchable objects actually catches them
+        for pos in positions.iter().filter(|pos| pos.catchable) {
+            assert!(
+                (pos.catcher_x - pos.x).abs() <= half_catch_width + 0.01,
+                "{pos:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn hyper_dash() {
+        let objects = [fruit(0.0, 0.0, true), fruit(512.0, 100.0, false)];
+
+        let positions = Catcher::simulate(&objects, 5.0, 1.5);
+
+        assert!(positions.iter().all(|pos| pos.catchable));
+        assert_eq!(positions[1].movement, CatcherMovement::HyperDash);
+        assert!(positions[1].speed > Catcher::BASE_SPEED * 1.5);
+    }
+
+    #[test]
+    fn moves_as_little_as_possible() {
+        let objects = [
+            fruit(256.0, 0.0, false),
+            fruit(266.0, 1000.0, false),
+            fruit(100.0, 2000.0, false),
+        ];
+
+        let positions = Catcher::simulate(&objects, 5.0, 1.0);
+
+        assert_eq!(positions[1].movement, CatcherMovement::Stand);
+        assert_eq!(positions[2].movement, CatcherMovement::Walk);
+    }
 }
@@ -0,0 +1,234 @@
+use crate::{
+    any::difficulty::converted::ConvertedDifficulty,
+    catch::{
+        attributes::ObjectCountBuilder, convert::convert_objects, object::palpable::PalpableObject,
+        Catch, CatchBeatmap, CatchDifficultyAttributes,
+    },
+    Difficulty,
+};
+
+use super::{
+    object::CatchDifficultyObject, skills::movement::EDGE_DASH_DIST, CatchDifficultySetup,
+};
+
+/// How the catcher has to move from an object to the next one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CatchDashKind {
+    /// Neither an edge dash nor a hyperdash.
+    Regular,
+    /// The next object can barely be reached without a hyperdash.
+    EdgeDash,
+    /// The next object can only be reached through a hyperdash.
+    HyperDash,
+}
+
+impl CatchDashKind {
+    fn new(hyper_dash: bool, dist_to_hyper_dash: f32) -> Self {
+        if hyper_dash {
+            Self::HyperDash
+        } else if dist_to_hyper_dash <= EDGE_DASH_DIST {
+            Self::EdgeDash
+        } else {
+            Self::Regular
+        }
+    }
+}
+
+/// A fruit or droplet of an osu!catch map annotated with how the catcher has
+/// to move towards the next fruit or droplet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatchDashObject {
+    /// Start time of the object in ms.
+    ///
+    /// The time is **not** adjusted by the clock rate.
+    pub start_time: f64,
+    /// The x-position of the object, including offsets of the HR mod.
+    pub x: f32,
+    /// How the catcher has to move towards the next object.
+    ///
+    /// Always [`CatchDashKind::Regular`] for the last object.
+    pub kind: CatchDashKind,
+    /// The distance in osu!pixels to the next object per ms.
+    ///
+    /// The time is adjusted by the clock rate. `0.0` for the last object.
+    pub dist_per_ms: f64,
+}
+
+impl ConvertedDifficulty<'_, Catch> {
+    /// Annotate the fruits and droplets of a [`CatchBeatmap`] with how the
+    /// catcher has to move between them.
+    pub fn dashes(self, map: &CatchBeatmap<'_>) -> Vec<CatchDashObject> {
+        dashes(self.inner(), map)
+    }
+}
+
+pub fn dashes(difficulty: &Difficulty, converted: &CatchBeatmap<'_>) -> Vec<CatchDashObject> {
+    let take = difficulty.get_passed_objects();
+    let clock_rate = difficulty.get_clock_rate();
+
+    let CatchDifficultySetup { map_attrs, .. } = CatchDifficultySetup::new(difficulty, converted);
+
+    let hr_offsets = difficulty.get_hardrock_offsets();
+    let mut count = ObjectCountBuilder::new_regular(take);
+
+    let palpable_objects = convert_objects(converted, &mut count, hr_offsets, map_attrs.cs as f32);
+    let palpable_objects = &palpable_objects[..take.min(palpable_objects.len())];
+
+    let mut dashes: Vec<_> = palpable_objects
+        .windows(2)
+        .map(|window| {
+            let [curr, next] = window else { unreachable!() };
+
+            CatchDashObject {
+                start_time: curr.start_time,
+                x: curr.effective_x(),
+                kind: CatchDashKind::new(curr.hyper_dash, curr.dist_to_hyper_dash),
+                dist_per_ms: dist_per_ms(curr, next, clock_rate),
+            }
+        })
+        .collect();
+
+    if let Some(last) = palpable_objects.last() {
+        dashes.push(CatchDashObject {
+            start_time: last.start_time,
+            x: last.effective_x(),
+            kind: CatchDashKind::Regular,
+            dist_per_ms: 0.0,
+        });
+    }
+
+    dashes
+}
+
+fn dist_per_ms(curr: &PalpableObject, next: &PalpableObject, clock_rate: f64) -> f64 {
+    let delta_time = (next.start_time - curr.start_time) / clock_rate;
+
+    if delta_time > 0.0 {
+        f64::from((next.effective_x() - curr.effective_x()).abs()) / delta_time
+    } else {
+        0.0
+    }
+}
+
+/// Incrementally gathers the dash statistics of [`CatchDifficultyAttributes`].
+#[derive(Clone, Debug, Default)]
+pub struct DashStatsBuilder {
+    n_hyper_dashes: u32,
+    curr_chain: u32,
+    longest_chain: u32,
+    n_edge_dashes: u32,
+    max_dist_per_ms: f64,
+    first_start_time: Option<f64>,
+    last_start_time: f64,
+}
+
+impl DashStatsBuilder {
+    pub fn process(&mut self, curr: &CatchDifficultyObject) {
+        self.first_start_time
+            .get_or_insert(curr.start_time - curr.delta_time);
+        self.last_start_time = curr.start_time;
+
+        let last = &curr.last_object;
+
+        match CatchDashKind::new(last.hyper_dash, last.dist_to_hyper_dash) {
+            CatchDashKind::HyperDash => {
+                self.n_hyper_dashes += 1;
+                self.curr_chain += 1;
+                self.longest_chain = self.longest_chain.max(self.curr_chain);
+            }
+            CatchDashKind::EdgeDash => {
+                self.n_edge_dashes += 1;
+                self.curr_chain = 0;
+            }
+            CatchDashKind::Regular => self.curr_chain = 0,
+        }
+
+        if curr.delta_time > 0.0 {
+            let dist_per_ms = f64::from(curr.dist) / curr.delta_time;
+            self.max_dist_per_ms = self.max_dist_per_ms.max(dist_per_ms);
+        }
+    }
+
+    /// Store the statistics of all objects processed so far in the given
+    /// attributes.
+    pub fn apply(&self, attrs: &mut CatchDifficultyAttributes) {
+        attrs.n_hyper_dashes = self.n_hyper_dashes;
+        attrs.longest_hyper_dash_chain = self.longest_chain;
+        attrs.n_edge_dashes = self.n_edge_dashes;
+        attrs.max_dist_per_ms = self.max_dist_per_ms;
+
+        let minutes = (self.last_start_time - self.first_start_time.unwrap_or(0.0)) / 60_000.0;
+
+        attrs.hyper_dash_density = if minutes > 0.0 {
+            f64::from(self.n_hyper_dashes) / minutes
+        } else {
+            0.0
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Beatmap;
+
+    use super::*;
+
+    fn converted() -> CatchBeatmap<'static> {
+        Beatmap::from_path("./resources/2118524.osu")
+            .unwrap()
+            .unchecked_into_converted()
+    }
+
+    #[test]
+    fn matches_attributes() {
+        let converted = converted();
+
+        for mods in [0, 16, 64] {
+            let difficulty = Difficulty::new().mods(mods);
+            let attrs = difficulty.with_mode().calculate(&converted);
+            let dashes = difficulty.with_mode().dashes(&converted);
+
+            assert_eq!(dashes.len() as u32, attrs.n_fruits + attrs.n_droplets);
+
+            let count = |kind| dashes.iter().filter(|dash| dash.kind == kind).count() as u32;
+
+            assert!(attrs.n_hyper_dashes > 0);
+            assert_eq!(count(CatchDashKind::HyperDash), attrs.n_hyper_dashes);
+            assert_eq!(count(CatchDashKind::EdgeDash), attrs.n_edge_dashes);
+            assert!(attrs.longest_hyper_dash_chain >= 1);
+            assert!(attrs.longest_hyper_dash_chain <= attrs.n_hyper_dashes);
+            assert!(attrs.hyper_dash_density > 0.0);
+
+            let max_dist_per_ms = dashes
+                .iter()
+                .map(|dash| dash.dist_per_ms)
+                .fold(0.0, f64::max);
+
+            assert!((max_dist_per_ms - attrs.max_dist_per_ms).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn clock_rate() {
+        let converted = converted();
+
+        let nomod = Difficulty::new().with_mode().calculate(&converted);
+        let dt = Difficulty::new().mods(64).with_mode().calculate(&converted);
+
+        assert!(dt.hyper_dash_density > nomod.hyper_dash_density);
+        assert!(dt.max_dist_per_ms > nomod.max_dist_per_ms);
+    }
+
+    #[test]
+    fn empty() {
+        let converted = Beatmap::from_bytes(&[])
+            .unwrap()
+            .unchecked_into_converted::<Catch>();
+
+        let attrs = Difficulty::new().with_mode().calculate(&converted);
+
+        assert!(Difficulty::new().with_mode().dashes(&converted).is_empty());
+        assert_eq!(attrs.n_hyper_dashes, 0);
+        assert_eq!(attrs.hyper_dash_density, 0.0);
+    }
+}
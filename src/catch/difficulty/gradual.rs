@@ -1,18 +1,23 @@
 use std::cmp;
 
+use rosu_map::section::general::GameMode;
+
 use crate::{
     any::difficulty::skills::Skill,
+    any::GradualSnapshot,
     catch::{
         attributes::{GradualObjectCount, ObjectCountBuilder},
         convert::convert_objects,
         CatchBeatmap, CatchDifficultyAttributes,
     },
+    model::hit_object::ObjectTime,
+    util::{checkpoints::Checkpoints, object_times::ObjectTimes},
     Difficulty,
 };
 
 use super::{
-    object::CatchDifficultyObject, skills::movement::Movement, CatchDifficultySetup,
-    DifficultyValues,
+    dashes::DashStatsBuilder, object::CatchDifficultyObject, skills::movement::Movement,
+    CatchDifficultySetup, DifficultyValues,
 };
 
 /// Gradually calculate the difficulty attributes of an osu!catch map.
@@ -61,6 +66,18 @@ pub struct CatchGradualDifficulty {
     count: Vec<GradualObjectCount>,
     diff_objects: Box<[CatchDifficultyObject]>,
     movement: Movement,
+    dashes: DashStatsBuilder,
+    checkpoints: Checkpoints<Checkpoint>,
+    times: ObjectTimes,
+}
+
+/// The part of [`CatchGradualDifficulty`] that changes while processing
+/// objects.
+#[derive(Clone)]
+struct Checkpoint {
+    attrs: CatchDifficultyAttributes,
+    movement: Movement,
+    dashes: DashStatsBuilder,
 }
 
 impl CatchGradualDifficulty {
@@ -82,9 +99,22 @@ impl CatchGradualDifficulty {
             palpable_objects.iter(),
         );
 
+        let times = ObjectTimes::new(
+            palpable_objects
+                .iter()
+                .map(|h| (h.start_time, h.start_time)),
+            clock_rate,
+        );
+
         let count = count.into_gradual();
         let movement = Movement::new(clock_rate);
 
+        let checkpoints = Checkpoints::new(Checkpoint {
+            attrs: attrs.clone(),
+            movement: movement.clone(),
+            dashes: DashStatsBuilder::default(),
+        });
+
         Self {
             idx: 0,
             difficulty,
@@ -92,14 +122,127 @@ impl CatchGradualDifficulty {
             count,
             diff_objects,
             movement,
+            dashes: DashStatsBuilder::default(),
+            checkpoints,
+            times,
         }
     }
-}
 
-impl Iterator for CatchGradualDifficulty {
-    type Item = CatchDifficultyAttributes;
+    /// Store a snapshot of the state after every `interval` processed
+    /// objects so that [`seek`] can jump back and forth quickly.
+    ///
+    /// Smaller intervals make seeking faster but require more memory.
+    /// Without an interval, no snapshots are stored and seeking backwards
+    /// restarts from the first object.
+    ///
+    /// [`seek`]: Self::seek
+    #[must_use]
+    pub fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Specify whether [`advance_to_time`] compares timestamps against the
+    /// start or the end time of fruits and droplets.
+    ///
+    /// Defaults to [`ObjectTime::Start`].
+    ///
+    /// [`advance_to_time`]: Self::advance_to_time
+    #[must_use]
+    pub const fn object_time(mut self, object_time: ObjectTime) -> Self {
+        self.times.set_object_time(object_time);
+
+        self
+    }
+
+    /// Calculate the difficulty attributes after the fruit or droplet at
+    /// index `idx`, regardless of how many objects were processed so far.
+    ///
+    /// The result is the same as calling `nth(idx)` on a new iterator and
+    /// afterwards, iteration continues with the object at index `idx + 1`.
+    /// Returns `None` if `idx` is out of bounds.
+    pub fn seek(&mut self, idx: usize) -> Option<CatchDifficultyAttributes> {
+        if idx >= self.idx + self.len() {
+            return None;
+        }
+
+        if let Some((checkpoint_idx, checkpoint)) = self.checkpoints.closest(self.idx, idx) {
+            let Checkpoint {
+                attrs,
+                movement,
+                dashes,
+            } = checkpoint.clone();
+
+            self.idx = checkpoint_idx;
+            self.attrs = attrs;
+            self.movement = movement;
+            self.dashes = dashes;
+        }
+
+        self.nth(idx - self.idx)
+    }
+
+    /// Process all fruits and droplets whose time is at or before the timestamp `time`
+    /// in milliseconds and return the resulting difficulty attributes.
+    ///
+    /// The timestamp is adjusted by the clock rate so it should be the
+    /// elapsed time of a play, e.g. with DT an object at `1500` ms in the
+    /// map is reached after `1000` ms.
+    ///
+    /// Returns `None` if no new object was processed.
+    pub fn advance_to_time(&mut self, time: f64) -> Option<CatchDifficultyAttributes> {
+        let count = self.times.count_until(time);
+
+        if count <= self.idx {
+            return None;
+        }
+
+        self.nth(count - self.idx - 1)
+    }
+
+    /// Capture the progress of the calculation in a compact
+    /// [`GradualSnapshot`].
+    pub fn snapshot(&self) -> GradualSnapshot {
+        GradualSnapshot::new(
+            GameMode::Catch,
+            &self.difficulty,
+            self.idx + self.len(),
+            self.idx,
+            self.times.object_time(),
+            self.checkpoints.interval(),
+        )
+    }
+
+    /// Resume a calculation from a [`GradualSnapshot`].
+    ///
+    /// Returns `None` if the snapshot was created for a different mode or if
+    /// the map's amount of objects differs from the snapshot's.
+    pub fn from_snapshot(snapshot: &GradualSnapshot, converted: &CatchBeatmap<'_>) -> Option<Self> {
+        if snapshot.mode() != GameMode::Catch {
+            return None;
+        }
+
+        let mut gradual =
+            Self::new(snapshot.difficulty().clone(), converted).object_time(snapshot.object_time());
+
+        if gradual.len() != snapshot.n_objects() {
+            return None;
+        }
+
+        if let Some(interval) = snapshot.checkpoint_interval() {
+            gradual = gradual.checkpoint_interval(interval);
+        }
+
+        for _ in 0..snapshot.n_processed() {
+            gradual.advance()?;
+        }
+
+        Some(gradual)
+    }
+
+    /// Process the next palpable object without evaluating the skills.
+    fn advance(&mut self) -> Option<()> {
         // The first difficulty object belongs to the second palpable object
         // since each difficulty object requires the current and the last note.
         // Hence, if we're still on the first object, we don't have a difficulty
@@ -107,6 +250,7 @@ impl Iterator for CatchGradualDifficulty {
         if self.idx > 0 {
             let curr = self.diff_objects.get(self.idx - 1)?;
             Skill::new(&mut self.movement, &self.diff_objects).process(curr);
+            self.dashes.process(curr);
         } else if self.count.is_empty() {
             return None;
         }
@@ -114,7 +258,24 @@ impl Iterator for CatchGradualDifficulty {
         self.attrs.add_object_count(self.count[self.idx]);
         self.idx += 1;
 
+        self.checkpoints.save(self.idx, || Checkpoint {
+            attrs: self.attrs.clone(),
+            movement: self.movement.clone(),
+            dashes: self.dashes.clone(),
+        });
+
+        Some(())
+    }
+}
+
+impl Iterator for CatchGradualDifficulty {
+    type Item = CatchDifficultyAttributes;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance()?;
+
         let mut attrs = self.attrs.clone();
+        self.dashes.apply(&mut attrs);
 
         let movement = self.movement.as_difficulty_value();
         DifficultyValues::eval(&mut attrs, movement);
@@ -129,24 +290,8 @@ impl Iterator for CatchGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
-
-        let mut take = cmp::min(n, self.len().saturating_sub(1));
-
-        // The first palpable object has no difficulty object
-        if self.idx == 0 && take > 0 {
-            take -= 1;
-            self.attrs.add_object_count(self.count[self.idx]);
-            self.idx += 1;
-        }
-
-        let mut movement = Skill::new(&mut self.movement, &self.diff_objects);
-
-        for curr in skip_iter.take(take) {
-            movement.process(curr);
-
-            self.attrs.add_object_count(self.count[self.idx]);
-            self.idx += 1;
+        for _ in 0..cmp::min(n, self.len().saturating_sub(1)) {
+            self.advance()?;
         }
 
         self.next()
@@ -213,4 +358,54 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn seek() {
+        let converted = Beatmap::from_path("./resources/2118524.osu")
+            .unwrap()
+            .unchecked_into_converted();
+
+        let difficulty = Difficulty::new().mods(64);
+        let mut gradual =
+            CatchGradualDifficulty::new(difficulty.clone(), &converted).checkpoint_interval(16);
+        let len = gradual.len();
+
+        for idx in [100, 20, 150, 151, 0, len - 1, 31, 32, 33] {
+            let mut expected = CatchGradualDifficulty::new(difficulty.clone(), &converted);
+
+            assert_eq!(gradual.seek(idx), expected.nth(idx), "idx={idx}");
+            assert_eq!(gradual.next(), expected.next(), "idx={idx}");
+        }
+
+        assert!(gradual.seek(len).is_none());
+    }
+
+    #[test]
+    fn advance_to_time() {
+        let converted = Beatmap::from_path("./resources/2118524.osu")
+            .unwrap()
+            .unchecked_into_converted();
+
+        let difficulty = Difficulty::new().mods(64);
+        let mut gradual = CatchGradualDifficulty::new(difficulty.clone(), &converted);
+        let mut expected = CatchGradualDifficulty::new(difficulty, &converted);
+
+        assert!(gradual.advance_to_time(-1.0).is_none());
+
+        let mut prev_idx = 0;
+
+        for time in [2000.0, 10_000.0, 10_000.0, 40_000.0] {
+            let attrs = gradual.advance_to_time(time);
+
+            if gradual.idx == prev_idx {
+                assert!(attrs.is_none());
+            } else {
+                assert_eq!(attrs, expected.nth(gradual.idx - prev_idx - 1));
+            }
+
+            prev_idx = gradual.idx;
+        }
+
+        assert!(prev_idx > 0 && gradual.len() > 0);
+    }
 }
@@ -6,7 +6,7 @@ use crate::{
     model::beatmap::BeatmapAttributes,
 };
 
-use self::skills::movement::Movement;
+use self::{dashes::DashStatsBuilder, skills::movement::Movement};
 
 use super::{
     attributes::{CatchDifficultyAttributes, ObjectCountBuilder},
@@ -14,11 +14,12 @@ use super::{
     object::palpable::PalpableObject,
 };
 
+pub mod dashes;
 pub mod gradual;
 mod object;
-mod skills;
+pub mod skills;
 
-const STAR_SCALING_FACTOR: f64 = 0.153;
+pub const STAR_SCALING_FACTOR: f64 = 0.153;
 
 pub fn difficulty(
     difficulty: &Difficulty,
@@ -81,16 +82,19 @@ impl DifficultyValues {
         );
 
         let mut movement = Movement::new(clock_rate);
+        let mut dashes = DashStatsBuilder::default();
 
         {
             let mut movement = Skill::new(&mut movement, &diff_objects);
 
             for curr in diff_objects.iter() {
                 movement.process(curr);
+                dashes.process(curr);
             }
         }
 
         attrs.set_object_count(&count.into_regular());
+        dashes.apply(&mut attrs);
 
         Self { movement, attrs }
     }
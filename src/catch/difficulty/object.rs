@@ -6,6 +6,8 @@ pub struct CatchDifficultyObject {
     pub delta_time: f64,
     pub normalized_pos: f32,
     pub last_normalized_pos: f32,
+    /// The distance to the last object in osu!pixels.
+    pub dist: f32,
     pub strain_time: f64,
     pub last_object: LastObject,
 }
@@ -22,6 +24,7 @@ impl CatchDifficultyObject {
     ) -> Self {
         let normalized_pos = hit_object.effective_x() * scaling_factor;
         let last_normalized_pos = last_object.effective_x() * scaling_factor;
+        let dist = (hit_object.effective_x() - last_object.effective_x()).abs();
 
         let start_time = hit_object.start_time / clock_rate;
         let delta_time = (hit_object.start_time - last_object.start_time) / clock_rate;
@@ -38,6 +41,7 @@ impl CatchDifficultyObject {
             delta_time,
             normalized_pos,
             last_normalized_pos,
+            dist,
             strain_time,
             last_object,
         }
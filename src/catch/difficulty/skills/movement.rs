@@ -14,10 +14,14 @@ const DIRECTION_CHANGE_BONUS: f64 = 21.0;
 const SKILL_MULTIPLIER: f64 = 900.0;
 const STRAIN_DECAY_BASE: f64 = 0.2;
 
-const DECAY_WEIGHT: f64 = 0.94;
+pub const DECAY_WEIGHT: f64 = 0.94;
 
 const SECTION_LEN: f64 = 750.0;
 
+/// Objects that are closer than this to requiring a hyperdash are edge dashes.
+pub const EDGE_DASH_DIST: f32 = 20.0;
+
+#[derive(Clone)]
 pub struct Movement {
     inner: StrainDecaySkill,
     last_player_pos: Option<f32>,
@@ -88,7 +92,7 @@ impl Movement {
                 / sqrt_strain;
         }
 
-        if curr.last_object.dist_to_hyper_dash <= 20.0 {
+        if curr.last_object.dist_to_hyper_dash <= EDGE_DASH_DIST {
             if curr.last_object.hyper_dash {
                 player_pos = curr.normalized_pos;
             } else {
@@ -97,7 +101,9 @@ impl Movement {
 
             dist_addition *= 1.0
                 + edge_dash_bonus
-                    * f64::from((20.0 - curr.last_object.dist_to_hyper_dash) / 20.0)
+                    * f64::from(
+                        (EDGE_DASH_DIST - curr.last_object.dist_to_hyper_dash) / EDGE_DASH_DIST,
+                    )
                     * ((curr.strain_time * self.clock_rate).min(265.0) / 265.0).powf(1.5);
         }
 
@@ -112,6 +118,11 @@ impl Movement {
         self.inner.get_curr_strain_peaks()
     }
 
+    /// The end time of the current strain section.
+    pub const fn section_end(&self) -> f64 {
+        self.inner.inner.curr_section_end
+    }
+
     pub fn difficulty_value(self) -> f64 {
         Self::static_difficulty_value(self.inner)
     }
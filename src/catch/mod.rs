@@ -1,4 +1,5 @@
 use crate::{
+    any::HardestSection,
     model::{
         beatmap::Beatmap,
         mode::{ConvertStatus, IGameMode},
@@ -8,9 +9,15 @@ use crate::{
 
 pub use self::{
     attributes::{CatchDifficultyAttributes, CatchPerformanceAttributes},
+    catcher::{CatcherMovement, CatcherPosition},
     convert::CatchBeatmap,
-    difficulty::gradual::CatchGradualDifficulty,
-    performance::{gradual::CatchGradualPerformance, CatchPerformance},
+    difficulty::{
+        dashes::{CatchDashKind, CatchDashObject},
+        gradual::CatchGradualDifficulty,
+    },
+    performance::{
+        batch::CatchPerformanceBatch, gradual::CatchGradualPerformance, CatchPerformance,
+    },
     score_state::CatchScoreState,
     strains::CatchStrains,
 };
@@ -57,6 +64,14 @@ impl IGameMode for Catch {
         strains::strains(difficulty, converted)
     }
 
+    fn hardest_section(
+        difficulty: &Difficulty,
+        converted: &CatchBeatmap<'_>,
+        duration: f64,
+    ) -> Option<HardestSection> {
+        strains::hardest_section(difficulty, converted, duration)
+    }
+
     fn performance(map: CatchBeatmap<'_>) -> Self::Performance<'_> {
         CatchPerformance::new(map)
     }
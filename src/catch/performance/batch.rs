@@ -0,0 +1,85 @@
+use crate::catch::{CatchDifficultyAttributes, CatchPerformanceAttributes, CatchScoreState};
+
+use super::{CatchPerformanceInner, CatchPerformanceInvariants};
+
+/// Calculate the performance attributes of many scores on the same osu!catch
+/// map.
+///
+/// All values that only depend on the map and mods are computed once upfront
+/// so this is considerably faster than creating a [`CatchPerformance`] for
+/// each score while producing the same results.
+///
+/// Note that score states are used as is, i.e. unlike [`CatchPerformance`],
+/// missing hitresults are not generated so every state should be complete.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty};
+/// use rosu_pp::catch::{Catch, CatchPerformanceBatch, CatchScoreState};
+///
+/// let converted = Beatmap::from_path("./resources/2118524.osu")
+///     .unwrap()
+///     .unchecked_into_converted::<Catch>();
+///
+/// let mods = 8 + 64; // HDDT
+/// let attrs = Difficulty::new().mods(mods).with_mode().calculate(&converted);
+///
+/// let states: Vec<_> = (0..10)
+///     .map(|misses| CatchScoreState {
+///         max_combo: attrs.max_combo() / (misses + 1),
+///         fruits: attrs.n_fruits - misses,
+///         droplets: attrs.n_droplets,
+///         tiny_droplets: attrs.n_tiny_droplets,
+///         tiny_droplet_misses: 0,
+///         misses,
+///     })
+///     .collect();
+///
+/// let batch = CatchPerformanceBatch::new(attrs, mods);
+///
+/// for performance in batch.calculate(&states) {
+///     println!("PP: {}", performance.pp);
+/// }
+/// ```
+///
+/// [`CatchPerformance`]: crate::catch::CatchPerformance
+pub struct CatchPerformanceBatch {
+    attrs: CatchDifficultyAttributes,
+    mods: u32,
+    invariants: CatchPerformanceInvariants,
+}
+
+impl CatchPerformanceBatch {
+    /// Prepare the batch calculation for the given difficulty attributes.
+    ///
+    /// `mods` must be the same mods that the attributes were calculated with.
+    pub fn new(attrs: CatchDifficultyAttributes, mods: u32) -> Self {
+        let invariants = CatchPerformanceInvariants::new(&attrs, mods);
+
+        Self {
+            attrs,
+            mods,
+            invariants,
+        }
+    }
+
+    /// Calculate the performance attributes for each score state.
+    pub fn calculate(&self, states: &[CatchScoreState]) -> Vec<CatchPerformanceAttributes> {
+        states
+            .iter()
+            .map(|state| self.calculate_single(state))
+            .collect()
+    }
+
+    /// Calculate the performance attributes for a single score state.
+    pub fn calculate_single(&self, state: &CatchScoreState) -> CatchPerformanceAttributes {
+        let inner = CatchPerformanceInner {
+            attrs: self.attrs.clone(),
+            mods: self.mods,
+            state: state.clone(),
+        };
+
+        inner.calculate_with(&self.invariants)
+    }
+}
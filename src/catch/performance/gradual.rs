@@ -1,5 +1,7 @@
 use crate::{
+    any::GradualSnapshot,
     catch::{CatchBeatmap, CatchGradualDifficulty, CatchPerformanceAttributes, CatchScoreState},
+    model::hit_object::ObjectTime,
     Difficulty,
 };
 
@@ -96,6 +98,32 @@ impl CatchGradualPerformance {
         Self { difficulty }
     }
 
+    /// Store a snapshot of the state after every `interval` processed
+    /// objects so that [`seek`] can jump back and forth quickly.
+    ///
+    /// See [`CatchGradualDifficulty::checkpoint_interval`] for more information.
+    ///
+    /// [`seek`]: Self::seek
+    #[must_use]
+    pub fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.difficulty = self.difficulty.checkpoint_interval(interval);
+
+        self
+    }
+
+    /// Specify whether [`advance_to_time`] compares timestamps against the
+    /// start or the end time of objects.
+    ///
+    /// See [`CatchGradualDifficulty::object_time`] for more information.
+    ///
+    /// [`advance_to_time`]: Self::advance_to_time
+    #[must_use]
+    pub fn object_time(mut self, object_time: ObjectTime) -> Self {
+        self.difficulty = self.difficulty.object_time(object_time);
+
+        self
+    }
+
     /// Process the next hit object and calculate the performance attributes
     /// for the resulting score state.
     ///
@@ -129,6 +157,64 @@ impl CatchGradualPerformance {
         Some(performance)
     }
 
+    /// Calculate the performance attributes after the fruit or droplet at index `idx`
+    /// for the given score state, regardless of how many objects were
+    /// processed so far.
+    ///
+    /// See [`CatchGradualDifficulty::seek`] for more information.
+    pub fn seek(
+        &mut self,
+        state: CatchScoreState,
+        idx: usize,
+    ) -> Option<CatchPerformanceAttributes> {
+        let performance = self
+            .difficulty
+            .seek(idx)?
+            .performance()
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .calculate();
+
+        Some(performance)
+    }
+
+    /// Process all objects whose time is at or before the timestamp `time`
+    /// in milliseconds and calculate the performance attributes for the
+    /// given score state.
+    ///
+    /// See [`CatchGradualDifficulty::advance_to_time`] for more information.
+    pub fn advance_to_time(
+        &mut self,
+        state: CatchScoreState,
+        time: f64,
+    ) -> Option<CatchPerformanceAttributes> {
+        let performance = self
+            .difficulty
+            .advance_to_time(time)?
+            .performance()
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .calculate();
+
+        Some(performance)
+    }
+
+    /// Capture the progress of the calculation in a compact
+    /// [`GradualSnapshot`].
+    pub fn snapshot(&self) -> GradualSnapshot {
+        self.difficulty.snapshot()
+    }
+
+    /// Resume a calculation from a [`GradualSnapshot`].
+    ///
+    /// See [`CatchGradualDifficulty::from_snapshot`] for more information.
+    pub fn from_snapshot(snapshot: &GradualSnapshot, converted: &CatchBeatmap<'_>) -> Option<Self> {
+        CatchGradualDifficulty::from_snapshot(snapshot, converted)
+            .map(|difficulty| Self { difficulty })
+    }
+
     /// Returns the amount of remaining objects.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
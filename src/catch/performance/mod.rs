@@ -3,7 +3,11 @@ use std::cmp::{self, Ordering};
 use crate::{
     any::{Difficulty, IntoModePerformance, IntoPerformance},
     osu::OsuPerformance,
-    util::{map_or_attrs::MapOrAttrs, mods::Mods},
+    util::{
+        map_or_attrs::MapOrAttrs,
+        mods::Mods,
+        solver::{self, Solvable},
+    },
     Performance,
 };
 
@@ -13,6 +17,7 @@ use super::{
     Catch,
 };
 
+pub mod batch;
 pub mod gradual;
 
 /// Performance calculator on osu!catch maps.
@@ -400,7 +405,12 @@ impl<'map> CatchPerformance<'map> {
     }
 
     /// Calculate all performance related values, including pp and stars.
-    pub fn calculate(mut self) -> CatchPerformanceAttributes {
+    pub fn calculate(self) -> CatchPerformanceAttributes {
+        self.inner().calculate()
+    }
+
+    /// Generate the score state and pair it with the difficulty attributes.
+    fn inner(mut self) -> CatchPerformanceInner {
         let state = self.generate_state();
 
         let attrs = match self.map_or_attrs {
@@ -408,13 +418,30 @@ impl<'map> CatchPerformance<'map> {
             MapOrAttrs::Attrs(attrs) => attrs,
         };
 
-        let inner = CatchPerformanceInner {
+        CatchPerformanceInner {
             attrs,
             mods: self.difficulty.get_mods(),
             state,
-        };
+        }
+    }
 
-        inner.calculate()
+    /// Find the minimal accuracy, in percent, for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Since explicitly specified hitresults take precedence over the
+    /// accuracy, they should generally be left unspecified.
+    ///
+    /// Returns `None` if `target_pp` is not reached even with 100% accuracy.
+    pub fn accuracy_for_pp(self, target_pp: f64) -> Option<f64> {
+        solver::accuracy_for_pp(self, target_pp)
+    }
+
+    /// Find the maximal amount of misses for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Returns `None` if `target_pp` is not reached even without misses.
+    pub fn misses_for_pp(self, target_pp: f64) -> Option<u32> {
+        solver::misses_for_pp(self, target_pp)
     }
 
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Catch>) -> Self {
@@ -463,6 +490,8 @@ impl<'map> TryFrom<OsuPerformance<'map>> for CatchPerformance<'map> {
             n100,
             n50,
             misses,
+            spinner_bonus: _,
+            spun_out: _,
             hitresult_priority: _,
         } = osu;
 
@@ -486,6 +515,39 @@ impl<'map, T: IntoModePerformance<'map, Catch>> From<T> for CatchPerformance<'ma
     }
 }
 
+impl Solvable for CatchPerformance<'_> {
+    type Invariants = CatchPerformanceInvariants;
+
+    fn invariants(&mut self) -> Self::Invariants {
+        let attrs = match self.map_or_attrs {
+            MapOrAttrs::Map(ref map) => {
+                let attrs = self.difficulty.with_mode().calculate(map);
+
+                self.map_or_attrs.insert_attrs(attrs)
+            }
+            MapOrAttrs::Attrs(ref attrs) => attrs,
+        };
+
+        CatchPerformanceInvariants::new(attrs, self.difficulty.get_mods())
+    }
+
+    fn with_accuracy(self, acc: f64) -> Self {
+        self.accuracy(acc)
+    }
+
+    fn with_misses(self, misses: u32) -> Self {
+        self.misses(misses)
+    }
+
+    fn max_misses(&self) -> u32 {
+        self.clone().misses(u32::MAX).generate_state().misses
+    }
+
+    fn pp(self, invariants: &Self::Invariants) -> f64 {
+        self.inner().calculate_with(invariants).pp
+    }
+}
+
 struct CatchPerformanceInner {
     attrs: CatchDifficultyAttributes,
     mods: u32,
@@ -494,25 +556,23 @@ struct CatchPerformanceInner {
 
 impl CatchPerformanceInner {
     fn calculate(self) -> CatchPerformanceAttributes {
-        let attributes = &self.attrs;
-        let stars = attributes.stars;
-        let max_combo = attributes.max_combo();
+        let invariants = CatchPerformanceInvariants::new(&self.attrs, self.mods);
+
+        self.calculate_with(&invariants)
+    }
 
+    fn calculate_with(self, invariants: &CatchPerformanceInvariants) -> CatchPerformanceAttributes {
         // Relying heavily on aim
-        let mut pp = (5.0 * (stars / 0.0049).max(1.0) - 4.0).powf(2.0) / 100_000.0;
+        let mut pp = invariants.base_pp;
 
         let mut combo_hits = self.combo_hits();
 
         if combo_hits == 0 {
-            combo_hits = max_combo;
+            combo_hits = invariants.max_combo;
         }
 
         // Longer maps are worth more
-        let mut len_bonus = 0.95 + 0.3 * (f64::from(combo_hits) / 2500.0).min(1.0);
-
-        if combo_hits > 2500 {
-            len_bonus += (f64::from(combo_hits) / 2500.0).log10() * 0.475;
-        }
+        let len_bonus = invariants.len_bonus(combo_hits);
 
         pp *= len_bonus;
 
@@ -521,27 +581,16 @@ impl CatchPerformanceInner {
 
         // Combo scaling
         if self.state.max_combo > 0 {
-            pp *= (f64::from(self.state.max_combo).powf(0.8) / f64::from(max_combo).powf(0.8))
-                .min(1.0);
+            pp *=
+                (f64::from(self.state.max_combo).powf(0.8) / invariants.max_combo_scaling).min(1.0);
         }
 
         // AR scaling
-        let ar = attributes.ar;
-        let mut ar_factor = 1.0;
-        if ar > 9.0 {
-            ar_factor += 0.1 * (ar - 9.0) + f64::from(u8::from(ar > 10.0)) * 0.1 * (ar - 10.0);
-        } else if ar < 8.0 {
-            ar_factor += 0.025 * (8.0 - ar);
-        }
-        pp *= ar_factor;
+        pp *= invariants.ar_factor;
 
         // HD bonus
-        if self.mods.hd() {
-            if ar <= 10.0 {
-                pp *= 1.05 + 0.075 * (10.0 - ar);
-            } else if ar > 10.0 {
-                pp *= 1.01 + 0.04 * (11.0 - ar.min(11.0));
-            }
+        if let Some(hd_factor) = invariants.hd_factor {
+            pp *= hd_factor;
         }
 
         // FL bonus
@@ -568,6 +617,69 @@ impl CatchPerformanceInner {
     }
 }
 
+/// Values of the performance calculation that only depend on the difficulty
+/// attributes and mods.
+pub(crate) struct CatchPerformanceInvariants {
+    base_pp: f64,
+    max_combo: u32,
+    max_combo_scaling: f64,
+    ar_factor: f64,
+    hd_factor: Option<f64>,
+    full_len_bonus: f64,
+}
+
+impl CatchPerformanceInvariants {
+    fn new(attrs: &CatchDifficultyAttributes, mods: u32) -> Self {
+        let max_combo = attrs.max_combo();
+        let ar = attrs.ar;
+
+        let mut ar_factor = 1.0;
+
+        if ar > 9.0 {
+            ar_factor += 0.1 * (ar - 9.0) + f64::from(u8::from(ar > 10.0)) * 0.1 * (ar - 10.0);
+        } else if ar < 8.0 {
+            ar_factor += 0.025 * (8.0 - ar);
+        }
+
+        let hd_factor = if !mods.hd() {
+            None
+        } else if ar <= 10.0 {
+            Some(1.05 + 0.075 * (10.0 - ar))
+        } else if ar > 10.0 {
+            Some(1.01 + 0.04 * (11.0 - ar.min(11.0)))
+        } else {
+            None
+        };
+
+        Self {
+            base_pp: (5.0 * (attrs.stars / 0.0049).max(1.0) - 4.0).powf(2.0) / 100_000.0,
+            max_combo,
+            max_combo_scaling: f64::from(max_combo).powf(0.8),
+            ar_factor,
+            hd_factor,
+            full_len_bonus: Self::compute_len_bonus(max_combo),
+        }
+    }
+
+    fn compute_len_bonus(combo_hits: u32) -> f64 {
+        let mut len_bonus = 0.95 + 0.3 * (f64::from(combo_hits) / 2500.0).min(1.0);
+
+        if combo_hits > 2500 {
+            len_bonus += (f64::from(combo_hits) / 2500.0).log10() * 0.475;
+        }
+
+        len_bonus
+    }
+
+    fn len_bonus(&self, combo_hits: u32) -> f64 {
+        if combo_hits == self.max_combo {
+            self.full_len_bonus
+        } else {
+            Self::compute_len_bonus(combo_hits)
+        }
+    }
+}
+
 fn accuracy(
     n_fruits: u32,
     n_droplets: u32,
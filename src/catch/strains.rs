@@ -1,4 +1,7 @@
-use crate::{any::Difficulty, catch::difficulty::DifficultyValues};
+use crate::{
+    any::{Difficulty, HardestSection, StrainTimeline},
+    catch::difficulty::{skills::movement::DECAY_WEIGHT, DifficultyValues, STAR_SCALING_FACTOR},
+};
 
 use super::convert::CatchBeatmap;
 
@@ -14,6 +17,18 @@ pub struct CatchStrains {
 impl CatchStrains {
     /// Time between two strains in ms.
     pub const SECTION_LEN: f64 = 750.0;
+
+    /// Turn the strain peaks into a smoothed difficulty curve.
+    ///
+    /// See [`StrainTimeline`] for more information.
+    #[must_use]
+    pub fn timeline(&self, timeline: &StrainTimeline) -> Self {
+        Self {
+            movement: timeline.smooth(&self.movement, Self::SECTION_LEN, DECAY_WEIGHT, |value| {
+                value.sqrt() * STAR_SCALING_FACTOR
+            }),
+        }
+    }
 }
 
 pub fn strains(difficulty: &Difficulty, converted: &CatchBeatmap<'_>) -> CatchStrains {
@@ -23,3 +38,20 @@ pub fn strains(difficulty: &Difficulty, converted: &CatchBeatmap<'_>) -> CatchSt
         movement: movement.get_curr_strain_peaks().into_vec(),
     }
 }
+
+pub fn hardest_section(
+    difficulty: &Difficulty,
+    converted: &CatchBeatmap<'_>,
+    duration: f64,
+) -> Option<HardestSection> {
+    let DifficultyValues { movement, .. } = DifficultyValues::calculate(difficulty, converted);
+    let section_end = movement.section_end();
+
+    HardestSection::new(
+        &movement.get_curr_strain_peaks().into_vec(),
+        CatchStrains::SECTION_LEN,
+        section_end,
+        difficulty.get_clock_rate(),
+        duration,
+    )
+}
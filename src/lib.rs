@@ -119,6 +119,14 @@
 //! Calculating performances: Median: 44.13µs | Mean: 45.53µs
 //! ```
 //!
+//! The `benches` directory contains [criterion] benchmarks for the difficulty,
+//! performance, gradual, and strain calculation of each mode, including a
+//! synthetic map with 50,000 objects. After `cargo bench`, `cargo bench --bench regression`
+//! compares the results with the stored `benches/baseline.json` and
+//! `cargo bench --bench regression -- --save` updates it. To compare the
+//! `compact_strains` feature, run `cargo bench --bench strains` both with and
+//! without `--no-default-features`.
+//!
 //! ## Features
 //!
 //! | Flag              | Description                           | Dependencies
@@ -126,6 +134,7 @@
 //! | `default`         | Enables the `compact_strains` feature |
 //! | `compact_strains` | Storing internal strain values in a plain Vec introduces an out-of-memory risk on maliciously long maps (see [/b/3739922](https://osu.ppy.sh/b/3739922)). This feature stores strains more compactly, but comes with a ~5% loss in performance. |
 //! | `sync`            | Some gradual calculation types can only be shared across threads if this feature is enabled. This adds a performance penalty so only enable this if really needed. |
+//! | `parallel`        | Processes independent osu!standard skills on separate threads. Results are identical to the sequential calculation. |
 //! | `tracing`         | Any error encountered during beatmap decoding will be logged through `tracing::error`. If this feature is **not** enabled, errors will be ignored. | [`tracing`]
 //!
 //! ## Bindings
@@ -141,6 +150,7 @@
 //! [rosu-pp-js]: https://github.com/MaxOhn/rosu-pp-js
 //! [rosu-pp-py]: https://github.com/MaxOhn/rosu-pp-py
 //! [benchmark]: https://gist.github.com/MaxOhn/625af10011f6d7e13a171b08ccf959ff
+//! [criterion]: https://docs.rs/criterion
 //! [`GradualDifficulty`]: crate::any::GradualDifficulty
 //! [`GradualPerformance`]: crate::any::GradualPerformance
 
@@ -162,7 +172,7 @@
 
 #[doc(inline)]
 pub use self::{
-    any::{Difficulty, GradualDifficulty, GradualPerformance, Performance},
+    any::{Difficulty, GradualDifficulty, GradualPerformance, Performance, PerformanceBatch},
     model::beatmap::{Beatmap, Converted},
 };
 
@@ -184,4 +194,7 @@ pub mod mania;
 /// Types used in and around this crate.
 pub mod model;
 
+/// Aggregation of scores into profile totals.
+pub mod profile;
+
 mod util;
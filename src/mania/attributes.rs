@@ -5,10 +5,22 @@ use crate::mania::performance::ManiaPerformance;
 pub struct ManiaDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
+    /// The difficulty of the jack skillset.
+    pub jack: f64,
+    /// The difficulty of the chord skillset.
+    pub chord: f64,
+    /// The difficulty of the stream and jumpstream skillset.
+    pub stream: f64,
+    /// The difficulty of the technical skillset.
+    pub technical: f64,
+    /// The difficulty of the long note skillset.
+    pub long_note: f64,
     /// The perceived hit window for an n300 inclusive of rate-adjusting mods (DT/HT/etc).
     pub hit_window: f64,
     /// The amount of hitobjects in the map.
     pub n_objects: u32,
+    /// The amount of long notes in the map.
+    pub n_hold_notes: u32,
     /// The maximum achievable combo.
     pub max_combo: u32,
     /// Whether the [`Beatmap`] was a convert i.e. an osu!standard map.
@@ -50,6 +62,16 @@ pub struct ManiaPerformanceAttributes {
     pub pp: f64,
     /// The difficulty portion of the final pp.
     pub pp_difficulty: f64,
+    /// The jack portion of the final pp.
+    pub pp_jack: f64,
+    /// The chord portion of the final pp.
+    pub pp_chord: f64,
+    /// The stream and jumpstream portion of the final pp.
+    pub pp_stream: f64,
+    /// The technical portion of the final pp.
+    pub pp_technical: f64,
+    /// The long note portion of the final pp.
+    pub pp_long_note: f64,
 }
 
 impl ManiaPerformanceAttributes {
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use rosu_map::{
     section::{general::GameMode, hit_objects::CurveBuffers},
     util::Pos,
@@ -9,7 +11,7 @@ use crate::{
         hit_object::{HitObjectKind, HoldNote, Spinner},
         mode::ConvertStatus,
     },
-    util::{limited_queue::LimitedQueue, random::Random, sort},
+    util::{float_ext::FloatExt, limited_queue::LimitedQueue, mods::Mods, random::Random, sort},
 };
 
 use self::{
@@ -28,6 +30,13 @@ mod pattern_generator;
 mod pattern_type;
 
 /// A [`Beatmap`] for [`Mania`] calculations.
+///
+/// If the map was converted from a [`&Beatmap`] of osu!standard, key mods and
+/// the coop mod of a calculation determine the amount of columns. Maps that
+/// were converted from an owned or mutable [`Beatmap`] keep their columns;
+/// use [`ManiaBeatmap::try_from_owned_with_mods`] to apply key mods to them.
+///
+/// [`&Beatmap`]: Beatmap
 pub type ManiaBeatmap<'a> = Converted<'a, Mania>;
 
 const MAX_NOTES_FOR_DENSITY: usize = 7;
@@ -41,9 +50,17 @@ pub const fn check_convert(map: &Beatmap) -> ConvertStatus {
 }
 
 pub fn try_convert(map: &mut Beatmap) -> ConvertStatus {
+    try_convert_with_mods(map, 0)
+}
+
+/// Same as [`try_convert`] but key mods and the coop mod determine the amount
+/// of columns.
+///
+/// Just like lazer, the mods are ignored for maps that are not converted.
+pub fn try_convert_with_mods(map: &mut Beatmap, mods: u32) -> ConvertStatus {
     match map.mode {
         GameMode::Osu => {
-            convert(map);
+            convert(map, mods);
 
             ConvertStatus::Conversion
         }
@@ -52,14 +69,14 @@ pub fn try_convert(map: &mut Beatmap) -> ConvertStatus {
     }
 }
 
-fn convert(map: &mut Beatmap) {
+fn convert(map: &mut Beatmap, mods: u32) {
     let seed = (map.hp + map.cs).round_ties_even() as i32 * 20
         + (map.od * 41.2) as i32
         + map.ar.round_ties_even() as i32;
 
     let mut random = Random::new(seed);
 
-    map.cs = target_columns(map);
+    map.cs = total_columns(map, mods);
 
     let mut prev_note_times = LimitedQueue::<f64, MAX_NOTES_FOR_DENSITY>::new();
     let mut density = f64::from(i32::MAX);
@@ -173,6 +190,70 @@ fn convert(map: &mut Beatmap) {
     map.is_convert = true;
 }
 
+impl<'a> ManiaBeatmap<'a> {
+    /// Attempt to convert a [`&Beatmap`] to osu!mania while considering key
+    /// mods (K1 to K9) and the coop mod.
+    ///
+    /// Key mods only affect converted maps; osu!mania maps keep their amount
+    /// of columns. If the conversion is incompatible, `None` is returned.
+    ///
+    /// [`&Beatmap`]: Beatmap
+    pub fn try_from_ref_with_mods(map: &'a Beatmap, mods: u32) -> Option<Self> {
+        let mut converted = match check_convert(map) {
+            ConvertStatus::Noop => return Some(Self::new(Cow::Borrowed(map))),
+            ConvertStatus::Conversion => map.to_owned(),
+            ConvertStatus::Incompatible => return None,
+        };
+
+        match try_convert_with_mods(&mut converted, mods) {
+            ConvertStatus::Conversion => Some(Self::with_source(Cow::Owned(converted), map)),
+            ConvertStatus::Noop => Some(Self::new(Cow::Owned(converted))),
+            ConvertStatus::Incompatible => None,
+        }
+    }
+
+    /// Attempt to convert a [`Beatmap`] to osu!mania while considering key
+    /// mods (K1 to K9) and the coop mod.
+    ///
+    /// Key mods only affect converted maps; osu!mania maps keep their amount
+    /// of columns. If the conversion is incompatible the [`Beatmap`] will be
+    /// returned unchanged as `Err`.
+    ///
+    /// Since the map is converted in place, calculations with different key
+    /// mods won't convert it again.
+    #[allow(clippy::result_large_err)]
+    pub fn try_from_owned_with_mods(mut map: Beatmap, mods: u32) -> Result<Self, Beatmap> {
+        match check_convert(&map) {
+            ConvertStatus::Conversion => {
+                convert(&mut map, mods);
+
+                Ok(Self::new(Cow::Owned(map)))
+            }
+            ConvertStatus::Noop => Ok(Self::new(Cow::Owned(map))),
+            ConvertStatus::Incompatible => Err(map),
+        }
+    }
+
+    /// The map as converted for the given mods.
+    ///
+    /// If the map was converted from a borrowed osu!standard map and key mods
+    /// or the coop mod require a different amount of columns, the original
+    /// map is converted again. Otherwise, the map is borrowed.
+    pub(crate) fn with_conversion_mods(&self, mods: u32) -> ManiaBeatmap<'_> {
+        let affects_columns = mods.key_count().is_some() || mods.co();
+
+        match self.source() {
+            Some(source) if affects_columns && total_columns(source, mods).not_eq(self.cs) => {
+                let mut map = source.to_owned();
+                convert(&mut map, mods);
+
+                ManiaBeatmap::with_source(Cow::Owned(map), source)
+            }
+            _ => ManiaBeatmap::new(Cow::Borrowed(self)),
+        }
+    }
+}
+
 pub struct PrevValues {
     time: f64,
     pos: Pos,
@@ -191,6 +272,20 @@ impl Default for PrevValues {
     }
 }
 
+/// The amount of columns across all stages.
+fn total_columns(map: &Beatmap, mods: u32) -> f32 {
+    let target_columns = mods
+        .key_count()
+        .map_or_else(|| target_columns(map), f32::from);
+
+    // * Dual stages (coop) double the amount of columns
+    if mods.co() {
+        target_columns * 2.0
+    } else {
+        target_columns
+    }
+}
+
 fn target_columns(map: &Beatmap) -> f32 {
     let rounded_cs = map.cs.round_ties_even();
     let rounded_od = map.od.round_ties_even();
@@ -217,7 +312,10 @@ fn target_columns(map: &Beatmap) -> f32 {
 
 #[cfg(test)]
 mod tests {
-    use crate::util::float_ext::FloatExt;
+    use crate::{
+        mania::{object::ManiaObject, ManiaGradualDifficulty},
+        Difficulty, Performance,
+    };
 
     use super::*;
 
@@ -254,4 +352,73 @@ mod tests {
         assert!(map.stack_leniency.eq(0.5), "{} != 0.5", map.stack_leniency);
         assert_eq!(map.breaks.len(), 1);
     }
+
+    #[test]
+    fn key_mods() {
+        const K4: u32 = 1 << 15;
+        const K9: u32 = 1 << 24;
+        const COOP: u32 = 1 << 25;
+
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        for (mods, total_columns) in [(0, 7), (K4, 4), (K9, 9), (K4 + COOP, 8), (COOP, 14)] {
+            let converted = ManiaBeatmap::try_from_ref_with_mods(&map, mods).unwrap();
+
+            assert!(
+                converted.cs.eq(total_columns as f32),
+                "{} != {total_columns}",
+                converted.cs
+            );
+
+            assert!(converted
+                .hit_objects
+                .iter()
+                .all(|h| ManiaObject::column(h.pos.x, converted.cs) < total_columns));
+        }
+
+        let nomod = ManiaBeatmap::try_from_ref_with_mods(&map, 0).unwrap();
+        assert_eq!(nomod, map.unchecked_as_converted());
+
+        // Key mods don't apply to osu!mania maps
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+        let converted = ManiaBeatmap::try_from_owned_with_mods(map, K9).unwrap();
+        assert!(converted.cs.eq(4.0), "{} != 4.0", converted.cs);
+    }
+
+    #[test]
+    fn key_mods_through_difficulty() {
+        const K4: u32 = 1 << 15;
+
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new().mods(K4);
+
+        let k4_converted = ManiaBeatmap::try_from_ref_with_mods(&map, K4).unwrap();
+        let expected = difficulty.with_mode().calculate(&k4_converted);
+
+        let converted = map.unchecked_as_converted::<Mania>();
+        assert!(converted.cs.eq(7.0), "{} != 7.0", converted.cs);
+        assert_eq!(difficulty.with_mode().calculate(&converted), expected);
+
+        // Owned maps are converted in place so they need the mods upfront
+        let owned = map.clone().unchecked_into_converted::<Mania>();
+        assert!(owned.source().is_none());
+        assert_ne!(difficulty.with_mode().calculate(&owned), expected);
+
+        let owned = ManiaBeatmap::try_from_owned_with_mods(map.clone(), K4).unwrap();
+        assert_eq!(difficulty.with_mode().calculate(&owned), expected);
+
+        let mut gradual = ManiaGradualDifficulty::new(difficulty.clone(), &converted);
+        assert_eq!(gradual.last(), Some(expected.clone()));
+
+        let performance = Performance::new(&map)
+            .mode_or_ignore(GameMode::Mania)
+            .mods(K4)
+            .calculate();
+
+        assert_eq!(performance.stars(), expected.stars);
+        assert_ne!(
+            Difficulty::new().with_mode().calculate(&converted),
+            expected
+        );
+    }
 }
@@ -1,15 +1,23 @@
 use std::cmp;
 
+use rosu_map::section::general::GameMode;
+
 use crate::{
     any::difficulty::skills::Skill,
+    any::GradualSnapshot,
     mania::{object::ObjectParams, ManiaBeatmap},
-    model::{beatmap::HitWindows, hit_object::HitObject},
+    model::{
+        beatmap::HitWindows,
+        hit_object::{HitObject, ObjectTime},
+    },
+    util::{checkpoints::Checkpoints, object_times::ObjectTimes},
     Difficulty,
 };
 
 use super::{
-    object::ManiaDifficultyObject, skills::strain::Strain, DifficultyValues,
-    ManiaDifficultyAttributes, ManiaObject, STAR_SCALING_FACTOR,
+    object::ManiaDifficultyObject,
+    skills::{skillset::Skillsets, strain::Strain},
+    DifficultyValues, ManiaDifficultyAttributes, ManiaObject, STAR_SCALING_FACTOR,
 };
 
 /// Gradually calculate the difficulty attributes of an osu!mania map.
@@ -52,14 +60,29 @@ pub struct ManiaGradualDifficulty {
     objects_is_circle: Box<[bool]>,
     is_convert: bool,
     strain: Strain,
+    skillsets: Skillsets,
     diff_objects: Box<[ManiaDifficultyObject]>,
     hit_window: f64,
     curr_combo: u32,
+    n_hold_notes: u32,
+    checkpoints: Checkpoints<Checkpoint>,
+    times: ObjectTimes,
+}
+
+/// The part of [`ManiaGradualDifficulty`] that changes while processing
+/// objects.
+#[derive(Clone)]
+struct Checkpoint {
+    strain: Strain,
+    skillsets: Skillsets,
+    curr_combo: u32,
+    n_hold_notes: u32,
 }
 
 impl ManiaGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!mania maps.
     pub fn new(difficulty: Difficulty, converted: &ManiaBeatmap<'_>) -> Self {
+        let converted = &converted.with_conversion_mods(difficulty.get_mods());
         let take = difficulty.get_passed_objects();
         let total_columns = converted.cs.round_ties_even().max(1.0);
         let clock_rate = difficulty.get_clock_rate();
@@ -77,6 +100,7 @@ impl ManiaGradualDifficulty {
         let diff_objects = DifficultyValues::create_difficulty_objects(clock_rate, mania_objects);
 
         let strain = Strain::new(total_columns as usize);
+        let skillsets = Skillsets::new(total_columns as usize);
 
         let mut curr_combo = 0;
 
@@ -97,23 +121,158 @@ impl ManiaGradualDifficulty {
             );
         }
 
+        let n_hold_notes = objects_is_circle
+            .first()
+            .map_or(0, |is_circle| u32::from(!is_circle));
+
+        let times = ObjectTimes::new(
+            converted
+                .hit_objects
+                .iter()
+                .map(|h| (h.start_time, h.end_time())),
+            clock_rate,
+        );
+
+        let checkpoints = Checkpoints::new(Checkpoint {
+            strain: strain.clone(),
+            skillsets: skillsets.clone(),
+            curr_combo,
+            n_hold_notes,
+        });
+
         Self {
             idx: 0,
             difficulty,
             objects_is_circle,
             is_convert: converted.is_convert,
             strain,
+            skillsets,
             diff_objects,
             hit_window,
             curr_combo,
+            n_hold_notes,
+            checkpoints,
+            times,
         }
     }
-}
 
-impl Iterator for ManiaGradualDifficulty {
-    type Item = ManiaDifficultyAttributes;
+    /// Store a snapshot of the state after every `interval` processed hit
+    /// objects so that [`seek`] can jump back and forth quickly.
+    ///
+    /// Smaller intervals make seeking faster but require more memory.
+    /// Without an interval, no snapshots are stored and seeking backwards
+    /// restarts from the first object.
+    ///
+    /// [`seek`]: Self::seek
+    #[must_use]
+    pub fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Specify whether [`advance_to_time`] compares timestamps against the
+    /// start or the end time of hit objects.
+    ///
+    /// Defaults to [`ObjectTime::Start`].
+    ///
+    /// [`advance_to_time`]: Self::advance_to_time
+    #[must_use]
+    pub const fn object_time(mut self, object_time: ObjectTime) -> Self {
+        self.times.set_object_time(object_time);
+
+        self
+    }
+
+    /// Calculate the difficulty attributes after the hit object at index
+    /// `idx`, regardless of how many hit objects were processed so far.
+    ///
+    /// The result is the same as calling `nth(idx)` on a new iterator and
+    /// afterwards, iteration continues with the object at index `idx + 1`.
+    /// Returns `None` if `idx` is out of bounds.
+    pub fn seek(&mut self, idx: usize) -> Option<ManiaDifficultyAttributes> {
+        if idx >= self.idx + self.len() {
+            return None;
+        }
+
+        if let Some((checkpoint_idx, checkpoint)) = self.checkpoints.closest(self.idx, idx) {
+            let Checkpoint {
+                strain,
+                skillsets,
+                curr_combo,
+                n_hold_notes,
+            } = checkpoint.clone();
+
+            self.idx = checkpoint_idx;
+            self.strain = strain;
+            self.skillsets = skillsets;
+            self.curr_combo = curr_combo;
+            self.n_hold_notes = n_hold_notes;
+        }
+
+        self.nth(idx - self.idx)
+    }
+
+    /// Process all hit objects whose time is at or before the timestamp `time`
+    /// in milliseconds and return the resulting difficulty attributes.
+    ///
+    /// The timestamp is adjusted by the clock rate so it should be the
+    /// elapsed time of a play, e.g. with DT an object at `1500` ms in the
+    /// map is reached after `1000` ms.
+    ///
+    /// Returns `None` if no new object was processed.
+    pub fn advance_to_time(&mut self, time: f64) -> Option<ManiaDifficultyAttributes> {
+        let count = self.times.count_until(time);
+
+        if count <= self.idx {
+            return None;
+        }
+
+        self.nth(count - self.idx - 1)
+    }
+
+    /// Capture the progress of the calculation in a compact
+    /// [`GradualSnapshot`].
+    pub fn snapshot(&self) -> GradualSnapshot {
+        GradualSnapshot::new(
+            GameMode::Mania,
+            &self.difficulty,
+            self.idx + self.len(),
+            self.idx,
+            self.times.object_time(),
+            self.checkpoints.interval(),
+        )
+    }
+
+    /// Resume a calculation from a [`GradualSnapshot`].
+    ///
+    /// Returns `None` if the snapshot was created for a different mode or if
+    /// the map's amount of objects differs from the snapshot's.
+    pub fn from_snapshot(snapshot: &GradualSnapshot, converted: &ManiaBeatmap<'_>) -> Option<Self> {
+        if snapshot.mode() != GameMode::Mania {
+            return None;
+        }
+
+        let mut gradual =
+            Self::new(snapshot.difficulty().clone(), converted).object_time(snapshot.object_time());
+
+        if gradual.len() != snapshot.n_objects() {
+            return None;
+        }
+
+        if let Some(interval) = snapshot.checkpoint_interval() {
+            gradual = gradual.checkpoint_interval(interval);
+        }
+
+        for _ in 0..snapshot.n_processed() {
+            gradual.advance()?;
+        }
+
+        Some(gradual)
+    }
+
+    /// Process the next hit object without evaluating the skills.
+    fn advance(&mut self) -> Option<()> {
         // The first difficulty object belongs to the second note since each
         // difficulty object requires the current and the last note. Hence, if
         // we're still on the first object, we don't have a difficulty object
@@ -121,8 +280,10 @@ impl Iterator for ManiaGradualDifficulty {
         if self.idx > 0 {
             let curr = self.diff_objects.get(self.idx - 1)?;
             Skill::new(&mut self.strain, &self.diff_objects).process(curr);
+            self.skillsets.process(curr, &self.diff_objects);
 
             let is_circle = self.objects_is_circle[self.idx];
+            self.n_hold_notes += u32::from(!is_circle);
             increment_combo(
                 is_circle,
                 curr,
@@ -135,11 +296,36 @@ impl Iterator for ManiaGradualDifficulty {
 
         self.idx += 1;
 
+        self.checkpoints.save(self.idx, || Checkpoint {
+            strain: self.strain.clone(),
+            skillsets: self.skillsets.clone(),
+            curr_combo: self.curr_combo,
+            n_hold_notes: self.n_hold_notes,
+        });
+
+        Some(())
+    }
+}
+
+impl Iterator for ManiaGradualDifficulty {
+    type Item = ManiaDifficultyAttributes;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance()?;
+
+        let [jack, chord, stream, technical, long_note] = self.skillsets.as_ratings();
+
         Some(ManiaDifficultyAttributes {
             stars: self.strain.as_difficulty_value() * STAR_SCALING_FACTOR,
+            jack,
+            chord,
+            stream,
+            technical,
+            long_note,
             hit_window: self.hit_window,
             max_combo: self.curr_combo,
             n_objects: self.idx as u32,
+            n_hold_notes: self.n_hold_notes,
             is_convert: self.is_convert,
         })
     }
@@ -151,27 +337,8 @@ impl Iterator for ManiaGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let skip_iter = self
-            .diff_objects
-            .iter()
-            .zip(self.objects_is_circle.iter().skip(1))
-            .skip(self.idx.saturating_sub(1));
-
-        let mut take = cmp::min(n, self.len().saturating_sub(1));
-
-        // The first note has no difficulty object
-        if self.idx == 0 && take > 0 {
-            take -= 1;
-            self.idx += 1;
-        }
-
-        let mut strain = Skill::new(&mut self.strain, &self.diff_objects);
-        let clock_rate = self.difficulty.get_clock_rate();
-
-        for (curr, is_circle) in skip_iter.take(take) {
-            increment_combo(*is_circle, curr, &mut self.curr_combo, clock_rate);
-            strain.process(curr);
-            self.idx += 1;
+        for _ in 0..cmp::min(n, self.len().saturating_sub(1)) {
+            self.advance()?;
         }
 
         self.next()
@@ -264,4 +431,60 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn seek() {
+        let converted = Beatmap::from_path("./resources/1638954.osu")
+            .unwrap()
+            .unchecked_into_converted::<Mania>();
+
+        let difficulty = Difficulty::new().mods(64);
+        let mut gradual =
+            ManiaGradualDifficulty::new(difficulty.clone(), &converted).checkpoint_interval(16);
+        let len = gradual.len();
+
+        for idx in [100, 20, 150, 151, 0, len - 1, 31, 32, 33] {
+            let mut expected = ManiaGradualDifficulty::new(difficulty.clone(), &converted);
+
+            assert_eq!(gradual.seek(idx), expected.nth(idx), "idx={idx}");
+            assert_eq!(gradual.next(), expected.next(), "idx={idx}");
+        }
+
+        assert!(gradual.seek(len).is_none());
+    }
+
+    #[test]
+    fn advance_to_time() {
+        let converted = Beatmap::from_path("./resources/1638954.osu")
+            .unwrap()
+            .unchecked_into_converted::<Mania>();
+
+        let difficulty = Difficulty::new().mods(64);
+        let clock_rate = difficulty.get_clock_rate();
+        let mut gradual = ManiaGradualDifficulty::new(difficulty.clone(), &converted);
+
+        assert!(gradual.advance_to_time(-1.0).is_none());
+
+        for time in [5000.0, 20_000.0, 20_001.0, 60_000.0] {
+            let Some(idx) = converted.object_index_at(time * clock_rate) else {
+                panic!("no object before {time}");
+            };
+
+            let expected = ManiaGradualDifficulty::new(difficulty.clone(), &converted).nth(idx);
+
+            if idx + 1 > gradual.idx {
+                assert_eq!(gradual.advance_to_time(time), expected, "time={time}");
+            } else {
+                assert!(gradual.advance_to_time(time).is_none());
+            }
+        }
+
+        let mut by_end = ManiaGradualDifficulty::new(difficulty.clone(), &converted)
+            .object_time(ObjectTime::End);
+        let mut by_start = ManiaGradualDifficulty::new(difficulty, &converted);
+
+        let _ = by_end.advance_to_time(30_000.0);
+        let _ = by_start.advance_to_time(30_000.0);
+        assert!(by_end.idx <= by_start.idx);
+    }
 }
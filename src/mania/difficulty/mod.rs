@@ -3,7 +3,10 @@ use std::cmp;
 use crate::{
     any::difficulty::{skills::Skill, Difficulty},
     mania::{
-        difficulty::{object::ManiaDifficultyObject, skills::strain::Strain},
+        difficulty::{
+            object::ManiaDifficultyObject,
+            skills::{skillset::Skillsets, strain::Strain},
+        },
         object::{ManiaObject, ObjectParams},
     },
 };
@@ -12,17 +15,27 @@ use super::{attributes::ManiaDifficultyAttributes, convert::ManiaBeatmap};
 
 pub mod gradual;
 mod object;
-mod skills;
+pub mod patterns;
+pub mod skills;
 
-const STAR_SCALING_FACTOR: f64 = 0.018;
+pub const STAR_SCALING_FACTOR: f64 = 0.018;
 
 pub fn difficulty(
     difficulty: &Difficulty,
     converted: &ManiaBeatmap<'_>,
 ) -> ManiaDifficultyAttributes {
+    let converted = &converted.with_conversion_mods(difficulty.get_mods());
     let n_objects = cmp::min(difficulty.get_passed_objects(), converted.hit_objects.len()) as u32;
 
+    let n_hold_notes = converted
+        .hit_objects
+        .iter()
+        .take(n_objects as usize)
+        .filter(|h| !h.is_circle())
+        .count() as u32;
+
     let values = DifficultyValues::calculate(difficulty, converted);
+    let [jack, chord, stream, technical, long_note] = values.skillsets.ratings();
 
     let hit_window = converted
         .attributes()
@@ -32,15 +45,22 @@ pub fn difficulty(
 
     ManiaDifficultyAttributes {
         stars: values.strain.difficulty_value() * STAR_SCALING_FACTOR,
+        jack,
+        chord,
+        stream,
+        technical,
+        long_note,
         hit_window,
         max_combo: values.max_combo,
         n_objects,
+        n_hold_notes,
         is_convert: converted.is_convert,
     }
 }
 
 pub struct DifficultyValues {
     pub strain: Strain,
+    pub skillsets: Skillsets,
     pub max_combo: u32,
 }
 
@@ -60,17 +80,20 @@ impl DifficultyValues {
         let diff_objects = Self::create_difficulty_objects(clock_rate, mania_objects);
 
         let mut strain = Strain::new(total_columns as usize);
+        let mut skillsets = Skillsets::new(total_columns as usize);
 
         {
             let mut strain = Skill::new(&mut strain, &diff_objects);
 
             for curr in diff_objects.iter() {
                 strain.process(curr);
+                skillsets.process(curr, &diff_objects);
             }
         }
 
         Self {
             strain,
+            skillsets,
             max_combo: params.into_max_combo(),
         }
     }
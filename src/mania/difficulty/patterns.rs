@@ -0,0 +1,479 @@
+use crate::{
+    any::difficulty::converted::ConvertedDifficulty,
+    mania::{
+        object::{ManiaObject, ObjectParams},
+        Mania, ManiaBeatmap,
+    },
+    util::tags::tags,
+    Difficulty,
+};
+
+use super::{skills::strain::Strain, DifficultyValues};
+
+/// Length of the time windows in ms, w.r.t. clock rate, that are classified.
+const SECTION_LEN: f64 = 2000.0;
+/// Windows with fewer notes are not classified.
+const MIN_SECTION_NOTES: usize = 8;
+/// Notes whose start times are closer than this (in ms) belong to the same
+/// row.
+const CHORD_TOLERANCE: f64 = 1.0;
+/// Notes that start within this time (in ms, w.r.t. clock rate) after the
+/// release of a long note in the same column are shielded.
+const SHIELD_GAP: f64 = 200.0;
+
+/// The kind of pattern of a [`ManiaPatternSection`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ManiaPatternKind {
+    /// Consecutive single notes in the same column.
+    Jackspeed,
+    /// Consecutive chords that share columns.
+    Chordjack,
+    /// A stream with frequent jumps i.e. chords of two notes.
+    Jumpstream,
+    /// A stream with frequent hands i.e. chords of at least three notes.
+    Handstream,
+    /// Consecutive single notes in different columns.
+    Stream,
+    /// Long notes whose releases have to be timed.
+    LongNoteRelease,
+    /// Notes that directly follow the release of a long note in the same
+    /// column.
+    LongNoteShield,
+    /// Single notes that alternate between two columns.
+    Trill,
+}
+
+/// A labeled section of an osu!mania map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManiaPatternSection {
+    /// The kind of pattern.
+    pub kind: ManiaPatternKind,
+    /// Start time of the section in ms.
+    ///
+    /// The time is **not** adjusted by the clock rate.
+    pub start_time: f64,
+    /// End time of the section in ms.
+    ///
+    /// The time is **not** adjusted by the clock rate.
+    pub end_time: f64,
+    /// The amount of notes in the section.
+    pub n_notes: usize,
+    /// The average strain of the section's notes.
+    ///
+    /// Sections of the same kind can be compared by this value.
+    pub intensity: f64,
+    /// The amount of notes per second, adjusted by the clock rate.
+    pub nps: f64,
+    /// The amount of notes per second for each column, adjusted by the clock
+    /// rate.
+    pub column_nps: Vec<f64>,
+}
+
+/// The result of classifying the patterns of an osu!mania map.
+///
+/// Parts of the map that are too sparse are not contained in any section.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ManiaPatterns {
+    /// The sections ordered by their start time.
+    pub sections: Vec<ManiaPatternSection>,
+}
+
+impl ManiaPatterns {
+    /// Return all pattern kinds whose sections make up at least `min_share`
+    /// of all classified notes, ordered by their share.
+    ///
+    /// `min_share` should be between `0.0` and `1.0`.
+    pub fn tags(&self, min_share: f64) -> Vec<ManiaPatternKind> {
+        let sections = self
+            .sections
+            .iter()
+            .map(|section| (section.kind, section.n_notes));
+
+        tags(sections, min_share)
+    }
+}
+
+impl ConvertedDifficulty<'_, Mania> {
+    /// Segment a [`ManiaBeatmap`] into sections of labeled patterns.
+    pub fn patterns(self, map: &ManiaBeatmap<'_>) -> ManiaPatterns {
+        patterns(self.inner(), map)
+    }
+}
+
+pub fn patterns(difficulty: &Difficulty, converted: &ManiaBeatmap<'_>) -> ManiaPatterns {
+    let converted = &converted.with_conversion_mods(difficulty.get_mods());
+    let take = difficulty.get_passed_objects();
+    let total_columns = converted.cs.round_ties_even().max(1.0);
+    let clock_rate = difficulty.get_clock_rate();
+    let mut params = ObjectParams::new(converted);
+
+    let mania_objects: Vec<_> = converted
+        .hit_objects
+        .iter()
+        .map(|h| ManiaObject::new(h, total_columns, &mut params))
+        .take(take)
+        .collect();
+
+    let strains = note_strains(&mania_objects, total_columns as usize, clock_rate);
+    let rows = Row::new_rows(&mania_objects, &strains, clock_rate);
+
+    let sections = classify(&rows, total_columns as usize, clock_rate);
+
+    ManiaPatterns { sections }
+}
+
+/// The strain value of each note.
+fn note_strains(mania_objects: &[ManiaObject], total_columns: usize, clock_rate: f64) -> Vec<f64> {
+    let diff_objects = DifficultyValues::create_difficulty_objects(
+        clock_rate,
+        mania_objects.iter().map(|h| ManiaObject {
+            start_time: h.start_time,
+            end_time: h.end_time,
+            column: h.column,
+        }),
+    );
+
+    let mut strain = Strain::new(total_columns);
+
+    // The first note has no difficulty object
+    let mut strains = Vec::with_capacity(mania_objects.len());
+    strains.extend(mania_objects.first().map(|_| 0.0));
+    strains.extend(diff_objects.iter().map(|curr| strain.strain_value_at(curr)));
+
+    strains
+}
+
+/// Notes that start at the same time.
+struct Row {
+    /// Start time w.r.t. clock rate.
+    start_time: f64,
+    columns: Vec<usize>,
+    n_long_notes: usize,
+    n_shielded: usize,
+    strain_sum: f64,
+    label: RowLabel,
+}
+
+impl Row {
+    fn new_rows(mania_objects: &[ManiaObject], strains: &[f64], clock_rate: f64) -> Vec<Self> {
+        let mut rows: Vec<Self> = Vec::new();
+
+        // End time of the last long note in each column
+        let mut long_note_ends: Vec<Option<f64>> = Vec::new();
+
+        for (h, strain) in mania_objects.iter().zip(strains) {
+            let start_time = h.start_time / clock_rate;
+            let end_time = h.end_time / clock_rate;
+
+            if long_note_ends.len() <= h.column {
+                long_note_ends.resize(h.column + 1, None);
+            }
+
+            let is_shielded = long_note_ends[h.column]
+                .is_some_and(|end| end <= start_time && start_time - end <= SHIELD_GAP);
+
+            let is_long_note = end_time > start_time;
+            long_note_ends[h.column] = is_long_note.then_some(end_time);
+
+            let row = match rows.last_mut() {
+                Some(row) if start_time - row.start_time <= CHORD_TOLERANCE => row,
+                _ => {
+                    rows.push(Self {
+                        start_time,
+                        columns: Vec::new(),
+                        n_long_notes: 0,
+                        n_shielded: 0,
+                        strain_sum: 0.0,
+                        label: RowLabel::Stream,
+                    });
+
+                    rows.last_mut().unwrap()
+                }
+            };
+
+            row.columns.push(h.column);
+            row.n_long_notes += usize::from(is_long_note);
+            row.n_shielded += usize::from(is_shielded);
+            row.strain_sum += strain;
+        }
+
+        for i in 0..rows.len() {
+            let prev = i.checked_sub(1).map(|j| &rows[j]);
+            let prev_prev = i.checked_sub(2).map(|j| &rows[j]);
+            rows[i].label = RowLabel::new(&rows[i], prev, prev_prev);
+        }
+
+        rows
+    }
+
+    const fn n_notes(&self) -> usize {
+        self.columns.len()
+    }
+
+    fn shares_column(&self, other: &Self) -> bool {
+        self.columns
+            .iter()
+            .any(|column| other.columns.contains(column))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RowLabel {
+    Jack,
+    Chordjack,
+    Jump,
+    Hand,
+    Trill,
+    Stream,
+}
+
+impl RowLabel {
+    fn new(curr: &Row, prev: Option<&Row>, prev_prev: Option<&Row>) -> Self {
+        let is_jack = prev.is_some_and(|prev| curr.shares_column(prev));
+
+        match (curr.n_notes(), is_jack) {
+            (1, true) => Self::Jack,
+            (_, true) => Self::Chordjack,
+            (2, false) => Self::Jump,
+            (3.., false) => Self::Hand,
+            _ => {
+                let is_trill = prev.zip(prev_prev).is_some_and(|(prev, prev_prev)| {
+                    prev.n_notes() == 1 && prev_prev.columns == curr.columns
+                });
+
+                if is_trill {
+                    Self::Trill
+                } else {
+                    Self::Stream
+                }
+            }
+        }
+    }
+}
+
+/// Notes within a window of [`SECTION_LEN`].
+struct Window {
+    idx: usize,
+    kind: ManiaPatternKind,
+    n_notes: usize,
+    strain_sum: f64,
+    column_notes: Vec<usize>,
+}
+
+impl Window {
+    fn new(idx: usize, rows: &[Row], total_columns: usize) -> Option<Self> {
+        let n_notes: usize = rows.iter().map(Row::n_notes).sum();
+
+        if n_notes < MIN_SECTION_NOTES {
+            return None;
+        }
+
+        let mut column_notes = vec![0; total_columns];
+
+        for column in rows.iter().flat_map(|row| row.columns.iter()) {
+            if let Some(count) = column_notes.get_mut(*column) {
+                *count += 1;
+            }
+        }
+
+        Some(Self {
+            idx,
+            kind: Self::kind(rows, n_notes),
+            n_notes,
+            strain_sum: rows.iter().map(|row| row.strain_sum).sum(),
+            column_notes,
+        })
+    }
+
+    fn kind(rows: &[Row], n_notes: usize) -> ManiaPatternKind {
+        let n_rows = rows.len() as f64;
+        let share = |label| rows.iter().filter(|row| row.label == label).count() as f64 / n_rows;
+
+        let n_long_notes: usize = rows.iter().map(|row| row.n_long_notes).sum();
+        let n_shielded: usize = rows.iter().map(|row| row.n_shielded).sum();
+
+        if n_long_notes * 2 >= n_notes {
+            if n_shielded * 4 >= n_notes {
+                ManiaPatternKind::LongNoteShield
+            } else {
+                ManiaPatternKind::LongNoteRelease
+            }
+        } else if share(RowLabel::Chordjack) >= 0.3 {
+            ManiaPatternKind::Chordjack
+        } else if share(RowLabel::Jack) >= 0.3 {
+            ManiaPatternKind::Jackspeed
+        } else if share(RowLabel::Hand) >= 0.15 {
+            ManiaPatternKind::Handstream
+        } else if share(RowLabel::Jump) >= 0.15 {
+            ManiaPatternKind::Jumpstream
+        } else if share(RowLabel::Trill) >= 0.5 {
+            ManiaPatternKind::Trill
+        } else {
+            ManiaPatternKind::Stream
+        }
+    }
+}
+
+fn classify(rows: &[Row], total_columns: usize, clock_rate: f64) -> Vec<ManiaPatternSection> {
+    let Some(first) = rows.first() else {
+        return Vec::new();
+    };
+
+    let first_start = first.start_time;
+    let mut windows = Vec::new();
+    let mut start = 0;
+
+    while start < rows.len() {
+        let idx = ((rows[start].start_time - first_start) / SECTION_LEN) as usize;
+        let window_end = first_start + (idx + 1) as f64 * SECTION_LEN;
+
+        let len = rows[start..]
+            .iter()
+            .take_while(|row| row.start_time < window_end)
+            .count();
+
+        windows.extend(Window::new(idx, &rows[start..start + len], total_columns));
+        start += len;
+    }
+
+    let mut sections: Vec<ManiaPatternSection> = Vec::new();
+    let mut last_idx = None;
+
+    for window in windows {
+        let window_start = first_start + window.idx as f64 * SECTION_LEN;
+
+        match sections.last_mut() {
+            Some(section) if section.kind == window.kind && last_idx == Some(window.idx - 1) => {
+                let n_notes = section.n_notes + window.n_notes;
+                let strain_sum = section.intensity * section.n_notes as f64 + window.strain_sum;
+
+                section.end_time = (window_start + SECTION_LEN) * clock_rate;
+                section.intensity = strain_sum / n_notes as f64;
+                section.n_notes = n_notes;
+
+                for (nps, count) in section.column_nps.iter_mut().zip(window.column_notes) {
+                    *nps += count as f64;
+                }
+            }
+            _ => sections.push(ManiaPatternSection {
+                kind: window.kind,
+                start_time: window_start * clock_rate,
+                end_time: (window_start + SECTION_LEN) * clock_rate,
+                n_notes: window.n_notes,
+                intensity: window.strain_sum / window.n_notes as f64,
+                nps: 0.0,
+                // Note counts for now, turned into NPS at the end
+                column_nps: window.column_notes.iter().map(|&n| n as f64).collect(),
+            }),
+        }
+
+        last_idx = Some(window.idx);
+    }
+
+    for section in sections.iter_mut() {
+        let secs = (section.end_time - section.start_time) / clock_rate / 1000.0;
+
+        section.nps = section.n_notes as f64 / secs;
+
+        for nps in section.column_nps.iter_mut() {
+            *nps /= secs;
+        }
+    }
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Beatmap;
+
+    use super::*;
+
+    fn converted() -> ManiaBeatmap<'static> {
+        Beatmap::from_path("./resources/1638954.osu")
+            .unwrap()
+            .unchecked_into_converted()
+    }
+
+    #[test]
+    fn sections_are_ordered() {
+        let converted = converted();
+        let patterns = Difficulty::new().with_mode::<Mania>().patterns(&converted);
+
+        assert!(!patterns.sections.is_empty());
+
+        for window in patterns.sections.windows(2) {
+            assert!(window[0].end_time <= window[1].start_time);
+        }
+
+        for section in patterns.sections.iter() {
+            assert!(section.start_time < section.end_time);
+            assert!(section.n_notes >= MIN_SECTION_NOTES);
+            assert!(section.intensity.is_finite());
+            assert_eq!(section.column_nps.len(), 4);
+
+            let column_sum: f64 = section.column_nps.iter().sum();
+            assert!((column_sum - section.nps).abs() < 1e-9);
+        }
+
+        assert!(!patterns.tags(0.1).is_empty());
+    }
+
+    #[test]
+    fn clock_rate_increases_nps() {
+        let converted = converted();
+
+        let nps = |patterns: &ManiaPatterns| {
+            patterns
+                .sections
+                .iter()
+                .map(|section| section.nps)
+                .fold(0.0, f64::max)
+        };
+
+        let nomod = Difficulty::new().with_mode::<Mania>().patterns(&converted);
+        let dt = Difficulty::new()
+            .mods(64)
+            .with_mode::<Mania>()
+            .patterns(&converted);
+
+        assert!(nps(&dt) > nps(&nomod));
+    }
+
+    #[test]
+    fn row_labels() {
+        let objects: Vec<_> = [(0.0, 0), (100.0, 0), (200.0, 1), (200.0, 2), (300.0, 2)]
+            .into_iter()
+            .map(|(start_time, column)| ManiaObject {
+                start_time,
+                end_time: start_time,
+                column,
+            })
+            .collect();
+
+        let rows = Row::new_rows(&objects, &[0.0; 5], 1.0);
+        let labels: Vec<_> = rows.iter().map(|row| row.label).collect();
+
+        assert_eq!(
+            labels,
+            [
+                RowLabel::Stream,
+                RowLabel::Jack,
+                RowLabel::Jump,
+                RowLabel::Jack,
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let converted = Beatmap::from_bytes(&[])
+            .unwrap()
+            .unchecked_into_converted::<Mania>();
+
+        let patterns = Difficulty::new().with_mode::<Mania>().patterns(&converted);
+
+        assert!(patterns.sections.is_empty());
+        assert!(patterns.tags(0.0).is_empty());
+    }
+}
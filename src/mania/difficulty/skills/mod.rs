@@ -1 +1,2 @@
+pub mod skillset;
 pub mod strain;
@@ -0,0 +1,301 @@
+use crate::{
+    any::difficulty::{
+        object::IDifficultyObject,
+        skills::{strain_decay, ISkill, Skill, StrainDecaySkill},
+    },
+    mania::difficulty::{object::ManiaDifficultyObject, STAR_SCALING_FACTOR},
+    util::strains_vec::StrainsVec,
+};
+
+/// Notes closer than this (in ms) are considered to be in the same chord.
+const CHORD_THRESHOLD: f64 = 1.0;
+/// Lower bound for time differences (in ms) so that values don't explode.
+const MIN_DELTA_TIME: f64 = 25.0;
+
+/// The skillsets that osu!mania maps are rated on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SkillsetKind {
+    /// Repeated notes in the same column.
+    Jack,
+    /// Multiple notes at the same time.
+    Chord,
+    /// Notes alternating between columns, including jumpstreams.
+    Stream,
+    /// Rhythm changes.
+    Technical,
+    /// Holding and releasing long notes.
+    LongNote,
+}
+
+impl SkillsetKind {
+    const fn skill_multiplier(self) -> f64 {
+        match self {
+            Self::Jack => 2.0,
+            Self::Chord => 2.5,
+            Self::Stream => 1.45,
+            Self::Technical => 5.0,
+            Self::LongNote => 2.5,
+        }
+    }
+
+    const fn strain_decay_base(self) -> f64 {
+        match self {
+            Self::Jack => 0.15,
+            Self::Chord | Self::Stream | Self::LongNote => 0.3,
+            Self::Technical => 0.4,
+        }
+    }
+}
+
+/// Strain skill that only considers a single [`SkillsetKind`].
+#[derive(Clone)]
+pub struct Skillset {
+    kind: SkillsetKind,
+    start_times: Box<[f64]>,
+    end_times: Box<[f64]>,
+    chord_size: usize,
+    row_delta_time: f64,
+    prev_row_delta_time: f64,
+    inner: StrainDecaySkill,
+}
+
+impl Skillset {
+    pub fn new(kind: SkillsetKind, total_columns: usize) -> Self {
+        Self {
+            kind,
+            start_times: vec![f64::NEG_INFINITY; total_columns].into_boxed_slice(),
+            end_times: vec![f64::NEG_INFINITY; total_columns].into_boxed_slice(),
+            chord_size: 1,
+            row_delta_time: 0.0,
+            prev_row_delta_time: 0.0,
+            inner: StrainDecaySkill::default(),
+        }
+    }
+
+    pub fn get_curr_strain_peaks(self) -> StrainsVec {
+        self.inner.get_curr_strain_peaks()
+    }
+
+    pub fn difficulty_value(self) -> f64 {
+        self.inner.difficulty_value(StrainDecaySkill::DECAY_WEIGHT)
+    }
+
+    /// Use [`difficulty_value`] instead whenever possible because
+    /// [`as_difficulty_value`] clones internally.
+    pub fn as_difficulty_value(&self) -> f64 {
+        self.inner
+            .clone()
+            .difficulty_value(StrainDecaySkill::DECAY_WEIGHT)
+    }
+
+    const fn curr_strain(&self) -> f64 {
+        self.inner.curr_strain
+    }
+
+    const fn curr_strain_mut(&mut self) -> &mut f64 {
+        &mut self.inner.curr_strain
+    }
+
+    fn strain_value_at(&mut self, curr: &ManiaDifficultyObject) -> f64 {
+        *self.curr_strain_mut() *= strain_decay(curr.delta_time, self.kind.strain_decay_base());
+        *self.curr_strain_mut() += self.strain_value_of(curr) * self.kind.skill_multiplier();
+
+        self.curr_strain()
+    }
+
+    fn strain_value_of(&mut self, curr: &ManiaDifficultyObject) -> f64 {
+        let column = curr.base_column;
+
+        if curr.delta_time <= CHORD_THRESHOLD {
+            self.chord_size += 1;
+        } else {
+            self.chord_size = 1;
+            self.prev_row_delta_time = self.row_delta_time;
+            self.row_delta_time = curr.delta_time;
+        }
+
+        let column_delta_time = curr.start_time - self.start_times[column];
+        // Notes accumulate strain already so the density only adds a little
+        let row_density = (100.0 / self.row_delta_time.max(MIN_DELTA_TIME)).sqrt();
+
+        let value = match self.kind {
+            SkillsetKind::Jack => (100.0 / column_delta_time.max(MIN_DELTA_TIME)).sqrt(),
+            SkillsetKind::Chord => (self.chord_size - 1) as f64 * row_density,
+            SkillsetKind::Stream => {
+                // Jacks within the stream are not part of the stream
+                if column_delta_time > self.row_delta_time + CHORD_THRESHOLD {
+                    row_density
+                } else {
+                    0.0
+                }
+            }
+            SkillsetKind::Technical => {
+                if self.chord_size > 1 || self.prev_row_delta_time <= 0.0 {
+                    0.0
+                } else {
+                    let ratio = self.row_delta_time / self.prev_row_delta_time;
+
+                    ratio.ln().abs().min(1.0) * row_density
+                }
+            }
+            SkillsetKind::LongNote => {
+                let n_held = self
+                    .end_times
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, end_time)| {
+                        *i != column && **end_time > curr.start_time + CHORD_THRESHOLD
+                    })
+                    .count();
+
+                let is_long_note = curr.end_time > curr.start_time;
+                let long_note_value = if is_long_note { 1.0 } else { 0.0 };
+
+                (long_note_value + 0.5 * n_held as f64) * row_density
+            }
+        };
+
+        self.start_times[column] = curr.start_time;
+        self.end_times[column] = curr.end_time;
+
+        value
+    }
+}
+
+impl ISkill for Skillset {
+    type DifficultyObjects<'a> = [ManiaDifficultyObject];
+}
+
+impl Skill<'_, Skillset> {
+    fn calculate_initial_strain(&self, time: f64, curr: &ManiaDifficultyObject) -> f64 {
+        let prev_start_time = curr
+            .previous(0, self.diff_objects)
+            .map_or(0.0, |prev| prev.start_time);
+
+        self.inner.curr_strain()
+            * strain_decay(time - prev_start_time, self.inner.kind.strain_decay_base())
+    }
+
+    const fn curr_section_peak(&self) -> f64 {
+        self.inner.inner.inner.curr_section_peak
+    }
+
+    const fn curr_section_peak_mut(&mut self) -> &mut f64 {
+        &mut self.inner.inner.inner.curr_section_peak
+    }
+
+    const fn curr_section_end(&self) -> f64 {
+        self.inner.inner.inner.curr_section_end
+    }
+
+    const fn curr_section_end_mut(&mut self) -> &mut f64 {
+        &mut self.inner.inner.inner.curr_section_end
+    }
+
+    pub fn process(&mut self, curr: &ManiaDifficultyObject) {
+        if curr.idx == 0 {
+            *self.curr_section_end_mut() = (curr.start_time / StrainDecaySkill::SECTION_LEN).ceil()
+                * StrainDecaySkill::SECTION_LEN;
+        }
+
+        while curr.start_time > self.curr_section_end() {
+            self.inner.inner.save_curr_peak();
+            let initial_strain = self.calculate_initial_strain(self.curr_section_end(), curr);
+            self.inner.inner.start_new_section_from(initial_strain);
+            *self.curr_section_end_mut() += StrainDecaySkill::SECTION_LEN;
+        }
+
+        let strain_value_at = self.inner.strain_value_at(curr);
+        *self.curr_section_peak_mut() = strain_value_at.max(self.curr_section_peak());
+    }
+}
+
+/// All skillset skills of an osu!mania map.
+#[derive(Clone)]
+pub struct Skillsets {
+    pub jack: Skillset,
+    pub chord: Skillset,
+    pub stream: Skillset,
+    pub technical: Skillset,
+    pub long_note: Skillset,
+}
+
+impl Skillsets {
+    pub fn new(total_columns: usize) -> Self {
+        Self {
+            jack: Skillset::new(SkillsetKind::Jack, total_columns),
+            chord: Skillset::new(SkillsetKind::Chord, total_columns),
+            stream: Skillset::new(SkillsetKind::Stream, total_columns),
+            technical: Skillset::new(SkillsetKind::Technical, total_columns),
+            long_note: Skillset::new(SkillsetKind::LongNote, total_columns),
+        }
+    }
+
+    pub fn process(
+        &mut self,
+        curr: &ManiaDifficultyObject,
+        diff_objects: &[ManiaDifficultyObject],
+    ) {
+        Skill::new(&mut self.jack, diff_objects).process(curr);
+        Skill::new(&mut self.chord, diff_objects).process(curr);
+        Skill::new(&mut self.stream, diff_objects).process(curr);
+        Skill::new(&mut self.technical, diff_objects).process(curr);
+        Skill::new(&mut self.long_note, diff_objects).process(curr);
+    }
+
+    /// The ratings in the order jack, chord, stream, technical, long note.
+    pub fn ratings(self) -> [f64; 5] {
+        [
+            self.jack.difficulty_value(),
+            self.chord.difficulty_value(),
+            self.stream.difficulty_value(),
+            self.technical.difficulty_value(),
+            self.long_note.difficulty_value(),
+        ]
+        .map(|value| value * STAR_SCALING_FACTOR)
+    }
+
+    /// Use [`ratings`] instead whenever possible because [`as_ratings`]
+    /// clones internally.
+    pub fn as_ratings(&self) -> [f64; 5] {
+        [
+            self.jack.as_difficulty_value(),
+            self.chord.as_difficulty_value(),
+            self.stream.as_difficulty_value(),
+            self.technical.as_difficulty_value(),
+            self.long_note.as_difficulty_value(),
+        ]
+        .map(|value| value * STAR_SCALING_FACTOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mania::Mania, Beatmap, Difficulty};
+
+    #[test]
+    fn ratings() {
+        let converted = Beatmap::from_path("./resources/1638954.osu")
+            .unwrap()
+            .unchecked_into_converted::<Mania>();
+
+        let nomod = Difficulty::new().with_mode().calculate(&converted);
+        let dt = Difficulty::new().mods(64).with_mode().calculate(&converted);
+
+        for (nomod, dt) in [
+            (nomod.jack, dt.jack),
+            (nomod.chord, dt.chord),
+            (nomod.stream, dt.stream),
+            (nomod.technical, dt.technical),
+            (nomod.long_note, dt.long_note),
+        ] {
+            assert!(nomod > 0.0);
+            assert!(dt > nomod);
+        }
+
+        let perf = nomod.performance().calculate();
+
+        assert!(perf.pp_stream > 0.0);
+        assert!(perf.pp_stream < perf.pp);
+    }
+}
@@ -14,6 +14,7 @@ const RELEASE_THRESHOLD: f64 = 24.0;
 const SKILL_MULTIPLIER: f64 = 1.0;
 const STRAIN_DECAY_BASE: f64 = 1.0;
 
+#[derive(Clone)]
 #[allow(clippy::struct_field_names)]
 pub struct Strain {
     start_times: Box<[f64]>,
@@ -42,6 +43,11 @@ impl Strain {
         self.inner.get_curr_strain_peaks()
     }
 
+    /// The end time of the current strain section.
+    pub const fn section_end(&self) -> f64 {
+        self.inner.inner.curr_section_end
+    }
+
     pub fn difficulty_value(self) -> f64 {
         Self::static_difficulty_value(self.inner)
     }
@@ -64,7 +70,7 @@ impl Strain {
         &mut self.inner.curr_strain
     }
 
-    fn strain_value_at(&mut self, curr: &ManiaDifficultyObject) -> f64 {
+    pub fn strain_value_at(&mut self, curr: &ManiaDifficultyObject) -> f64 {
         *self.curr_strain_mut() *= strain_decay(curr.delta_time, STRAIN_DECAY_BASE);
         *self.curr_strain_mut() += self.strain_value_of(curr) * SKILL_MULTIPLIER;
 
@@ -1,4 +1,5 @@
 use crate::{
+    any::HardestSection,
     model::{
         beatmap::Beatmap,
         mode::{ConvertStatus, IGameMode},
@@ -9,9 +10,15 @@ use crate::{
 pub use self::{
     attributes::{ManiaDifficultyAttributes, ManiaPerformanceAttributes},
     convert::ManiaBeatmap,
-    difficulty::gradual::ManiaGradualDifficulty,
-    performance::{gradual::ManiaGradualPerformance, ManiaPerformance},
+    difficulty::{
+        gradual::ManiaGradualDifficulty,
+        patterns::{ManiaPatternKind, ManiaPatternSection, ManiaPatterns},
+    },
+    performance::{
+        batch::ManiaPerformanceBatch, gradual::ManiaGradualPerformance, ManiaPerformance,
+    },
     score_state::ManiaScoreState,
+    scoring::ManiaScoring,
     strains::ManiaStrains,
 };
 
@@ -21,6 +28,7 @@ mod difficulty;
 mod object;
 mod performance;
 mod score_state;
+mod scoring;
 mod strains;
 
 /// Marker type for [`GameMode::Mania`].
@@ -35,6 +43,8 @@ impl IGameMode for Mania {
     type GradualDifficulty = ManiaGradualDifficulty;
     type GradualPerformance = ManiaGradualPerformance;
 
+    const CONVERSION_DEPENDS_ON_MODS: bool = true;
+
     fn check_convert(map: &Beatmap) -> ConvertStatus {
         convert::check_convert(map)
     }
@@ -54,6 +64,14 @@ impl IGameMode for Mania {
         strains::strains(difficulty, converted)
     }
 
+    fn hardest_section(
+        difficulty: &Difficulty,
+        converted: &ManiaBeatmap<'_>,
+        duration: f64,
+    ) -> Option<HardestSection> {
+        strains::hardest_section(difficulty, converted, duration)
+    }
+
     fn performance(map: ManiaBeatmap<'_>) -> Self::Performance<'_> {
         ManiaPerformance::new(map)
     }
@@ -0,0 +1,97 @@
+use crate::mania::{
+    ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaScoreState, ManiaScoring,
+};
+
+use super::{ManiaPerformanceInner, ManiaPerformanceInvariants};
+
+/// Calculate the performance attributes of many scores on the same osu!mania
+/// map.
+///
+/// All values that only depend on the map and mods are computed once upfront
+/// so this is considerably faster than creating a [`ManiaPerformance`] for
+/// each score while producing the same results.
+///
+/// Note that score states are used as is, i.e. unlike [`ManiaPerformance`],
+/// missing hitresults are not generated so every state should be complete.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty};
+/// use rosu_pp::mania::{Mania, ManiaPerformanceBatch, ManiaScoreState};
+///
+/// let converted = Beatmap::from_path("./resources/1638954.osu")
+///     .unwrap()
+///     .unchecked_into_converted::<Mania>();
+///
+/// let mods = 64; // DT
+/// let attrs = Difficulty::new().mods(mods).with_mode().calculate(&converted);
+///
+/// let states: Vec<_> = (0..10)
+///     .map(|misses| ManiaScoreState {
+///         n320: attrs.n_objects - misses,
+///         misses,
+///         ..Default::default()
+///     })
+///     .collect();
+///
+/// let batch = ManiaPerformanceBatch::new(attrs, mods);
+///
+/// for performance in batch.calculate(&states) {
+///     println!("PP: {}", performance.pp);
+/// }
+/// ```
+///
+/// [`ManiaPerformance`]: crate::mania::ManiaPerformance
+pub struct ManiaPerformanceBatch {
+    attrs: ManiaDifficultyAttributes,
+    mods: u32,
+    scoring: ManiaScoring,
+    invariants: ManiaPerformanceInvariants,
+}
+
+impl ManiaPerformanceBatch {
+    /// Prepare the batch calculation for the given difficulty attributes.
+    ///
+    /// `mods` must be the same mods that the attributes were calculated with.
+    pub fn new(attrs: ManiaDifficultyAttributes, mods: u32) -> Self {
+        let invariants = ManiaPerformanceInvariants::new(&attrs, mods);
+
+        Self {
+            attrs,
+            mods,
+            scoring: ManiaScoring::DEFAULT,
+            invariants,
+        }
+    }
+
+    /// Specify the scoring model of the score states.
+    ///
+    /// Defaults to [`ManiaScoring::ScoreV1`].
+    #[must_use]
+    pub const fn scoring(mut self, scoring: ManiaScoring) -> Self {
+        self.scoring = scoring;
+
+        self
+    }
+
+    /// Calculate the performance attributes for each score state.
+    pub fn calculate(&self, states: &[ManiaScoreState]) -> Vec<ManiaPerformanceAttributes> {
+        states
+            .iter()
+            .map(|state| self.calculate_single(state))
+            .collect()
+    }
+
+    /// Calculate the performance attributes for a single score state.
+    pub fn calculate_single(&self, state: &ManiaScoreState) -> ManiaPerformanceAttributes {
+        let inner = ManiaPerformanceInner {
+            attrs: self.attrs.clone(),
+            mods: self.mods,
+            state: state.clone(),
+            scoring: self.scoring,
+        };
+
+        inner.calculate_with(&self.invariants)
+    }
+}
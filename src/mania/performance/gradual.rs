@@ -1,5 +1,7 @@
 use crate::{
-    mania::{ManiaBeatmap, ManiaGradualDifficulty},
+    any::GradualSnapshot,
+    mania::{ManiaBeatmap, ManiaGradualDifficulty, ManiaScoring},
+    model::hit_object::ObjectTime,
     Difficulty,
 };
 
@@ -71,6 +73,7 @@ use super::{ManiaPerformanceAttributes, ManiaScoreState};
 /// [`nth`]: ManiaGradualPerformance::nth
 pub struct ManiaGradualPerformance {
     difficulty: ManiaGradualDifficulty,
+    scoring: ManiaScoring,
 }
 
 impl ManiaGradualPerformance {
@@ -78,7 +81,46 @@ impl ManiaGradualPerformance {
     pub fn new(difficulty: Difficulty, converted: &ManiaBeatmap<'_>) -> Self {
         let difficulty = ManiaGradualDifficulty::new(difficulty, converted);
 
-        Self { difficulty }
+        Self {
+            difficulty,
+            scoring: ManiaScoring::DEFAULT,
+        }
+    }
+
+    /// Specify the scoring model of the play.
+    ///
+    /// Defaults to [`ManiaScoring::ScoreV1`].
+    #[must_use]
+    pub const fn scoring(mut self, scoring: ManiaScoring) -> Self {
+        self.scoring = scoring;
+
+        self
+    }
+
+    /// Store a snapshot of the state after every `interval` processed
+    /// objects so that [`seek`] can jump back and forth quickly.
+    ///
+    /// See [`ManiaGradualDifficulty::checkpoint_interval`] for more information.
+    ///
+    /// [`seek`]: Self::seek
+    #[must_use]
+    pub fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.difficulty = self.difficulty.checkpoint_interval(interval);
+
+        self
+    }
+
+    /// Specify whether [`advance_to_time`] compares timestamps against the
+    /// start or the end time of objects.
+    ///
+    /// See [`ManiaGradualDifficulty::object_time`] for more information.
+    ///
+    /// [`advance_to_time`]: Self::advance_to_time
+    #[must_use]
+    pub fn object_time(mut self, object_time: ObjectTime) -> Self {
+        self.difficulty = self.difficulty.object_time(object_time);
+
+        self
     }
 
     /// Process the next hit object and calculate the performance attributes
@@ -104,6 +146,53 @@ impl ManiaGradualPerformance {
             .nth(n)?
             .performance()
             .state(state)
+            .scoring(self.scoring)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .calculate();
+
+        Some(performance)
+    }
+
+    /// Calculate the performance attributes after the hit object at index `idx`
+    /// for the given score state, regardless of how many hit objects were
+    /// processed so far.
+    ///
+    /// See [`ManiaGradualDifficulty::seek`] for more information.
+    pub fn seek(
+        &mut self,
+        state: ManiaScoreState,
+        idx: usize,
+    ) -> Option<ManiaPerformanceAttributes> {
+        let performance = self
+            .difficulty
+            .seek(idx)?
+            .performance()
+            .state(state)
+            .scoring(self.scoring)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .calculate();
+
+        Some(performance)
+    }
+
+    /// Process all objects whose time is at or before the timestamp `time`
+    /// in milliseconds and calculate the performance attributes for the
+    /// given score state.
+    ///
+    /// See [`ManiaGradualDifficulty::advance_to_time`] for more information.
+    pub fn advance_to_time(
+        &mut self,
+        state: ManiaScoreState,
+        time: f64,
+    ) -> Option<ManiaPerformanceAttributes> {
+        let performance = self
+            .difficulty
+            .advance_to_time(time)?
+            .performance()
+            .state(state)
+            .scoring(self.scoring)
             .difficulty(self.difficulty.difficulty.clone())
             .passed_objects(self.difficulty.idx as u32)
             .calculate();
@@ -111,6 +200,22 @@ impl ManiaGradualPerformance {
         Some(performance)
     }
 
+    /// Capture the progress of the calculation in a compact
+    /// [`GradualSnapshot`].
+    pub fn snapshot(&self) -> GradualSnapshot {
+        self.difficulty.snapshot()
+    }
+
+    /// Resume a calculation from a [`GradualSnapshot`].
+    ///
+    /// See [`ManiaGradualDifficulty::from_snapshot`] for more information.
+    pub fn from_snapshot(snapshot: &GradualSnapshot, converted: &ManiaBeatmap<'_>) -> Option<Self> {
+        ManiaGradualDifficulty::from_snapshot(snapshot, converted).map(|difficulty| Self {
+            difficulty,
+            scoring: ManiaScoring::DEFAULT,
+        })
+    }
+
     /// Returns the amount of remaining objects.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
@@ -1,18 +1,25 @@
-use std::cmp;
+use std::{borrow::Cow, cmp};
 
 use crate::{
     any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance},
+    model::beatmap::Converted,
     osu::OsuPerformance,
-    util::{map_or_attrs::MapOrAttrs, mods::Mods},
+    util::{
+        map_or_attrs::MapOrAttrs,
+        mods::Mods,
+        solver::{self, Solvable},
+    },
     Performance,
 };
 
 use super::{
     attributes::{ManiaDifficultyAttributes, ManiaPerformanceAttributes},
     score_state::ManiaScoreState,
-    Mania,
+    scoring::ManiaScoring,
+    Mania, ManiaBeatmap,
 };
 
+pub mod batch;
 pub mod gradual;
 
 /// Performance calculator on osu!mania maps.
@@ -29,6 +36,7 @@ pub struct ManiaPerformance<'map> {
     misses: Option<u32>,
     acc: Option<f64>,
     hitresult_priority: HitResultPriority,
+    scoring: ManiaScoring,
 }
 
 impl<'map> ManiaPerformance<'map> {
@@ -146,6 +154,8 @@ impl<'map> ManiaPerformance<'map> {
 
     /// Specify the accuracy of a play between `0.0` and `100.0`.
     /// This will be used to generate matching hitresults.
+    ///
+    /// The accuracy is interpreted according to the [`ManiaScoring`].
     pub fn accuracy(mut self, acc: f64) -> Self {
         self.acc = Some(acc.clamp(0.0, 100.0) / 100.0);
 
@@ -161,6 +171,18 @@ impl<'map> ManiaPerformance<'map> {
         self
     }
 
+    /// Specify the scoring model of a play.
+    ///
+    /// It decides how accuracy relates to hitresults and, for
+    /// [`ManiaScoring::Lazer`], that long note tails are judged separately.
+    ///
+    /// Defaults to [`ManiaScoring::ScoreV1`].
+    pub const fn scoring(mut self, scoring: ManiaScoring) -> Self {
+        self.scoring = scoring;
+
+        self
+    }
+
     /// Specify the amount of 320s of a play.
     pub const fn n320(mut self, n320: u32) -> Self {
         self.n320 = Some(n320);
@@ -238,6 +260,11 @@ impl<'map> ManiaPerformance<'map> {
         };
 
         let n_objects = cmp::min(self.difficulty.get_passed_objects() as u32, attrs.n_objects);
+        let n_hold_notes = cmp::min(attrs.n_hold_notes, n_objects);
+
+        // Amount of judgements rather than hit objects since lazer judges long
+        // note tails separately
+        let n_objects = self.scoring.n_judgements(n_objects, n_hold_notes);
 
         let priority = self.hitresult_priority;
 
@@ -250,7 +277,23 @@ impl<'map> ManiaPerformance<'map> {
         let mut n100 = self.n100.map_or(0, |n| cmp::min(n, n_remaining));
         let mut n50 = self.n50.map_or(0, |n| cmp::min(n, n_remaining));
 
-        if let Some(acc) = self.acc {
+        if let (Some(acc), ManiaScoring::ScoreV2 | ManiaScoring::Lazer) = (self.acc, self.scoring) {
+            let mut counts = [n320, n300, n200, n100, n50];
+
+            let given = [self.n320, self.n300, self.n200, self.n100, self.n50].map(|n| n.is_some());
+
+            distribute_weighted(
+                acc,
+                self.scoring.weights(),
+                n_objects,
+                misses,
+                given,
+                &mut counts,
+                priority,
+            );
+
+            [n320, n300, n200, n100, n50] = counts;
+        } else if let Some(acc) = self.acc {
             let target_total = acc * f64::from(6 * n_objects);
 
             match (self.n320, self.n300, self.n200, self.n100, self.n50) {
@@ -752,7 +795,12 @@ impl<'map> ManiaPerformance<'map> {
     }
 
     /// Calculate all performance related values, including pp and stars.
-    pub fn calculate(mut self) -> ManiaPerformanceAttributes {
+    pub fn calculate(self) -> ManiaPerformanceAttributes {
+        self.inner().calculate()
+    }
+
+    /// Generate the score state and pair it with the difficulty attributes.
+    fn inner(mut self) -> ManiaPerformanceInner {
         let state = self.generate_state();
 
         let attrs = match self.map_or_attrs {
@@ -760,13 +808,34 @@ impl<'map> ManiaPerformance<'map> {
             MapOrAttrs::Attrs(attrs) => attrs,
         };
 
-        let inner = ManiaPerformanceInner {
+        ManiaPerformanceInner {
             mods: self.difficulty.get_mods(),
             attrs,
             state,
-        };
+            scoring: self.scoring,
+        }
+    }
 
-        inner.calculate()
+    /// Find the minimal accuracy, in percent, for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Since explicitly specified hitresults take precedence over the
+    /// accuracy, they should generally be left unspecified.
+    ///
+    /// Hitresults are generated according to the specified
+    /// [`HitResultPriority`] for each probed accuracy.
+    ///
+    /// Returns `None` if `target_pp` is not reached even with 100% accuracy.
+    pub fn accuracy_for_pp(self, target_pp: f64) -> Option<f64> {
+        solver::accuracy_for_pp(self, target_pp)
+    }
+
+    /// Find the maximal amount of misses for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Returns `None` if `target_pp` is not reached even without misses.
+    pub fn misses_for_pp(self, target_pp: f64) -> Option<u32> {
+        solver::misses_for_pp(self, target_pp)
     }
 
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Mania>) -> Self {
@@ -781,6 +850,7 @@ impl<'map> ManiaPerformance<'map> {
             misses: None,
             acc: None,
             hitresult_priority: HitResultPriority::DEFAULT,
+            scoring: ManiaScoring::DEFAULT,
         }
     }
 }
@@ -798,13 +868,26 @@ impl<'map> TryFrom<OsuPerformance<'map>> for ManiaPerformance<'map> {
             return Err(osu);
         };
 
-        let map = match converted.try_convert() {
-            Ok(map) => map,
-            Err(map) => {
-                osu.map_or_attrs = MapOrAttrs::Map(map);
+        let mods = osu.difficulty.get_mods();
+
+        let map = match converted.into_inner() {
+            Cow::Borrowed(map) => {
+                let Some(converted) = ManiaBeatmap::try_from_ref_with_mods(map, mods) else {
+                    osu.map_or_attrs = MapOrAttrs::Map(Converted::new(Cow::Borrowed(map)));
 
-                return Err(osu);
+                    return Err(osu);
+                };
+
+                converted
             }
+            Cow::Owned(map) => match ManiaBeatmap::try_from_owned_with_mods(map, mods) {
+                Ok(map) => map,
+                Err(map) => {
+                    osu.map_or_attrs = MapOrAttrs::Map(Converted::new(Cow::Owned(map)));
+
+                    return Err(osu);
+                }
+            },
         };
 
         let OsuPerformance {
@@ -816,6 +899,8 @@ impl<'map> TryFrom<OsuPerformance<'map>> for ManiaPerformance<'map> {
             n100,
             n50,
             misses,
+            spinner_bonus: _,
+            spun_out: _,
             hitresult_priority,
         } = osu;
 
@@ -830,6 +915,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for ManiaPerformance<'map> {
             misses,
             acc,
             hitresult_priority,
+            scoring: ManiaScoring::DEFAULT,
         })
     }
 }
@@ -840,50 +926,94 @@ impl<'map, T: IntoModePerformance<'map, Mania>> From<T> for ManiaPerformance<'ma
     }
 }
 
+impl Solvable for ManiaPerformance<'_> {
+    type Invariants = ManiaPerformanceInvariants;
+
+    fn invariants(&mut self) -> Self::Invariants {
+        let attrs = match self.map_or_attrs {
+            MapOrAttrs::Map(ref map) => {
+                let attrs = self.difficulty.with_mode().calculate(map);
+
+                self.map_or_attrs.insert_attrs(attrs)
+            }
+            MapOrAttrs::Attrs(ref attrs) => attrs,
+        };
+
+        ManiaPerformanceInvariants::new(attrs, self.difficulty.get_mods())
+    }
+
+    fn with_accuracy(self, acc: f64) -> Self {
+        self.accuracy(acc)
+    }
+
+    fn with_misses(self, misses: u32) -> Self {
+        self.misses(misses)
+    }
+
+    fn max_misses(&self) -> u32 {
+        self.clone().misses(u32::MAX).generate_state().misses
+    }
+
+    fn pp(self, invariants: &Self::Invariants) -> f64 {
+        self.inner().calculate_with(invariants).pp
+    }
+}
+
 struct ManiaPerformanceInner {
     attrs: ManiaDifficultyAttributes,
     mods: u32,
     state: ManiaScoreState,
+    scoring: ManiaScoring,
 }
 
 impl ManiaPerformanceInner {
     fn calculate(self) -> ManiaPerformanceAttributes {
-        // * Arbitrary initial value for scaling pp in order to standardize distributions across game modes.
-        // * The specific number has no intrinsic meaning and can be adjusted as needed.
-        let mut multiplier = 8.0;
+        let invariants = ManiaPerformanceInvariants::new(&self.attrs, self.mods);
 
-        if self.mods.nf() {
-            multiplier *= 0.75;
-        }
+        self.calculate_with(&invariants)
+    }
 
-        if self.mods.ez() {
-            multiplier *= 0.5;
-        }
+    fn calculate_with(self, invariants: &ManiaPerformanceInvariants) -> ManiaPerformanceAttributes {
+        let multiplier = invariants.multiplier;
 
-        let difficulty_value = self.compute_difficulty_value();
+        // * From 80% accuracy, 1/20th of total pp is awarded per additional 1% accuracy
+        let acc_factor = (5.0 * self.calculate_custom_accuracy() - 4.0).max(0.0);
+        let len_bonus = invariants.len_bonus(self.state.total_hits());
+
+        let [stars, jack, chord, stream, technical, long_note] = invariants
+            .star_curves
+            .map(|star_curve| star_curve * acc_factor * len_bonus);
+
+        let difficulty_value = stars;
         let pp = difficulty_value * multiplier;
 
+        // The skillset components are informational and don't affect the
+        // final pp
+        let pp_jack = jack * multiplier;
+        let pp_chord = chord * multiplier;
+        let pp_stream = stream * multiplier;
+        let pp_technical = technical * multiplier;
+        let pp_long_note = long_note * multiplier;
+
         ManiaPerformanceAttributes {
             difficulty: self.attrs,
             pp,
             pp_difficulty: difficulty_value,
+            pp_jack,
+            pp_chord,
+            pp_stream,
+            pp_technical,
+            pp_long_note,
         }
     }
 
-    fn compute_difficulty_value(&self) -> f64 {
-        // * Star rating to pp curve
-        (self.attrs.stars - 0.15).max(0.05).powf(2.2)
-             // * From 80% accuracy, 1/20th of total pp is awarded per additional 1% accuracy
-             * (5.0 * self.calculate_custom_accuracy() - 4.0).max(0.0)
-             // * Length bonus, capped at 1500 notes
-             * (1.0 + 0.1 * (self.total_hits() / 1500.0).min(1.0))
-    }
-
-    const fn total_hits(&self) -> f64 {
-        self.state.total_hits() as f64
-    }
-
     fn calculate_custom_accuracy(&self) -> f64 {
+        // Classic scoring doesn't distinguish n320 and n300 so the pp keep
+        // weighting them like osu!lazer does
+        if let ManiaScoring::ScoreV2 | ManiaScoring::Lazer = self.scoring {
+            return self.scoring.accuracy(&self.state);
+        }
+
         let ManiaScoreState {
             n320,
             n300,
@@ -903,6 +1033,68 @@ impl ManiaPerformanceInner {
     }
 }
 
+/// Values of the performance calculation that only depend on the difficulty
+/// attributes and mods.
+pub(crate) struct ManiaPerformanceInvariants {
+    multiplier: f64,
+    /// Star rating to pp curve of the stars, jack, chord, stream, technical,
+    /// and long note ratings.
+    star_curves: [f64; 6],
+    n_objects: u32,
+    full_len_bonus: f64,
+}
+
+impl ManiaPerformanceInvariants {
+    fn new(attrs: &ManiaDifficultyAttributes, mods: u32) -> Self {
+        // * Arbitrary initial value for scaling pp in order to standardize distributions across game modes.
+        // * The specific number has no intrinsic meaning and can be adjusted as needed.
+        let mut multiplier = 8.0;
+
+        if mods.nf() {
+            multiplier *= 0.75;
+        }
+
+        if mods.ez() {
+            multiplier *= 0.5;
+        }
+
+        let star_curves = [
+            attrs.stars,
+            attrs.jack,
+            attrs.chord,
+            attrs.stream,
+            attrs.technical,
+            attrs.long_note,
+        ]
+        .map(Self::compute_star_curve);
+
+        Self {
+            multiplier,
+            star_curves,
+            n_objects: attrs.n_objects,
+            full_len_bonus: Self::compute_len_bonus(attrs.n_objects),
+        }
+    }
+
+    fn compute_star_curve(stars: f64) -> f64 {
+        // * Star rating to pp curve
+        (stars - 0.15).max(0.05).powf(2.2)
+    }
+
+    fn compute_len_bonus(total_hits: u32) -> f64 {
+        // * Length bonus, capped at 1500 notes
+        1.0 + 0.1 * (f64::from(total_hits) / 1500.0).min(1.0)
+    }
+
+    fn len_bonus(&self, total_hits: u32) -> f64 {
+        if total_hits == self.n_objects {
+            self.full_len_bonus
+        } else {
+            Self::compute_len_bonus(total_hits)
+        }
+    }
+}
+
 fn custom_accuracy(n320: u32, n300: u32, n200: u32, n100: u32, n50: u32, total_hits: u32) -> f64 {
     let numerator = n320 * 32 + n300 * 30 + n200 * 20 + n100 * 10 + n50 * 5;
     let denominator = total_hits * 32;
@@ -910,6 +1102,77 @@ fn custom_accuracy(n320: u32, n300: u32, n200: u32, n100: u32, n50: u32, total_h
     f64::from(numerator) / f64::from(denominator)
 }
 
+/// Distribute the remaining hits for scoring models in which n320 and n300
+/// have different weights.
+///
+/// `counts` contains the amount of n320, n300, n200, n100, and n50 and
+/// `given` whether the corresponding amount was specified. Unspecified amounts
+/// are overwritten.
+fn distribute_weighted(
+    acc: f64,
+    weights: [u32; 5],
+    n_judgements: u32,
+    misses: u32,
+    given: [bool; 5],
+    counts: &mut [u32; 5],
+    priority: HitResultPriority,
+) {
+    let free: Vec<usize> = (0..counts.len()).filter(|&i| !given[i]).collect();
+
+    let n_given: u32 = counts
+        .iter()
+        .zip(given)
+        .filter(|(_, given)| *given)
+        .map(|(n, _)| n)
+        .sum();
+    let n_remaining = n_judgements.saturating_sub(n_given + misses);
+
+    let (Some(&best), Some(&worst)) = (free.first(), free.last()) else {
+        match priority {
+            HitResultPriority::BestCase => counts[0] += n_remaining,
+            HitResultPriority::WorstCase => counts[4] += n_remaining,
+        }
+
+        return;
+    };
+
+    for &i in free.iter() {
+        counts[i] = 0;
+    }
+
+    if n_remaining == 0 {
+        return;
+    }
+
+    let given_total: u32 = weights.iter().zip(counts.iter()).map(|(w, n)| w * n).sum();
+    let target_total = acc * f64::from(weights[0] * n_judgements) - f64::from(given_total);
+    let avg = target_total / f64::from(n_remaining);
+
+    // Best case uses the two adjacent hitresults around the average weight,
+    // worst case the most extreme ones so that bad hitresults are preferred.
+    let (hi, lo) = match priority {
+        HitResultPriority::BestCase => free
+            .windows(2)
+            .find(|pair| f64::from(weights[pair[1]]) <= avg)
+            .or_else(|| free.windows(2).last())
+            .map_or((best, best), |pair| (pair[0], pair[1])),
+        HitResultPriority::WorstCase => (best, worst),
+    };
+
+    if hi == lo {
+        counts[hi] = n_remaining;
+
+        return;
+    }
+
+    let (w_hi, w_lo) = (f64::from(weights[hi]), f64::from(weights[lo]));
+    let raw_hi = (target_total - w_lo * f64::from(n_remaining)) / (w_hi - w_lo);
+    let n_hi = raw_hi.round().clamp(0.0, f64::from(n_remaining)) as u32;
+
+    counts[hi] = n_hi;
+    counts[lo] = n_remaining - n_hi;
+}
+
 fn accuracy(n320: u32, n300: u32, n200: u32, n100: u32, n50: u32, misses: u32) -> f64 {
     let numerator = 6 * (n320 + n300) + 4 * n200 + 2 * n100 + n50;
     let denominator = 6 * (n320 + n300 + n200 + n100 + n50 + misses);
@@ -935,6 +1198,7 @@ mod tests {
     static ATTRS: OnceLock<ManiaDifficultyAttributes> = OnceLock::new();
 
     const N_OBJECTS: u32 = 594;
+    const N_HOLD_NOTES: u32 = 121;
 
     fn beatmap() -> Beatmap {
         Beatmap::from_path("./resources/1638954.osu").unwrap()
@@ -1280,6 +1544,121 @@ mod tests {
         assert_eq!(state, expected);
     }
 
+    #[test]
+    fn lazer_judges_tails() {
+        let attrs = attrs();
+        assert_eq!(attrs.n_hold_notes, N_HOLD_NOTES);
+
+        let state = ManiaPerformance::from(attrs.clone())
+            .scoring(ManiaScoring::Lazer)
+            .misses(2)
+            .generate_state();
+
+        assert_eq!(state.total_hits(), N_OBJECTS + N_HOLD_NOTES);
+        assert_eq!(state.n320, N_OBJECTS + N_HOLD_NOTES - 2);
+
+        let state = ManiaPerformance::from(attrs)
+            .scoring(ManiaScoring::ScoreV2)
+            .generate_state();
+
+        assert_eq!(state.total_hits(), N_OBJECTS);
+    }
+
+    #[test]
+    fn weighted_accuracy() {
+        for scoring in [ManiaScoring::ScoreV2, ManiaScoring::Lazer] {
+            for acc in [98.5, 95.0, 90.0, 70.0] {
+                let generate_state = |priority| {
+                    ManiaPerformance::from(attrs())
+                        .scoring(scoring)
+                        .accuracy(acc)
+                        .misses(3)
+                        .n100(5)
+                        .hitresult_priority(priority)
+                        .generate_state()
+                };
+
+                let best = generate_state(HitResultPriority::BestCase);
+                let worst = generate_state(HitResultPriority::WorstCase);
+
+                for state in [&best, &worst] {
+                    let n_judgements = scoring.n_judgements(N_OBJECTS, N_HOLD_NOTES);
+
+                    assert_eq!(state.total_hits(), n_judgements);
+                    assert_eq!(state.misses, 3);
+                    assert_eq!(state.n100, 5);
+
+                    let dist = (scoring.accuracy(state) - acc / 100.0).abs();
+                    assert!(
+                        dist < 1.0 / f64::from(n_judgements),
+                        "{scoring:?} {acc}: {state:?}"
+                    );
+                }
+
+                assert!(worst.n50 >= best.n50, "{scoring:?} {acc}");
+            }
+        }
+    }
+
+    #[test]
+    fn scoring_affects_pp() {
+        let state = ManiaScoreState {
+            n320: N_OBJECTS - 300,
+            n300: 200,
+            n200: 50,
+            n100: 30,
+            n50: 10,
+            misses: 10,
+        };
+
+        let calculate = |scoring| {
+            ManiaPerformance::from(attrs())
+                .scoring(scoring)
+                .state(state.clone())
+                .calculate()
+                .pp
+        };
+
+        let v1 = calculate(ManiaScoring::ScoreV1);
+        let v2 = calculate(ManiaScoring::ScoreV2);
+
+        assert!(v1 < v2, "{v1} vs {v2}");
+
+        let batch = batch::ManiaPerformanceBatch::new(attrs(), 0).scoring(ManiaScoring::ScoreV2);
+        assert!((batch.calculate_single(&state).pp - v2).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn key_mods_through_osu() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let calculate = |mods: u32| {
+            let Ok(Performance::Mania(mania)) = OsuPerformance::new(map.unchecked_as_converted())
+                .mods(mods)
+                .try_mode(GameMode::Mania)
+            else {
+                panic!("expected osu!mania performance");
+            };
+
+            mania.calculate().difficulty
+        };
+
+        let nomod = calculate(0);
+        let k4 = calculate(1 << 15);
+
+        let converted = map.unchecked_as_converted::<Mania>();
+        assert_eq!(nomod, converted.performance().calculate().difficulty);
+        assert_ne!(nomod, k4);
+
+        let k4_converted = ManiaBeatmap::try_from_ref_with_mods(&map, 1 << 15).unwrap();
+        let expected = Difficulty::new()
+            .mods(1 << 15)
+            .with_mode()
+            .calculate(&k4_converted);
+
+        assert_eq!(k4, expected);
+    }
+
     #[test]
     fn create() {
         let mut map = beatmap();
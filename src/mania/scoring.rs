@@ -0,0 +1,94 @@
+use super::score_state::ManiaScoreState;
+
+/// The scoring model of an osu!mania score.
+///
+/// It decides how accuracy is derived from hitresults and how many
+/// judgements a map consists of.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ManiaScoring {
+    /// osu!stable's classic scoring.
+    ///
+    /// n320 and n300 are worth the same and every hit object is judged once.
+    ScoreV1,
+    /// osu!stable's score v2.
+    ///
+    /// n320 are worth slightly more than n300 and every hit object is judged
+    /// once.
+    ScoreV2,
+    /// osu!lazer's scoring.
+    ///
+    /// n320 are worth slightly more than n300 and the heads and tails of long
+    /// notes are judged separately.
+    Lazer,
+}
+
+impl ManiaScoring {
+    pub(crate) const DEFAULT: Self = Self::ScoreV1;
+
+    /// The accuracy weights of n320, n300, n200, n100, and n50.
+    pub(crate) const fn weights(self) -> [u32; 5] {
+        match self {
+            Self::ScoreV1 => [300, 300, 200, 100, 50],
+            Self::ScoreV2 | Self::Lazer => [305, 300, 200, 100, 50],
+        }
+    }
+
+    /// The amount of judgements for the given amount of hit objects and long
+    /// notes.
+    pub const fn n_judgements(self, n_objects: u32, n_hold_notes: u32) -> u32 {
+        match self {
+            Self::ScoreV1 | Self::ScoreV2 => n_objects,
+            Self::Lazer => n_objects + n_hold_notes,
+        }
+    }
+
+    /// Calculate the accuracy between `0.0` and `1.0` for the given state.
+    pub fn accuracy(self, state: &ManiaScoreState) -> f64 {
+        let total_hits = state.total_hits();
+
+        if total_hits == 0 {
+            return 0.0;
+        }
+
+        let [w320, w300, w200, w100, w50] = self.weights();
+
+        let numerator = w320 * state.n320
+            + w300 * state.n300
+            + w200 * state.n200
+            + w100 * state.n100
+            + w50 * state.n50;
+
+        f64::from(numerator) / f64::from(w320 * total_hits)
+    }
+}
+
+impl Default for ManiaScoring {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accuracy() {
+        let state = ManiaScoreState {
+            n320: 10,
+            n300: 10,
+            n200: 0,
+            n100: 0,
+            n50: 0,
+            misses: 0,
+        };
+
+        assert!((ManiaScoring::ScoreV1.accuracy(&state) - state.accuracy()).abs() < f64::EPSILON);
+        assert!((ManiaScoring::ScoreV1.accuracy(&state) - 1.0).abs() < f64::EPSILON);
+        assert!((ManiaScoring::ScoreV2.accuracy(&state) - 605.0 / 610.0).abs() < f64::EPSILON);
+        assert_eq!(
+            ManiaScoring::ScoreV2.accuracy(&state),
+            ManiaScoring::Lazer.accuracy(&state)
+        );
+    }
+}
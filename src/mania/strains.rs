@@ -1,4 +1,7 @@
-use crate::{any::Difficulty, mania::difficulty::DifficultyValues};
+use crate::{
+    any::{difficulty::skills::StrainDecaySkill, Difficulty, HardestSection, StrainTimeline},
+    mania::difficulty::{skills::skillset::Skillsets, DifficultyValues, STAR_SCALING_FACTOR},
+};
 
 use super::convert::ManiaBeatmap;
 
@@ -9,17 +12,86 @@ use super::convert::ManiaBeatmap;
 pub struct ManiaStrains {
     /// Strain peaks of the strain skill.
     pub strains: Vec<f64>,
+    /// Strain peaks of the jack skill.
+    pub jack: Vec<f64>,
+    /// Strain peaks of the chord skill.
+    pub chord: Vec<f64>,
+    /// Strain peaks of the stream skill.
+    pub stream: Vec<f64>,
+    /// Strain peaks of the technical skill.
+    pub technical: Vec<f64>,
+    /// Strain peaks of the long note skill.
+    pub long_note: Vec<f64>,
 }
 
 impl ManiaStrains {
     /// Time between two strains in ms.
     pub const SECTION_LEN: f64 = 400.0;
+
+    /// Turn the strain peaks into a smoothed difficulty curve.
+    ///
+    /// See [`StrainTimeline`] for more information.
+    #[must_use]
+    pub fn timeline(&self, timeline: &StrainTimeline) -> Self {
+        let smooth = |peaks: &[f64]| {
+            timeline.smooth(
+                peaks,
+                Self::SECTION_LEN,
+                StrainDecaySkill::DECAY_WEIGHT,
+                |value| value * STAR_SCALING_FACTOR,
+            )
+        };
+
+        Self {
+            strains: smooth(&self.strains),
+            jack: smooth(&self.jack),
+            chord: smooth(&self.chord),
+            stream: smooth(&self.stream),
+            technical: smooth(&self.technical),
+            long_note: smooth(&self.long_note),
+        }
+    }
 }
 
 pub fn strains(difficulty: &Difficulty, converted: &ManiaBeatmap<'_>) -> ManiaStrains {
-    let values = DifficultyValues::calculate(difficulty, converted);
+    let converted = &converted.with_conversion_mods(difficulty.get_mods());
+    let DifficultyValues {
+        strain,
+        skillsets:
+            Skillsets {
+                jack,
+                chord,
+                stream,
+                technical,
+                long_note,
+            },
+        max_combo: _,
+    } = DifficultyValues::calculate(difficulty, converted);
 
     ManiaStrains {
-        strains: values.strain.get_curr_strain_peaks().into_vec(),
+        strains: strain.get_curr_strain_peaks().into_vec(),
+        jack: jack.get_curr_strain_peaks().into_vec(),
+        chord: chord.get_curr_strain_peaks().into_vec(),
+        stream: stream.get_curr_strain_peaks().into_vec(),
+        technical: technical.get_curr_strain_peaks().into_vec(),
+        long_note: long_note.get_curr_strain_peaks().into_vec(),
     }
 }
+
+pub fn hardest_section(
+    difficulty: &Difficulty,
+    converted: &ManiaBeatmap<'_>,
+    duration: f64,
+) -> Option<HardestSection> {
+    let converted = &converted.with_conversion_mods(difficulty.get_mods());
+    let DifficultyValues { strain, .. } = DifficultyValues::calculate(difficulty, converted);
+    let section_end = strain.section_end();
+
+    HardestSection::new(
+        &strain.get_curr_strain_peaks().into_vec(),
+        ManiaStrains::SECTION_LEN,
+        section_end,
+        difficulty.get_clock_rate(),
+        duration,
+    )
+}
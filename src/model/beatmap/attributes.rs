@@ -45,7 +45,7 @@ pub struct BeatmapAttributesBuilder {
 }
 
 impl BeatmapAttributesBuilder {
-    const OSU_MIN: f64 = 80.0;
+    pub(crate) const OSU_MIN: f64 = 80.0;
     const OSU_AVG: f64 = 50.0;
     const OSU_MAX: f64 = 20.0;
 
@@ -329,7 +329,7 @@ impl<M> From<&Converted<'_, M>> for BeatmapAttributesBuilder {
     }
 }
 
-fn difficulty_range(difficulty: f64, min: f64, mid: f64, max: f64) -> f64 {
+pub(crate) fn difficulty_range(difficulty: f64, min: f64, mid: f64, max: f64) -> f64 {
     if difficulty > 5.0 {
         mid + (max - mid) * (difficulty - 5.0) / 5.0
     } else if difficulty < 5.0 {
@@ -31,6 +31,9 @@ const INCOMPATIBLE_MODES: &str = "the gamemodes were incompatible";
 /// All other conversions are incompatible.
 pub struct Converted<'a, M> {
     map: Cow<'a, Beatmap>,
+    /// The borrowed map before conversion if
+    /// [`IGameMode::CONVERSION_DEPENDS_ON_MODS`].
+    source: Option<&'a Beatmap>,
     mode: PhantomData<M>,
 }
 
@@ -40,10 +43,32 @@ impl<'a, M> Converted<'a, M> {
     pub(crate) const fn new(map: Cow<'a, Beatmap>) -> Self {
         Self {
             map,
+            source: None,
             mode: PhantomData,
         }
     }
 
+    /// Same as [`Converted::new`] but also borrows the map that was
+    /// converted.
+    pub(crate) const fn with_source(map: Cow<'a, Beatmap>, source: &'a Beatmap) -> Self {
+        Self {
+            map,
+            source: Some(source),
+            mode: PhantomData,
+        }
+    }
+
+    /// The map before conversion.
+    ///
+    /// Only available if the map was converted from a [`&Beatmap`] to a mode
+    /// whose conversion depends on mods. Owned and mutable maps are converted
+    /// in place so their source is not available.
+    ///
+    /// [`&Beatmap`]: Beatmap
+    pub(crate) const fn source(&self) -> Option<&'a Beatmap> {
+        self.source
+    }
+
     /// Returns the internal [`Beatmap`].
     pub fn into_inner(self) -> Cow<'a, Beatmap> {
         self.map
@@ -56,7 +81,26 @@ impl<'a, M> Converted<'a, M> {
     /// shorter.
     #[must_use]
     pub fn as_owned(&'a self) -> Self {
-        Self::new(Cow::Borrowed(self.map.as_ref()))
+        Self {
+            map: Cow::Borrowed(self.map.as_ref()),
+            source: self.source,
+            mode: PhantomData,
+        }
+    }
+
+    /// The index of the last hit object that starts at or before `time` in
+    /// milliseconds.
+    ///
+    /// `time` is a timestamp within the map so the clock rate of mods is
+    /// not considered. To find the object at a timestamp of a play, multiply
+    /// the timestamp with the clock rate first.
+    ///
+    /// Returns `None` if no hit object starts at or before `time`.
+    pub fn object_index_at(&self, time: f64) -> Option<usize> {
+        self.map
+            .hit_objects
+            .partition_point(|h| h.start_time <= time)
+            .checked_sub(1)
     }
 }
 
@@ -106,15 +150,18 @@ impl<'a, M: IGameMode> Converted<'a, M> {
     ///
     /// [`&Beatmap`]: Beatmap
     pub fn try_from_ref(map: &'a Beatmap) -> Option<Self> {
-        let mut map = match M::check_convert(map) {
+        let mut converted = match M::check_convert(map) {
             ConvertStatus::Noop => return Some(Self::new(Cow::Borrowed(map))),
             ConvertStatus::Conversion => map.to_owned(),
             ConvertStatus::Incompatible => return None,
         };
 
-        match M::try_convert(&mut map) {
-            ConvertStatus::Conversion => Some(Self::new(Cow::Owned(map))),
-            ConvertStatus::Noop => Some(Self::new(Cow::Owned(map))),
+        match M::try_convert(&mut converted) {
+            ConvertStatus::Conversion if M::CONVERSION_DEPENDS_ON_MODS => {
+                Some(Self::with_source(Cow::Owned(converted), map))
+            }
+            ConvertStatus::Conversion => Some(Self::new(Cow::Owned(converted))),
+            ConvertStatus::Noop => Some(Self::new(Cow::Owned(converted))),
             ConvertStatus::Incompatible => None,
         }
     }
@@ -162,9 +209,11 @@ impl<'a, M: IGameMode> Converted<'a, M> {
     pub fn try_convert<N: IGameMode>(self) -> Result<Converted<'a, N>, Self> {
         match self.map {
             Cow::Borrowed(map) => Converted::<N>::try_from_ref(map).ok_or(self),
-            Cow::Owned(map) => {
-                Converted::<N>::try_from_owned(map).map_err(|map| Self::new(Cow::Owned(map)))
-            }
+            Cow::Owned(map) => Converted::<N>::try_from_owned(map).map_err(|map| Self {
+                map: Cow::Owned(map),
+                source: self.source,
+                mode: PhantomData,
+            }),
         }
     }
 
@@ -183,7 +232,11 @@ impl<'a, M: IGameMode> Converted<'a, M> {
 
 impl<M> Clone for Converted<'_, M> {
     fn clone(&self) -> Self {
-        Self::new(self.map.clone())
+        Self {
+            map: self.map.clone(),
+            source: self.source,
+            mode: PhantomData,
+        }
     }
 }
 
@@ -18,6 +18,8 @@ pub use self::{
     decode::{BeatmapState, ParseBeatmapError},
 };
 
+pub(crate) use self::attributes::difficulty_range;
+
 use super::{
     control_point::{
         difficulty_point_at, effect_point_at, timing_point_at, DifficultyPoint, EffectPoint,
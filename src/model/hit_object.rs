@@ -92,3 +92,15 @@ pub struct Spinner {
 pub struct HoldNote {
     pub duration: f64,
 }
+
+/// Which point in time of a hit object is considered when comparing it
+/// against a timestamp.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ObjectTime {
+    /// The time at which the object starts.
+    #[default]
+    Start,
+    /// The time at which the object ends, e.g. the end of a slider or
+    /// spinner.
+    End,
+}
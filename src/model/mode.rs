@@ -1,6 +1,6 @@
 pub use rosu_map::section::general::GameMode;
 
-use crate::Difficulty;
+use crate::{any::HardestSection, Difficulty};
 
 use super::beatmap::{Beatmap, Converted};
 
@@ -29,6 +29,13 @@ pub trait IGameMode: Sized {
     /// The type of a gradual performance calculator.
     type GradualPerformance;
 
+    /// Whether mods can affect the conversion of a beatmap.
+    ///
+    /// If so, [`Converted`] beatmaps that were converted from a borrowed map
+    /// keep a reference to it so that calculations can convert it again for
+    /// their mods.
+    const CONVERSION_DEPENDS_ON_MODS: bool = false;
+
     /// Check whether the map's mode can be converted to the current type.
     fn check_convert(map: &Beatmap) -> ConvertStatus;
 
@@ -47,6 +54,14 @@ pub trait IGameMode: Sized {
     /// processing the final skill values.
     fn strains(difficulty: &Difficulty, map: &Converted<'_, Self>) -> Self::Strains;
 
+    /// Find the contiguous section of a [`Converted`] beatmap with the
+    /// highest combined strain.
+    fn hardest_section(
+        difficulty: &Difficulty,
+        map: &Converted<'_, Self>,
+        duration: f64,
+    ) -> Option<HardestSection>;
+
     /// Create a performance calculator for a [`Converted`] beatmap.
     fn performance(map: Converted<'_, Self>) -> Self::Performance<'_>;
 
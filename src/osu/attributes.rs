@@ -23,6 +23,9 @@ pub struct OsuDifficultyAttributes {
     pub n_sliders: u32,
     /// The amount of spinners.
     pub n_spinners: u32,
+    /// The highest amount of spins per second that a spinner requires to be
+    /// cleared, adjusted to the clock rate.
+    pub spinner_sps: f64,
     /// The final star rating
     pub stars: f64,
     /// The maximum combo.
@@ -32,36 +32,57 @@ pub fn convert_objects(
     scaling_factor: &ScalingFactor,
     hr: bool,
     time_preempt: f64,
-    mut take: usize,
+    take: usize,
     attrs: &mut OsuDifficultyAttributes,
 ) -> Box<[OsuObject]> {
+    let mut osu_objects = create_objects(converted);
+    count_objects(&osu_objects, take, attrs);
+    stack_objects(converted, &mut osu_objects, hr, time_preempt);
+    apply_stack_offsets(&mut osu_objects, scaling_factor);
+
+    osu_objects
+}
+
+/// Create the objects including sliders' nested objects.
+///
+/// This does not depend on mods.
+pub fn create_objects(converted: &OsuBeatmap<'_>) -> Box<[OsuObject]> {
     let mut curve_bufs = CurveBuffers::default();
     // mean=5.16 | median=4
     let mut ticks_buf = Vec::new();
 
-    let mut osu_objects: Box<[_]> = converted
+    converted
         .hit_objects
         .iter()
         .map(|h| OsuObject::new(h, converted, &mut curve_bufs, &mut ticks_buf))
-        .inspect(|h| {
-            if take == 0 {
-                return;
-            }
+        .collect()
+}
 
-            take -= 1;
-            attrs.max_combo += 1;
+/// Count the first `take` objects and their max combo into `attrs`.
+pub fn count_objects(osu_objects: &[OsuObject], take: usize, attrs: &mut OsuDifficultyAttributes) {
+    for h in osu_objects.iter().take(take) {
+        attrs.max_combo += 1;
 
-            match h.kind {
-                OsuObjectKind::Circle => attrs.n_circles += 1,
-                OsuObjectKind::Slider(ref slider) => {
-                    attrs.n_sliders += 1;
-                    attrs.max_combo += slider.nested_objects.len() as u32;
-                }
-                OsuObjectKind::Spinner(_) => attrs.n_spinners += 1,
+        match h.kind {
+            OsuObjectKind::Circle => attrs.n_circles += 1,
+            OsuObjectKind::Slider(ref slider) => {
+                attrs.n_sliders += 1;
+                attrs.max_combo += slider.nested_objects.len() as u32;
             }
-        })
-        .collect();
+            OsuObjectKind::Spinner(_) => attrs.n_spinners += 1,
+        }
+    }
+}
 
+/// Reflect objects for HR and calculate their stack heights.
+///
+/// Only depends on HR and, through `time_preempt`, on AR.
+pub fn stack_objects(
+    converted: &OsuBeatmap<'_>,
+    osu_objects: &mut [OsuObject],
+    hr: bool,
+    time_preempt: f64,
+) {
     if hr {
         osu_objects
             .iter_mut()
@@ -73,11 +94,16 @@ pub fn convert_objects(
     let stack_threshold = time_preempt * f64::from(converted.stack_leniency);
 
     if converted.version >= 6 {
-        stacking(&mut osu_objects, stack_threshold);
+        stacking(osu_objects, stack_threshold);
     } else {
-        old_stacking(&mut osu_objects, stack_threshold);
+        old_stacking(osu_objects, stack_threshold);
     }
+}
 
+/// Apply the stack offsets based on the stack heights.
+///
+/// Only depends on CS.
+pub fn apply_stack_offsets(osu_objects: &mut [OsuObject], scaling_factor: &ScalingFactor) {
     for h in osu_objects.iter_mut() {
         h.stack_offset = scaling_factor.stack_offset(h.stack_height);
 
@@ -85,8 +111,6 @@ pub fn convert_objects(
             slider.lazy_end_pos += h.pos + h.stack_offset;
         }
     }
-
-    osu_objects
 }
 
 const STACK_DISTANCE: f32 = 3.0;
@@ -1,21 +1,28 @@
 use std::{cmp, mem};
 
+use rosu_map::section::general::GameMode;
+
 use crate::{
     any::difficulty::skills::Skill,
+    any::GradualSnapshot,
+    model::hit_object::ObjectTime,
     osu::{
         convert::convert_objects,
         object::{OsuObject, OsuObjectKind},
         OsuBeatmap,
     },
-    util::mods::Mods,
+    util::{checkpoints::Checkpoints, mods::Mods, object_times::ObjectTimes},
     Difficulty,
 };
 
 use self::osu_objects::OsuObjects;
 
 use super::{
-    object::OsuDifficultyObject, skills::OsuSkills, DifficultyValues, OsuDifficultyAttributes,
-    OsuDifficultySetup,
+    object::OsuDifficultyObject,
+    skills::OsuSkills,
+    spinner::SpinnerRequirements,
+    streams::{OsuStreamStats, StreamStatsBuilder},
+    DifficultyValues, OsuDifficultyAttributes, OsuDifficultySetup,
 };
 
 /// Gradually calculate the difficulty attributes of an osu!standard map.
@@ -58,11 +65,15 @@ pub struct OsuGradualDifficulty {
     pub(crate) difficulty: Difficulty,
     attrs: OsuDifficultyAttributes,
     skills: OsuSkills,
+    spinner: SpinnerRequirements,
+    streams: StreamStatsBuilder,
     // Lifetimes actually depend on `osu_objects` so this type is
     // self-referential. This field must be treated with great caution, moving
     // `osu_objects` will immediately invalidate `diff_objects`.
     diff_objects: Box<[OsuDifficultyObject<'static>]>,
     osu_objects: OsuObjects,
+    checkpoints: Checkpoints<Checkpoint>,
+    times: ObjectTimes,
     // Additional safety measure that this type can't be cloned as it would
     // invalidate `diff_objects`.
     _not_clonable: NotClonable,
@@ -70,6 +81,14 @@ pub struct OsuGradualDifficulty {
 
 struct NotClonable;
 
+/// The part of [`OsuGradualDifficulty`] that changes while processing objects.
+#[derive(Clone)]
+struct Checkpoint {
+    attrs: OsuDifficultyAttributes,
+    skills: OsuSkills,
+    streams: StreamStatsBuilder,
+}
+
 impl OsuGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!standard maps.
     pub fn new(difficulty: Difficulty, converted: &OsuBeatmap<'_>) -> Self {
@@ -80,6 +99,7 @@ impl OsuGradualDifficulty {
             map_attrs,
             mut attrs,
             time_preempt,
+            spinner,
         } = OsuDifficultySetup::new(&difficulty, converted);
 
         let osu_objects = convert_objects(
@@ -97,9 +117,14 @@ impl OsuGradualDifficulty {
         attrs.max_combo = 0;
 
         if let Some(h) = osu_objects.first() {
-            Self::increment_combo(h, &mut attrs);
+            Self::increment_combo(h, &spinner, &mut attrs);
         }
 
+        let times = ObjectTimes::new(
+            osu_objects.iter().map(|h| (h.start_time, h.end_time())),
+            difficulty.get_clock_rate(),
+        );
+
         let mut osu_objects = OsuObjects::new(osu_objects);
 
         let diff_objects = DifficultyValues::create_difficulty_objects(
@@ -112,19 +137,194 @@ impl OsuGradualDifficulty {
         let skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt);
         let diff_objects = extend_lifetime(diff_objects.into_boxed_slice());
 
+        let checkpoints = Checkpoints::new(Checkpoint {
+            attrs: attrs.clone(),
+            skills: skills.clone(),
+            streams: StreamStatsBuilder::default(),
+        });
+
         Self {
             idx: 0,
             difficulty,
             attrs,
             skills,
+            spinner,
+            streams: StreamStatsBuilder::default(),
             diff_objects,
             osu_objects,
+            checkpoints,
+            times,
             _not_clonable: NotClonable,
         }
     }
 
-    fn increment_combo(h: &OsuObject, attrs: &mut OsuDifficultyAttributes) {
+    /// Store a snapshot of the state after every `interval` processed hit
+    /// objects so that [`seek`] can jump back and forth quickly.
+    ///
+    /// Smaller intervals make seeking faster but require more memory.
+    /// Without an interval, no snapshots are stored and seeking backwards
+    /// restarts from the first object.
+    ///
+    /// [`seek`]: Self::seek
+    #[must_use]
+    pub fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
+
+    /// Specify whether [`advance_to_time`] compares timestamps against the
+    /// start or the end time of hit objects.
+    ///
+    /// Defaults to [`ObjectTime::Start`].
+    ///
+    /// [`advance_to_time`]: Self::advance_to_time
+    #[must_use]
+    pub const fn object_time(mut self, object_time: ObjectTime) -> Self {
+        self.times.set_object_time(object_time);
+
+        self
+    }
+
+    /// Calculate the difficulty attributes after the hit object at index
+    /// `idx`, regardless of how many hit objects were processed so far.
+    ///
+    /// The result is the same as calling `nth(idx)` on a new iterator and
+    /// afterwards, iteration continues with the object at index `idx + 1`.
+    /// Returns `None` if `idx` is out of bounds.
+    pub fn seek(&mut self, idx: usize) -> Option<OsuDifficultyAttributes> {
+        if idx >= self.idx + self.len() {
+            return None;
+        }
+
+        if let Some((checkpoint_idx, checkpoint)) = self.checkpoints.closest(self.idx, idx) {
+            let Checkpoint {
+                attrs,
+                skills,
+                streams,
+            } = checkpoint.clone();
+
+            self.idx = checkpoint_idx;
+            self.attrs = attrs;
+            self.skills = skills;
+            self.streams = streams;
+        }
+
+        self.nth(idx - self.idx)
+    }
+
+    /// Process all hit objects whose time is at or before the timestamp `time`
+    /// in milliseconds and return the resulting difficulty attributes.
+    ///
+    /// The timestamp is adjusted by the clock rate so it should be the
+    /// elapsed time of a play, e.g. with DT an object at `1500` ms in the
+    /// map is reached after `1000` ms.
+    ///
+    /// Returns `None` if no new object was processed.
+    pub fn advance_to_time(&mut self, time: f64) -> Option<OsuDifficultyAttributes> {
+        let count = self.times.count_until(time);
+
+        if count <= self.idx {
+            return None;
+        }
+
+        self.nth(count - self.idx - 1)
+    }
+
+    /// Capture the progress of the calculation in a compact
+    /// [`GradualSnapshot`].
+    pub fn snapshot(&self) -> GradualSnapshot {
+        GradualSnapshot::new(
+            GameMode::Osu,
+            &self.difficulty,
+            self.idx + self.len(),
+            self.idx,
+            self.times.object_time(),
+            self.checkpoints.interval(),
+        )
+    }
+
+    /// Resume a calculation from a [`GradualSnapshot`].
+    ///
+    /// Returns `None` if the snapshot was created for a different mode or if
+    /// the map's amount of objects differs from the snapshot's.
+    pub fn from_snapshot(snapshot: &GradualSnapshot, converted: &OsuBeatmap<'_>) -> Option<Self> {
+        if snapshot.mode() != GameMode::Osu {
+            return None;
+        }
+
+        let mut gradual =
+            Self::new(snapshot.difficulty().clone(), converted).object_time(snapshot.object_time());
+
+        if gradual.len() != snapshot.n_objects() {
+            return None;
+        }
+
+        if let Some(interval) = snapshot.checkpoint_interval() {
+            gradual = gradual.checkpoint_interval(interval);
+        }
+
+        for _ in 0..snapshot.n_processed() {
+            gradual.advance()?;
+        }
+
+        Some(gradual)
+    }
+
+    /// Process the next hit object without evaluating the skills.
+    fn advance(&mut self) -> Option<()> {
+        // The first difficulty object belongs to the second note since each
+        // difficulty object requires the current and the last note. Hence, if
+        // we're still on the first object, we don't have a difficulty object
+        // yet and just skip processing.
+        if self.idx > 0 {
+            let curr = self.diff_objects.get(self.idx - 1)?;
+
+            let aim_values = self
+                .skills
+                .aim_evaluator
+                .evaluate_diff_of(curr, &self.diff_objects);
+
+            Skill::new(&mut self.skills.aim, &self.diff_objects).process(curr, aim_values.aim);
+            Skill::new(&mut self.skills.flow_aim, &self.diff_objects)
+                .process(curr, aim_values.flow_aim);
+            Skill::new(&mut self.skills.jump_aim, &self.diff_objects)
+                .process(curr, aim_values.jump_aim);
+            Skill::new(&mut self.skills.raw_aim, &self.diff_objects)
+                .process(curr, aim_values.raw_aim);
+            Skill::new(&mut self.skills.speed, &self.diff_objects).process(curr);
+            Skill::new(&mut self.skills.stamina, &self.diff_objects).process(curr);
+            Skill::new(&mut self.skills.rhythm, &self.diff_objects).process(curr);
+            self.streams.process(curr);
+
+            Self::increment_combo(curr.base, &self.spinner, &mut self.attrs);
+        } else if self.osu_objects.is_empty() {
+            return None;
+        }
+
+        self.idx += 1;
+
+        self.checkpoints.save(self.idx, || Checkpoint {
+            attrs: self.attrs.clone(),
+            skills: self.skills.clone(),
+            streams: self.streams.clone(),
+        });
+
+        Some(())
+    }
+
+    /// Statistics about the streams of all objects processed so far.
+    pub fn stream_stats(&self) -> OsuStreamStats {
+        self.streams.build()
+    }
+
+    fn increment_combo(
+        h: &OsuObject,
+        spinner: &SpinnerRequirements,
+        attrs: &mut OsuDifficultyAttributes,
+    ) {
         attrs.max_combo += 1;
+        DifficultyValues::update_spinner_sps(h, spinner, attrs);
 
         match &h.kind {
             OsuObjectKind::Circle => attrs.n_circles += 1,
@@ -150,27 +350,7 @@ impl Iterator for OsuGradualDifficulty {
     type Item = OsuDifficultyAttributes;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // The first difficulty object belongs to the second note since each
-        // difficulty object requires the current and the last note. Hence, if
-        // we're still on the first object, we don't have a difficulty object
-        // yet and just skip processing.
-        if self.idx > 0 {
-            let curr = self.diff_objects.get(self.idx - 1)?;
-
-            Skill::new(&mut self.skills.aim, &self.diff_objects).process(curr);
-            Skill::new(&mut self.skills.flow_aim, &self.diff_objects).process(curr);
-            Skill::new(&mut self.skills.jump_aim, &self.diff_objects).process(curr);
-            Skill::new(&mut self.skills.raw_aim, &self.diff_objects).process(curr);
-            Skill::new(&mut self.skills.speed, &self.diff_objects).process(curr);
-            Skill::new(&mut self.skills.stamina, &self.diff_objects).process(curr);
-            Skill::new(&mut self.skills.rhythm, &self.diff_objects).process(curr);
-
-            Self::increment_combo(curr.base, &mut self.attrs);
-        } else if self.osu_objects.is_empty() {
-            return None;
-        }
-
-        self.idx += 1;
+        self.advance()?;
 
         let mut attrs = self.attrs.clone();
 
@@ -203,35 +383,8 @@ impl Iterator for OsuGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
-
-        let mut take = cmp::min(n, self.len().saturating_sub(1));
-
-        // The first note has no difficulty object
-        if self.idx == 0 && take > 0 {
-            take -= 1;
-            self.idx += 1;
-        }
-
-        let mut aim = Skill::new(&mut self.skills.aim, &self.diff_objects);
-        let mut flow_aim = Skill::new(&mut self.skills.flow_aim, &self.diff_objects);
-        let mut jump_aim = Skill::new(&mut self.skills.jump_aim, &self.diff_objects);
-        let mut raw_aim = Skill::new(&mut self.skills.raw_aim, &self.diff_objects);
-        let mut stamina = Skill::new(&mut self.skills.stamina, &self.diff_objects);
-        let mut rhythm = Skill::new(&mut self.skills.rhythm, &self.diff_objects);
-        let mut speed = Skill::new(&mut self.skills.speed, &self.diff_objects);
-
-        for curr in skip_iter.take(take) {
-            aim.process(curr);
-            flow_aim.process(curr);
-            jump_aim.process(curr);
-            raw_aim.process(curr);
-            stamina.process(curr);
-            rhythm.process(curr);
-            speed.process(curr);
-
-            Self::increment_combo(curr.base, &mut self.attrs);
-            self.idx += 1;
+        for _ in 0..cmp::min(n, self.len().saturating_sub(1)) {
+            self.advance()?;
         }
 
         self.next()
@@ -327,4 +480,67 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn seek() {
+        let converted = Beatmap::from_path("./resources/2785319.osu")
+            .unwrap()
+            .unchecked_into_converted::<Osu>();
+
+        let difficulty = Difficulty::new().mods(64);
+        let len = OsuGradualDifficulty::new(difficulty.clone(), &converted).len();
+
+        for interval in [None, Some(16)] {
+            let mut gradual = OsuGradualDifficulty::new(difficulty.clone(), &converted);
+
+            if let Some(interval) = interval {
+                gradual = gradual.checkpoint_interval(interval);
+            }
+
+            // The attributes after the first object contain NaN so index 0
+            // is only compared through the next object.
+            for idx in [100, 20, 150, 151, 1, len - 1, 31, 32, 33] {
+                let mut expected = OsuGradualDifficulty::new(difficulty.clone(), &converted);
+
+                assert_eq!(gradual.seek(idx), expected.nth(idx), "idx={idx}");
+                assert_eq!(gradual.next(), expected.next(), "idx={idx}");
+            }
+
+            let mut expected = OsuGradualDifficulty::new(difficulty.clone(), &converted);
+            let _ = expected.next();
+            let _ = gradual.seek(0);
+            assert_eq!(gradual.next(), expected.next());
+
+            assert!(gradual.seek(len).is_none());
+        }
+    }
+
+    #[test]
+    fn stream_stats() {
+        let converted = Beatmap::from_path("./resources/2785319.osu")
+            .unwrap()
+            .unchecked_into_converted::<Osu>();
+
+        let difficulty = Difficulty::new().mods(64);
+        let mut gradual = OsuGradualDifficulty::new(difficulty.clone(), &converted);
+
+        assert_eq!(gradual.stream_stats(), OsuStreamStats::default());
+
+        for n in [100, 250] {
+            let _ = gradual.nth(n - gradual.idx - 1);
+
+            let expected = difficulty
+                .clone()
+                .passed_objects(n as u32)
+                .with_mode()
+                .stream_stats(&converted);
+
+            assert_eq!(gradual.stream_stats(), expected);
+        }
+
+        let _ = gradual.by_ref().last();
+        let expected = difficulty.with_mode().stream_stats(&converted);
+
+        assert_eq!(gradual.stream_stats(), expected);
+    }
 }
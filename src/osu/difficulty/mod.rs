@@ -6,32 +6,49 @@ use crate::{
     osu::{
         convert::convert_objects,
         difficulty::{object::OsuDifficultyObject, scaling_factor::ScalingFactor},
-        object::OsuObject,
+        object::{OsuObject, OsuObjectKind},
         performance::PERFORMANCE_BASE_MULTIPLIER,
     },
     util::mods::Mods,
 };
 
-use self::skills::OsuSkills;
+use self::{skills::OsuSkills, spinner::SpinnerRequirements};
 
 use super::{attributes::OsuDifficultyAttributes, convert::OsuBeatmap};
 
 pub mod gradual;
-mod object;
+pub mod object;
+pub mod patterns;
 pub mod scaling_factor;
 pub mod skills;
+mod spinner;
+pub mod streams;
 
-const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
+pub const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
 
 const HD_FADE_IN_DURATION_MULTIPLIER: f64 = 0.4;
 const HD_FADE_OUT_DURATION_MULTIPLIER: f64 = 0.3;
 
 pub fn difficulty(difficulty: &Difficulty, converted: &OsuBeatmap<'_>) -> OsuDifficultyAttributes {
+    difficulty_from_values(DifficultyValues::calculate(difficulty, converted))
+}
+
+/// Evaluate the skills of the given [`DifficultyValues`].
+pub fn difficulty_from_values(values: DifficultyValues) -> OsuDifficultyAttributes {
     let DifficultyValues {
         skills:
-            OsuSkills { aim, flow_aim, jump_aim, raw_aim, speed, stamina, rhythm },
+            OsuSkills {
+                aim,
+                flow_aim,
+                jump_aim,
+                raw_aim,
+                speed,
+                stamina,
+                rhythm,
+                ..
+            },
         mut attrs,
-    } = DifficultyValues::calculate(difficulty, converted);
+    } = values;
 
     let aim_difficulty_value = aim.difficulty_value();
     let flow_aim_difficulty_value = flow_aim.difficulty_value();
@@ -58,10 +75,11 @@ pub fn difficulty(difficulty: &Difficulty, converted: &OsuBeatmap<'_>) -> OsuDif
 }
 
 pub struct OsuDifficultySetup {
-    scaling_factor: ScalingFactor,
+    pub scaling_factor: ScalingFactor,
     map_attrs: BeatmapAttributes,
-    attrs: OsuDifficultyAttributes,
-    time_preempt: f64,
+    pub attrs: OsuDifficultyAttributes,
+    pub time_preempt: f64,
+    spinner: SpinnerRequirements,
 }
 
 impl OsuDifficultySetup {
@@ -78,16 +96,57 @@ impl OsuDifficultySetup {
         };
 
         let time_preempt = f64::from((map_attrs.hit_windows.ar * clock_rate) as f32);
+        let spinner = SpinnerRequirements::new(&map_attrs);
 
         Self {
             scaling_factor,
             map_attrs,
             attrs,
             time_preempt,
+            spinner,
         }
     }
 }
 
+/// Convert the map's objects and pass the resulting difficulty objects of all
+/// passed objects to `f`.
+pub fn with_difficulty_objects<T>(
+    difficulty: &Difficulty,
+    converted: &OsuBeatmap<'_>,
+    f: impl FnOnce(&[OsuDifficultyObject<'_>]) -> T,
+) -> T {
+    let mods = difficulty.get_mods();
+    let take = difficulty.get_passed_objects();
+
+    let OsuDifficultySetup {
+        scaling_factor,
+        mut attrs,
+        time_preempt,
+        ..
+    } = OsuDifficultySetup::new(difficulty, converted);
+
+    let mut osu_objects = convert_objects(
+        converted,
+        &scaling_factor,
+        mods.hr(),
+        time_preempt,
+        take,
+        &mut attrs,
+    );
+
+    let diff_objects = DifficultyValues::create_difficulty_objects(
+        difficulty,
+        &scaling_factor,
+        osu_objects.iter_mut().map(Pin::new),
+        time_preempt,
+    );
+
+    // The first hit object has no difficulty object
+    let take_diff_objects = cmp::min(converted.hit_objects.len(), take).saturating_sub(1);
+
+    f(&diff_objects[..take_diff_objects])
+}
+
 pub struct DifficultyValues {
     pub skills: OsuSkills,
     pub attrs: OsuDifficultyAttributes,
@@ -95,6 +154,51 @@ pub struct DifficultyValues {
 
 impl DifficultyValues {
     pub fn calculate(difficulty: &Difficulty, converted: &OsuBeatmap<'_>) -> Self {
+        let mut setup = OsuDifficultySetup::new(difficulty, converted);
+
+        let mut osu_objects = convert_objects(
+            converted,
+            &setup.scaling_factor,
+            difficulty.get_mods().hr(),
+            setup.time_preempt,
+            difficulty.get_passed_objects(),
+            &mut setup.attrs,
+        );
+
+        Self::calculate_with_objects(difficulty, setup, &mut osu_objects)
+    }
+
+    /// Calculate the skills based on already converted objects.
+    ///
+    /// The objects must have been converted with the same `setup` and their
+    /// counts must already be stored in `setup.attrs`.
+    pub fn calculate_with_objects(
+        difficulty: &Difficulty,
+        setup: OsuDifficultySetup,
+        osu_objects: &mut [OsuObject],
+    ) -> Self {
+        Self::compute_slider_cursor_positions(osu_objects, &setup.scaling_factor);
+
+        let diff_objects = Self::create_difficulty_objects_from_positioned(
+            difficulty.get_clock_rate(),
+            &setup.scaling_factor,
+            osu_objects.iter(),
+            setup.time_preempt,
+        );
+
+        Self::calculate_with_diff_objects(difficulty, setup, osu_objects, &diff_objects)
+    }
+
+    /// Calculate the skills based on already created difficulty objects.
+    ///
+    /// The difficulty objects must have been created from `osu_objects` with
+    /// the same `setup`.
+    pub fn calculate_with_diff_objects(
+        difficulty: &Difficulty,
+        setup: OsuDifficultySetup,
+        osu_objects: &[OsuObject],
+        diff_objects: &[OsuDifficultyObject<'_>],
+    ) -> Self {
         let mods = difficulty.get_mods();
         let take = difficulty.get_passed_objects();
 
@@ -103,48 +207,142 @@ impl DifficultyValues {
             map_attrs,
             mut attrs,
             time_preempt,
-        } = OsuDifficultySetup::new(difficulty, converted);
+            spinner,
+        } = setup;
 
-        let mut osu_objects = convert_objects(
-            converted,
-            &scaling_factor,
-            mods.hr(),
-            time_preempt,
-            take,
-            &mut attrs,
-        );
+        for h in osu_objects.iter().take(take) {
+            Self::update_spinner_sps(h, &spinner, &mut attrs);
+        }
 
-        let osu_object_iter = osu_objects.iter_mut().map(Pin::new);
+        let mut skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt);
 
-        let diff_objects =
-            Self::create_difficulty_objects(difficulty, &scaling_factor, osu_object_iter, time_preempt);
+        // The first hit object has no difficulty object
+        let take_diff_objects = cmp::min(osu_objects.len(), take).saturating_sub(1);
 
-        let mut skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt);
+        Self::process_skills(&mut skills, diff_objects, take_diff_objects);
 
-        {
-            let mut aim = Skill::new(&mut skills.aim, &diff_objects);
-            let mut flow_aim = Skill::new(&mut skills.flow_aim, &diff_objects);
-            let mut jump_aim = Skill::new(&mut skills.jump_aim, &diff_objects);
-            let mut raw_aim = Skill::new(&mut skills.raw_aim, &diff_objects);
-            let mut stamina = Skill::new(&mut skills.stamina, &diff_objects);
-            let mut rhythm = Skill::new(&mut skills.rhythm, &diff_objects);
-            let mut speed = Skill::new(&mut skills.speed, &diff_objects);
-
-            // The first hit object has no difficulty object
-            let take_diff_objects = cmp::min(converted.hit_objects.len(), take).saturating_sub(1);
-
-            for hit_object in diff_objects.iter().take(take_diff_objects) {
-                aim.process(hit_object);
-                raw_aim.process(hit_object);
-                jump_aim.process(hit_object);
-                flow_aim.process(hit_object);
-                stamina.process(hit_object);
-                rhythm.process(hit_object);
-                speed.process(hit_object);
-            }
+        Self { skills, attrs }
+    }
+
+    /// Process the first `take` difficulty objects with all skills.
+    fn process_skills<'a>(
+        skills: &mut OsuSkills,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+        take: usize,
+    ) {
+        #[cfg(feature = "parallel")]
+        if take >= Self::PARALLEL_THRESHOLD {
+            return Self::process_skills_parallel(skills, diff_objects, take);
         }
 
-        Self { skills, attrs }
+        Self::process_skills_sequential(skills, diff_objects, take);
+    }
+
+    /// Process the first `take` difficulty objects with all skills on the
+    /// current thread.
+    ///
+    /// The aim values of each object are evaluated only once and shared
+    /// between all aim skills.
+    fn process_skills_sequential<'a>(
+        skills: &mut OsuSkills,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+        take: usize,
+    ) {
+        let mut aim = Skill::new(&mut skills.aim, diff_objects);
+        let mut flow_aim = Skill::new(&mut skills.flow_aim, diff_objects);
+        let mut jump_aim = Skill::new(&mut skills.jump_aim, diff_objects);
+        let mut raw_aim = Skill::new(&mut skills.raw_aim, diff_objects);
+        let mut stamina = Skill::new(&mut skills.stamina, diff_objects);
+        let mut rhythm = Skill::new(&mut skills.rhythm, diff_objects);
+        let mut speed = Skill::new(&mut skills.speed, diff_objects);
+
+        for hit_object in diff_objects.iter().take(take) {
+            let aim_values = skills
+                .aim_evaluator
+                .evaluate_diff_of(hit_object, diff_objects);
+
+            aim.process(hit_object, aim_values.aim);
+            raw_aim.process(hit_object, aim_values.raw_aim);
+            jump_aim.process(hit_object, aim_values.jump_aim);
+            flow_aim.process(hit_object, aim_values.flow_aim);
+            stamina.process(hit_object);
+            rhythm.process(hit_object);
+            speed.process(hit_object);
+        }
+    }
+
+    /// The minimum amount of difficulty objects for which skills are
+    /// processed on separate threads.
+    ///
+    /// For fewer objects, spawning the threads takes longer than it saves.
+    #[cfg(feature = "parallel")]
+    const PARALLEL_THRESHOLD: usize = 256;
+
+    /// Process the first `take` difficulty objects with all skills.
+    ///
+    /// Skills don't depend on each other so the aim skills, which share their
+    /// evaluated aim values, and the remaining skills each run on their own
+    /// thread. Since every skill still processes the objects in order, the
+    /// results are identical to a sequential calculation.
+    #[cfg(feature = "parallel")]
+    fn process_skills_parallel<'a>(
+        skills: &mut OsuSkills,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+        take: usize,
+    ) {
+        let OsuSkills {
+            aim_evaluator,
+            aim,
+            flow_aim,
+            jump_aim,
+            raw_aim,
+            speed,
+            stamina,
+            rhythm,
+        } = skills;
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                let mut stamina = Skill::new(stamina, diff_objects);
+
+                diff_objects
+                    .iter()
+                    .take(take)
+                    .for_each(|h| stamina.process(h));
+            });
+
+            scope.spawn(|| {
+                let mut rhythm = Skill::new(rhythm, diff_objects);
+
+                diff_objects
+                    .iter()
+                    .take(take)
+                    .for_each(|h| rhythm.process(h));
+            });
+
+            scope.spawn(|| {
+                let mut speed = Skill::new(speed, diff_objects);
+
+                diff_objects
+                    .iter()
+                    .take(take)
+                    .for_each(|h| speed.process(h));
+            });
+
+            let mut aim = Skill::new(aim, diff_objects);
+            let mut flow_aim = Skill::new(flow_aim, diff_objects);
+            let mut jump_aim = Skill::new(jump_aim, diff_objects);
+            let mut raw_aim = Skill::new(raw_aim, diff_objects);
+
+            for hit_object in diff_objects.iter().take(take) {
+                let aim_values = aim_evaluator.evaluate_diff_of(hit_object, diff_objects);
+
+                aim.process(hit_object, aim_values.aim);
+                raw_aim.process(hit_object, aim_values.raw_aim);
+                jump_aim.process(hit_object, aim_values.jump_aim);
+                flow_aim.process(hit_object, aim_values.flow_aim);
+            }
+        });
     }
 
     /// Process the difficulty values and store the results in `attrs`.
@@ -180,34 +378,74 @@ impl DifficultyValues {
         attrs.speed = speed_rating;
     }
 
+    /// Update the highest spins per second requirement if `h` is a spinner.
+    pub fn update_spinner_sps(
+        h: &OsuObject,
+        spinner: &SpinnerRequirements,
+        attrs: &mut OsuDifficultyAttributes,
+    ) {
+        if let OsuObjectKind::Spinner(ref s) = h.kind {
+            attrs.spinner_sps = attrs.spinner_sps.max(spinner.spins_per_second(s.duration));
+        }
+    }
+
     pub fn create_difficulty_objects<'a>(
         difficulty: &Difficulty,
         scaling_factor: &ScalingFactor,
         osu_objects: impl ExactSizeIterator<Item = Pin<&'a mut OsuObject>>,
-        time_preempt: f64
+        time_preempt: f64,
     ) -> Vec<OsuDifficultyObject<'a>> {
-        let take = difficulty.get_passed_objects();
-        let clock_rate = difficulty.get_clock_rate();
+        if difficulty.get_passed_objects() == 0 {
+            return Vec::new();
+        }
 
-        let mut osu_objects_iter = osu_objects
+        let osu_objects = osu_objects
             .map(|h| OsuDifficultyObject::compute_slider_cursor_pos(h, scaling_factor.radius))
-            .map(Pin::into_ref);
+            .map(|h| Pin::into_ref(h).get_ref());
+
+        Self::create_difficulty_objects_from_positioned(
+            difficulty.get_clock_rate(),
+            scaling_factor,
+            osu_objects,
+            time_preempt,
+        )
+    }
 
-        let Some(mut last) = osu_objects_iter.next().filter(|_| take > 0) else {
+    /// Compute the lazy cursor positions of all sliders.
+    ///
+    /// Must be called only once per object.
+    pub fn compute_slider_cursor_positions(
+        osu_objects: &mut [OsuObject],
+        scaling_factor: &ScalingFactor,
+    ) {
+        for h in osu_objects.iter_mut() {
+            OsuDifficultyObject::compute_slider_cursor_pos(Pin::new(h), scaling_factor.radius);
+        }
+    }
+
+    /// Create difficulty objects for objects whose slider cursor positions
+    /// have already been computed.
+    pub fn create_difficulty_objects_from_positioned<'a>(
+        clock_rate: f64,
+        scaling_factor: &ScalingFactor,
+        mut osu_objects: impl Iterator<Item = &'a OsuObject>,
+        time_preempt: f64,
+    ) -> Vec<OsuDifficultyObject<'a>> {
+        let Some(mut last) = osu_objects.next() else {
             return Vec::new();
         };
 
         let mut last_last = None;
         let mut last_diff_object: Option<OsuDifficultyObject> = None;
         let mut last_last_diff_object: Option<OsuDifficultyObject> = None;
-  
-        osu_objects_iter
+
+        osu_objects
             .enumerate()
             .map(|(idx, h)| {
                 let diff_object = OsuDifficultyObject::new(
-                    h.get_ref(),
-                    last.get_ref(),
-                    last_last.as_deref(),
+                    h,
+                    last,
+                    last_last,
                     last_diff_object,
                     last_last_diff_object,
                     clock_rate,
@@ -227,3 +465,70 @@ impl DifficultyValues {
             .collect()
     }
 }
+
+#[cfg(all(test, feature = "parallel"))]
+mod tests {
+    use crate::{osu::Osu, Beatmap};
+
+    use super::*;
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let converted = Beatmap::from_path("./resources/2785319.osu")
+            .unwrap()
+            .unchecked_into_converted::<Osu>();
+
+        for mods in [0, 8, 16, 64, 256, 8 + 16 + 64] {
+            let difficulty = Difficulty::new().mods(mods);
+            let mut setup = OsuDifficultySetup::new(&difficulty, &converted);
+
+            let mut osu_objects = convert_objects(
+                &converted,
+                &setup.scaling_factor,
+                mods.hr(),
+                setup.time_preempt,
+                usize::MAX,
+                &mut setup.attrs,
+            );
+
+            DifficultyValues::compute_slider_cursor_positions(
+                &mut osu_objects,
+                &setup.scaling_factor,
+            );
+
+            let diff_objects = DifficultyValues::create_difficulty_objects_from_positioned(
+                difficulty.get_clock_rate(),
+                &setup.scaling_factor,
+                osu_objects.iter(),
+                setup.time_preempt,
+            );
+
+            let take = diff_objects.len();
+            assert!(take >= DifficultyValues::PARALLEL_THRESHOLD);
+
+            let new_skills = || {
+                OsuSkills::new(
+                    mods,
+                    &setup.scaling_factor,
+                    &setup.map_attrs,
+                    setup.time_preempt,
+                )
+            };
+
+            let mut sequential = new_skills();
+            DifficultyValues::process_skills_sequential(&mut sequential, &diff_objects, take);
+
+            let mut parallel = new_skills();
+            DifficultyValues::process_skills_parallel(&mut parallel, &diff_objects, take);
+
+            let attrs = |skills| {
+                difficulty_from_values(DifficultyValues {
+                    skills,
+                    attrs: setup.attrs.clone(),
+                })
+            };
+
+            assert_eq!(attrs(sequential), attrs(parallel), "{mods}");
+        }
+    }
+}
@@ -13,7 +13,7 @@ use super::{
     HD_FADE_OUT_DURATION_MULTIPLIER,
 };
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct OsuDifficultyNoBase {
     pub idx: usize,
     pub start_time: f64,
@@ -33,6 +33,30 @@ pub struct OsuDifficultyNoBase {
     stream_bpm: f64,
 }
 
+impl OsuDifficultyNoBase {
+    /// Pair the values with the object they were created for.
+    pub const fn with_base(self, base: &OsuObject) -> OsuDifficultyObject<'_> {
+        OsuDifficultyObject {
+            idx: self.idx,
+            base,
+            start_time: self.start_time,
+            delta_time: self.delta_time,
+            strain_time: self.strain_time,
+            last_two_strain_time: self.last_two_strain_time,
+            raw_jump_dist: self.raw_jump_dist,
+            jump_dist: self.jump_dist,
+            base_flow: self.base_flow,
+            flow: self.flow,
+            travel_dist: self.travel_dist,
+            travel_time: self.travel_time,
+            angle: self.angle,
+            angle_leniency: self.angle_leniency,
+            preempt: self.preempt,
+            stream_bpm: self.stream_bpm,
+        }
+    }
+}
+
 impl From<OsuDifficultyObject<'_>> for OsuDifficultyNoBase {
     fn from(value: OsuDifficultyObject) -> Self {
         OsuDifficultyNoBase {
@@ -136,6 +160,11 @@ impl<'a> OsuDifficultyObject<'a> {
         this
     }
 
+    /// The BPM of a 1/4 stream with this object's strain time.
+    pub const fn stream_bpm(&self) -> f64 {
+        self.stream_bpm
+    }
+
     pub fn set_flow_values(
         &mut self,
         last_diff_object: Option<OsuDifficultyObject>,
@@ -0,0 +1,395 @@
+use std::f64::consts::PI;
+
+use crate::{
+    any::difficulty::converted::ConvertedDifficulty,
+    osu::{Osu, OsuBeatmap},
+    util::tags::tags,
+    Difficulty,
+};
+
+use super::{
+    object::OsuDifficultyObject,
+    skills::aim::{AimEvaluator, AimPattern},
+    with_difficulty_objects,
+};
+
+/// Objects that are further apart than this (in ms, w.r.t. clock rate) do not
+/// belong to the same section.
+const MAX_DELTA_TIME: f64 = 500.0;
+/// Objects with at least this much flow are considered part of a stream.
+pub(super) const FLOW_THRESHOLD: f64 = 0.5;
+/// Flowing objects need at least this stream BPM to be considered part of a
+/// stream, i.e. 1/2 rhythms are jumps.
+const MIN_STREAM_BPM: f64 = 120.0;
+/// Objects whose pattern weight is at least this much change their velocity
+/// or angle notably w.r.t. the previous objects.
+const IRREGULAR_PATTERN_WEIGHT: f64 = 1.25;
+/// Streamed objects that are spaced further than this are considered a spaced
+/// stream.
+const SPACED_STREAM_DIST: f64 = OsuDifficultyObject::NORMALIZED_RADIUS * 2.0;
+/// Jumps with a smaller angle than this go back towards the previous object.
+const BACK_AND_FORTH_ANGLE: f64 = PI / 4.0;
+
+const MIN_BURST_LEN: usize = 3;
+const MIN_STREAM_LEN: usize = 9;
+const MIN_DEATHSTREAM_LEN: usize = 33;
+const MIN_SECTION_LEN: usize = 4;
+const MIN_TECH_LEN: usize = 6;
+
+/// The kind of pattern of an [`OsuPatternSection`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OsuPatternKind {
+    /// Between 9 and 32 consecutive tightly spaced objects.
+    Stream,
+    /// At least 33 consecutive tightly spaced objects.
+    Deathstream,
+    /// Between 3 and 8 consecutive tightly spaced objects.
+    Burst,
+    /// Consecutive objects that are spaced apart.
+    Jump,
+    /// Jumps that move back and forth between two areas.
+    BackAndForth,
+    /// A stream whose objects don't overlap.
+    SpacedStream,
+    /// Frequent changes between streamed and jumped objects or between
+    /// velocities and angles.
+    AlternatingTech,
+    /// Consecutive sliders.
+    SliderSection,
+}
+
+/// A labeled section of an osu!standard map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuPatternSection {
+    /// The kind of pattern.
+    pub kind: OsuPatternKind,
+    /// Start time of the section's first object in ms.
+    ///
+    /// The time is **not** adjusted by the clock rate.
+    pub start_time: f64,
+    /// End time of the section's last object in ms.
+    ///
+    /// The time is **not** adjusted by the clock rate.
+    pub end_time: f64,
+    /// The amount of objects in the section.
+    pub n_objects: usize,
+    /// The average aim strain of the section's objects based on their pattern.
+    ///
+    /// Sections of the same kind can be compared by this value.
+    pub intensity: f64,
+}
+
+/// The result of classifying the patterns of an osu!standard map.
+///
+/// Objects that are not part of any distinct pattern are not contained in
+/// any section.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OsuPatterns {
+    /// The sections ordered by their start time.
+    pub sections: Vec<OsuPatternSection>,
+}
+
+impl OsuPatterns {
+    /// Return all pattern kinds whose sections make up at least `min_share`
+    /// of all classified objects, ordered by their share.
+    ///
+    /// `min_share` should be between `0.0` and `1.0`.
+    pub fn tags(&self, min_share: f64) -> Vec<OsuPatternKind> {
+        let sections = self
+            .sections
+            .iter()
+            .map(|section| (section.kind, section.n_objects));
+
+        tags(sections, min_share)
+    }
+}
+
+impl ConvertedDifficulty<'_, Osu> {
+    /// Segment a [`OsuBeatmap`] into sections of labeled patterns.
+    pub fn patterns(self, map: &OsuBeatmap<'_>) -> OsuPatterns {
+        patterns(self.inner(), map)
+    }
+}
+
+pub fn patterns(difficulty: &Difficulty, converted: &OsuBeatmap<'_>) -> OsuPatterns {
+    let sections = with_difficulty_objects(difficulty, converted, classify);
+
+    OsuPatterns { sections }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ObjectLabel {
+    Stream,
+    SpacedStream,
+    Jump,
+    BackAndForth,
+    Slider,
+}
+
+impl ObjectLabel {
+    fn new(curr: &OsuDifficultyObject<'_>) -> Option<Self> {
+        if curr.base.is_spinner() || curr.delta_time > MAX_DELTA_TIME {
+            return None;
+        }
+
+        let label = if curr.base.is_slider() {
+            Self::Slider
+        } else if curr.flow >= FLOW_THRESHOLD && curr.stream_bpm() >= MIN_STREAM_BPM {
+            if curr.jump_dist >= SPACED_STREAM_DIST {
+                Self::SpacedStream
+            } else {
+                Self::Stream
+            }
+        } else if curr.angle.is_some_and(|angle| angle < BACK_AND_FORTH_ANGLE) {
+            Self::BackAndForth
+        } else {
+            Self::Jump
+        };
+
+        Some(label)
+    }
+
+    const fn is_streamed(self) -> bool {
+        matches!(self, Self::Stream | Self::SpacedStream)
+    }
+
+    /// Whether the object changes its pattern notably w.r.t. the previous
+    /// objects based on the pattern weight that applies to the label.
+    fn is_irregular(self, pattern: &AimPattern) -> bool {
+        let pattern_weight = if self.is_streamed() {
+            pattern.flow_pattern_weight
+        } else {
+            pattern.jump_pattern_weight
+        };
+
+        pattern_weight >= IRREGULAR_PATTERN_WEIGHT
+    }
+
+    const fn kind(self, len: usize) -> Option<OsuPatternKind> {
+        let kind = match self {
+            Self::Stream if len >= MIN_DEATHSTREAM_LEN => OsuPatternKind::Deathstream,
+            Self::Stream if len >= MIN_STREAM_LEN => OsuPatternKind::Stream,
+            Self::Stream if len >= MIN_BURST_LEN => OsuPatternKind::Burst,
+            Self::SpacedStream if len >= MIN_BURST_LEN => OsuPatternKind::SpacedStream,
+            Self::Jump if len >= MIN_SECTION_LEN => OsuPatternKind::Jump,
+            Self::BackAndForth if len >= MIN_SECTION_LEN => OsuPatternKind::BackAndForth,
+            Self::Slider if len >= MIN_SECTION_LEN => OsuPatternKind::SliderSection,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+}
+
+/// Consecutive difficulty objects with the same label.
+struct Run {
+    label: Option<ObjectLabel>,
+    start: usize,
+    end: usize,
+}
+
+impl Run {
+    const fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+fn classify(diff_objects: &[OsuDifficultyObject<'_>]) -> Vec<OsuPatternSection> {
+    let patterns: Vec<_> = diff_objects
+        .iter()
+        .map(|curr| AimEvaluator::pattern(curr, diff_objects))
+        .collect();
+
+    let mut runs: Vec<Run> = Vec::new();
+
+    for (i, curr) in diff_objects.iter().enumerate() {
+        let label = ObjectLabel::new(curr);
+
+        match runs.last_mut() {
+            Some(run) if run.label == label => run.end = i + 1,
+            _ => runs.push(Run {
+                label,
+                start: i,
+                end: i + 1,
+            }),
+        }
+    }
+
+    let mut sections = Vec::new();
+
+    // Consecutive runs that are too short to be a section on their own.
+    // Changes are counted between streamed and jumped runs, as well as for
+    // objects with irregular patterns.
+    let mut tech_start = 0;
+    let mut tech_runs = 0;
+    let mut tech_changes = 0;
+
+    for (i, run) in runs.iter().enumerate() {
+        let kind = run.label.and_then(|label| label.kind(run.len()));
+
+        if let (Some(label), None) = (run.label, kind) {
+            if tech_runs == 0 {
+                tech_start = run.start;
+            } else if runs[i - 1]
+                .label
+                .is_some_and(|prev| prev.is_streamed() != label.is_streamed())
+            {
+                tech_changes += 1;
+            }
+
+            tech_changes += patterns[run.start..run.end]
+                .iter()
+                .filter(|pattern| label.is_irregular(pattern))
+                .count();
+
+            tech_runs += 1;
+
+            continue;
+        }
+
+        if tech_runs > 0 {
+            push_tech(
+                &mut sections,
+                diff_objects,
+                &patterns,
+                tech_start,
+                run.start,
+                tech_changes,
+            );
+            tech_runs = 0;
+            tech_changes = 0;
+        }
+
+        if let Some(kind) = kind {
+            sections.push(new_section(
+                kind,
+                diff_objects,
+                &patterns,
+                run.start,
+                run.end,
+            ));
+        }
+    }
+
+    if tech_runs > 0 {
+        let end = runs.last().map_or(0, |run| run.end);
+        push_tech(
+            &mut sections,
+            diff_objects,
+            &patterns,
+            tech_start,
+            end,
+            tech_changes,
+        );
+    }
+
+    sections
+}
+
+fn push_tech(
+    sections: &mut Vec<OsuPatternSection>,
+    diff_objects: &[OsuDifficultyObject<'_>],
+    patterns: &[AimPattern],
+    start: usize,
+    end: usize,
+    changes: usize,
+) {
+    if end - start >= MIN_TECH_LEN && changes >= 2 {
+        let section = new_section(
+            OsuPatternKind::AlternatingTech,
+            diff_objects,
+            patterns,
+            start,
+            end,
+        );
+
+        sections.push(section);
+    }
+}
+
+fn new_section(
+    kind: OsuPatternKind,
+    diff_objects: &[OsuDifficultyObject<'_>],
+    patterns: &[AimPattern],
+    start: usize,
+    end: usize,
+) -> OsuPatternSection {
+    let objects = &diff_objects[start..end];
+    let strain_sum: f64 = patterns[start..end]
+        .iter()
+        .map(|pattern| pattern.strain)
+        .sum();
+
+    OsuPatternSection {
+        kind,
+        start_time: objects.first().map_or(0.0, |h| h.base.start_time),
+        end_time: objects.last().map_or(0.0, |h| h.base.end_time()),
+        n_objects: objects.len(),
+        intensity: strain_sum / objects.len() as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Beatmap;
+
+    use super::*;
+
+    fn converted() -> OsuBeatmap<'static> {
+        Beatmap::from_path("./resources/2785319.osu")
+            .unwrap()
+            .unchecked_into_converted()
+    }
+
+    #[test]
+    fn sections_are_ordered() {
+        let converted = converted();
+        let patterns = Difficulty::new().with_mode::<Osu>().patterns(&converted);
+
+        assert!(!patterns.sections.is_empty());
+
+        for window in patterns.sections.windows(2) {
+            assert!(window[0].end_time <= window[1].start_time);
+        }
+
+        for section in patterns.sections.iter() {
+            assert!(section.start_time <= section.end_time);
+            assert!(section.intensity.is_finite());
+        }
+
+        assert!(!patterns.tags(0.1).is_empty());
+    }
+
+    #[test]
+    fn clock_rate_increases_intensity() {
+        let converted = converted();
+
+        let nomod = Difficulty::new().with_mode::<Osu>().patterns(&converted);
+        let dt = Difficulty::new()
+            .mods(64)
+            .with_mode::<Osu>()
+            .patterns(&converted);
+
+        let intensity = |patterns: &OsuPatterns| {
+            patterns
+                .sections
+                .iter()
+                .map(|section| section.intensity * section.n_objects as f64)
+                .sum::<f64>()
+        };
+
+        assert!(intensity(&dt) > intensity(&nomod));
+    }
+
+    #[test]
+    fn empty() {
+        let converted = Beatmap::from_bytes(&[])
+            .unwrap()
+            .unchecked_into_converted::<Osu>();
+
+        let patterns = Difficulty::new().with_mode::<Osu>().patterns(&converted);
+
+        assert!(patterns.sections.is_empty());
+        assert!(patterns.tags(0.0).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::VecDeque,
     f64::consts::PI,
+    slice,
 };
 
 use rosu_map::util::Pos;
@@ -23,35 +24,13 @@ const STRAIN_DECAY_BASE: f64 = 0.15;
 pub struct Aim {
     curr_strain: f64,
     inner: OsuStrainSkill,
-    evaluator: AimEvaluator,
-    flow_aim: bool,
-    jump_aim: bool,
-    raw_aim: bool,
 }
 
 impl Aim {
-    pub fn new(
-        radius: f64,
-        time_preempt: f64,
-        time_fade_in: f64,
-        mods: u32,
-        flow_aim: bool,
-        jump_aim: bool,
-        raw_aim: bool,
-    ) -> Self {
+    pub fn new() -> Self {
         Self {
             curr_strain: 0.0,
             inner: OsuStrainSkill::default(),
-            evaluator: AimEvaluator {
-                radius,
-                time_preempt,
-                time_fade_in,
-                mods,
-                preempt_hit_objects: VecDeque::new(),
-            },
-            flow_aim,
-            jump_aim,
-            raw_aim,
         }
     }
 
@@ -59,6 +38,11 @@ impl Aim {
         self.inner.get_curr_strain_peaks()
     }
 
+    /// The end time of the current strain section.
+    pub const fn section_end(&self) -> f64 {
+        self.inner.inner.curr_section_end
+    }
+
     pub fn difficulty_value(self) -> f64 {
         Self::static_difficulty_value(self.inner)
     }
@@ -103,7 +87,9 @@ impl<'a> Skill<'a, Aim> {
         &mut self.inner.inner.inner.curr_section_end
     }
 
-    pub fn process(&mut self, curr: &'a OsuDifficultyObject<'a>) {
+    /// Process `curr` with the aim value that the [`AimEvaluator`] evaluated
+    /// for this skill's variant.
+    pub fn process(&mut self, curr: &'a OsuDifficultyObject<'a>, aim_value: f64) {
         if curr.idx == 0 {
             *self.curr_section_end_mut() = (curr.start_time / OsuStrainSkill::SECTION_LEN).ceil()
                 * OsuStrainSkill::SECTION_LEN;
@@ -116,26 +102,45 @@ impl<'a> Skill<'a, Aim> {
             *self.curr_section_end_mut() += OsuStrainSkill::SECTION_LEN;
         }
 
-        let strain_value_at = self.strain_value_at(curr);
+        let strain_value_at = self.strain_value_at(curr, aim_value);
         *self.curr_section_peak_mut() = strain_value_at.max(self.curr_section_peak());
     }
 
-    fn strain_value_at(&mut self, curr: &'a OsuDifficultyObject<'a>) -> f64 {
+    fn strain_value_at(&mut self, curr: &'a OsuDifficultyObject<'a>, aim_value: f64) -> f64 {
         self.inner.curr_strain *= strain_decay(curr.delta_time, STRAIN_DECAY_BASE);
-        self.inner.curr_strain += self.inner.evaluator.evaluate_diff_of(
-            curr,
-            self.diff_objects,
-            self.inner.flow_aim,
-            self.inner.jump_aim,
-            self.inner.raw_aim,
-        ) * SKILL_MULTIPLIER;
+        self.inner.curr_strain += aim_value * SKILL_MULTIPLIER;
 
         self.inner.curr_strain
     }
 }
 
+/// The aim values of a single difficulty object for each [`Aim`] variant.
+#[derive(Copy, Clone, Debug)]
+pub struct AimValues {
+    pub aim: f64,
+    pub flow_aim: f64,
+    pub jump_aim: f64,
+    pub raw_aim: f64,
+}
+
+/// Pattern quantities of a single difficulty object that
+/// [`AimEvaluator::pattern`] evaluates.
+#[derive(Copy, Clone, Debug)]
+pub struct AimPattern {
+    /// The aim strain that the object adds based on its pattern alone.
+    pub strain: f64,
+    /// How much the velocity and angle changes w.r.t. the previous jumps
+    /// increase the jump aim.
+    pub jump_pattern_weight: f64,
+    /// How much the distance and angle changes w.r.t. the previous object
+    /// increase the flow aim.
+    pub flow_pattern_weight: f64,
+}
+
+/// Evaluates the aim values of all [`Aim`] variants at once so that the
+/// flow, jump, and reading computations are shared between them.
 #[derive(Clone)]
-struct AimEvaluator {
+pub struct AimEvaluator {
     time_preempt: f64,
     time_fade_in: f64,
     radius: f64,
@@ -144,37 +149,32 @@ struct AimEvaluator {
 }
 
 impl AimEvaluator {
-    fn evaluate_diff_of<'a>(
+    pub const fn new(radius: f64, time_preempt: f64, time_fade_in: f64, mods: u32) -> Self {
+        Self {
+            time_preempt,
+            time_fade_in,
+            radius,
+            mods,
+            preempt_hit_objects: VecDeque::new(),
+        }
+    }
+
+    pub fn evaluate_diff_of<'a>(
         &mut self,
         curr: &'a OsuDifficultyObject<'a>,
         diff_objects: &'a [OsuDifficultyObject<'a>],
-        flow_aim: bool,
-        jump_aim: bool,
-        raw_aim: bool,
-    ) -> f64 {
+    ) -> AimValues {
         let osu_curr_obj = curr;
 
-        let prev2s: Vec<OsuDifficultyObject> = curr
-            .previous(0, diff_objects)
-            .into_iter()
-            .chain(curr.previous(1, diff_objects)).copied()
-            .collect();
-
-        let aim = if flow_aim {
-            Self::calc_flow_aim_value(osu_curr_obj, prev2s.first())
-                * Self::calc_small_circle_bonus(self.radius)
-        } else if jump_aim {
-            Self::calc_jump_aim_value(osu_curr_obj, &prev2s, false)
-                * Self::calc_small_circle_bonus(self.radius)
-        } else if raw_aim {
-            Self::calc_flow_aim_value(osu_curr_obj, prev2s.first())
-                + Self::calc_jump_aim_value(osu_curr_obj, &prev2s, true)
-        } else {
-            (Self::calc_flow_aim_value(osu_curr_obj, prev2s.first())
-                + Self::calc_jump_aim_value(osu_curr_obj, &prev2s, false))
-                * Self::calc_small_circle_bonus(self.radius)
-        };
-        
+        let (flow_aim, jump_aim, raw_jump_aim) = Self::with_prev2s(curr, diff_objects, |prev2s| {
+            (
+                Self::calc_flow_aim_value(osu_curr_obj, prev2s.first()),
+                Self::calc_jump_aim_value(osu_curr_obj, prev2s, false),
+                Self::calc_jump_aim_value(osu_curr_obj, prev2s, true),
+            )
+        });
+        let small_circle_bonus = Self::calc_small_circle_bonus(self.radius);
+
         let reading_multiplier = Self::calc_reading_multiplier(
             &mut self.preempt_hit_objects,
             osu_curr_obj,
@@ -183,7 +183,50 @@ impl AimEvaluator {
             self.radius,
         );
 
-        aim * reading_multiplier
+        AimValues {
+            aim: (flow_aim + jump_aim) * small_circle_bonus * reading_multiplier,
+            flow_aim: flow_aim * small_circle_bonus * reading_multiplier,
+            jump_aim: jump_aim * small_circle_bonus * reading_multiplier,
+            raw_aim: (flow_aim + raw_jump_aim) * reading_multiplier,
+        }
+    }
+
+    /// The pattern of `curr` as it is evaluated for aim, i.e. without
+    /// reading, flashlight, or small circle bonuses.
+    pub fn pattern<'a>(
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+    ) -> AimPattern {
+        Self::with_prev2s(curr, diff_objects, |prev2s| {
+            let aim = Self::calc_flow_aim_value(curr, prev2s.first())
+                + Self::calc_jump_aim_value(curr, prev2s, false);
+
+            let distance = curr.jump_dist / OsuDifficultyObject::NORMALIZED_RADIUS;
+
+            AimPattern {
+                strain: aim * SKILL_MULTIPLIER,
+                jump_pattern_weight: Self::calc_jump_pattern_weight(curr, prev2s),
+                flow_pattern_weight: Self::calc_flow_pattern_weight(
+                    curr,
+                    prev2s.first(),
+                    distance,
+                ),
+            }
+        })
+    }
+
+    /// Call `f` with the up to two previous objects of `curr`, the most
+    /// recent one first.
+    fn with_prev2s<'a, T>(
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+        f: impl FnOnce(&[OsuDifficultyObject<'a>]) -> T,
+    ) -> T {
+        match (curr.previous(0, diff_objects), curr.previous(1, diff_objects)) {
+            (Some(prev), Some(prev_prev)) => f(&[*prev, *prev_prev]),
+            (Some(prev), None) => f(slice::from_ref(prev)),
+            (None, _) => f(&[]),
+        }
     }
 
     fn calc_jump_aim_value(
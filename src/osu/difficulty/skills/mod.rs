@@ -3,7 +3,10 @@ use stamina::Stamina;
 
 use crate::{model::beatmap::BeatmapAttributes, osu::object::OsuObject, util::mods::Mods};
 
-use self::{aim::Aim, speed::Speed};
+use self::{
+    aim::{Aim, AimEvaluator},
+    speed::Speed,
+};
 
 use super::{scaling_factor::ScalingFactor, HD_FADE_IN_DURATION_MULTIPLIER};
 
@@ -13,7 +16,9 @@ pub mod strain;
 pub mod rhythm;
 pub mod stamina;
 
+#[derive(Clone)]
 pub struct OsuSkills {
+    pub aim_evaluator: AimEvaluator,
     pub aim: Aim,
     pub flow_aim: Aim,
     pub jump_aim: Aim,
@@ -46,15 +51,17 @@ impl OsuSkills {
             400.0 * (time_preempt / OsuObject::PREEMPT_MIN).min(1.0)
         };
 
-        let aim = Aim::new(scaling_factor.radius, time_preempt, time_fade_in, mods, false, false, false);
-        let flow_aim = Aim::new(scaling_factor.radius, time_preempt, time_fade_in, mods, true, false, false);
-        let jump_aim = Aim::new(scaling_factor.radius, time_preempt, time_fade_in, mods, false, true, false);
-        let raw_aim = Aim::new(scaling_factor.radius, time_preempt, time_fade_in, mods, false, false, true);
+        let aim_evaluator = AimEvaluator::new(scaling_factor.radius, time_preempt, time_fade_in, mods);
+        let aim = Aim::new();
+        let flow_aim = Aim::new();
+        let jump_aim = Aim::new();
+        let raw_aim = Aim::new();
         let speed = Speed::new();
         let stamina = Stamina::new();
         let rhythm = RhythmComplexity::new();
 
         Self {
+            aim_evaluator,
             aim,
             flow_aim,
             jump_aim,
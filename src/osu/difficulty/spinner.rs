@@ -0,0 +1,84 @@
+use crate::model::beatmap::{difficulty_range, BeatmapAttributes, BeatmapAttributesBuilder};
+
+/// Spin requirements that are shared by all spinners of a map.
+#[derive(Copy, Clone, Debug)]
+pub struct SpinnerRequirements {
+    min_rotations_per_second: f64,
+    clock_rate: f64,
+}
+
+impl SpinnerRequirements {
+    const MIN_RPS: f64 = 3.0;
+    const AVG_RPS: f64 = 5.0;
+    const MAX_RPS: f64 = 7.5;
+
+    pub fn new(map_attrs: &BeatmapAttributes) -> Self {
+        let clock_rate = map_attrs.clock_rate;
+
+        // The OD of `map_attrs` already considers the clock rate whereas
+        // spinners only consider the mods-adjusted OD.
+        let od = (BeatmapAttributesBuilder::OSU_MIN - map_attrs.hit_windows.od * clock_rate) / 6.0;
+
+        let min_rotations_per_second =
+            difficulty_range(od, Self::MIN_RPS, Self::AVG_RPS, Self::MAX_RPS);
+
+        Self {
+            min_rotations_per_second,
+            clock_rate,
+        }
+    }
+
+    /// The amount of spins that are required to clear a spinner of the given
+    /// duration.
+    pub fn spins_required(&self, duration: f64) -> u32 {
+        (duration / 1000.0 * self.min_rotations_per_second) as u32
+    }
+
+    /// The amount of spins per second that are required to clear a spinner of
+    /// the given duration, measured in real time i.e. w.r.t. the clock rate.
+    pub fn spins_per_second(&self, duration: f64) -> f64 {
+        let real_secs = duration / 1000.0 / self.clock_rate;
+
+        if real_secs <= 0.0 {
+            return 0.0;
+        }
+
+        f64::from(self.spins_required(duration)) / real_secs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{osu::OsuBeatmap, Beatmap, Difficulty};
+
+    use super::*;
+
+    fn requirements(difficulty: &Difficulty) -> SpinnerRequirements {
+        let converted: OsuBeatmap<'_> = Beatmap::from_path("./resources/2785319.osu")
+            .unwrap()
+            .unchecked_into_converted();
+
+        let map_attrs = converted.attributes().difficulty(difficulty).build();
+
+        SpinnerRequirements::new(&map_attrs)
+    }
+
+    #[test]
+    fn clock_rate_increases_sps() {
+        let nomod = requirements(&Difficulty::new());
+        let dt = requirements(&Difficulty::new().mods(64));
+
+        assert_eq!(nomod.spins_required(3000.0), dt.spins_required(3000.0));
+        assert!((nomod.spins_per_second(3000.0) * 1.5 - dt.spins_per_second(3000.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn od_increases_spins() {
+        let low = requirements(&Difficulty::new().od(0.0, true));
+        let high = requirements(&Difficulty::new().od(10.0, true));
+
+        assert_eq!(low.spins_required(2000.0), 6);
+        assert_eq!(high.spins_required(2000.0), 15);
+        assert!(low.spins_per_second(0.0).abs() < f64::EPSILON);
+    }
+}
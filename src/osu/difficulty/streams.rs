@@ -0,0 +1,269 @@
+use crate::{
+    any::difficulty::converted::ConvertedDifficulty,
+    osu::{Osu, OsuBeatmap},
+    util::pplus,
+    Difficulty,
+};
+
+use super::{object::OsuDifficultyObject, patterns::FLOW_THRESHOLD, with_difficulty_objects};
+
+/// Statistics about the streams of an osu!standard map.
+///
+/// All times and BPM values are adjusted by the clock rate.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OsuStreamStats {
+    /// The amount of objects of the longest stream or burst.
+    pub longest_stream: u32,
+    /// The duration of the longest stream or burst in ms.
+    pub longest_stream_ms: f64,
+    /// The amount of streams for each length bucket.
+    ///
+    /// See [`OsuStreamStats::BUCKETS`] for the bucket bounds.
+    pub n_streams: [u32; 4],
+    /// The highest BPM of a stream, assuming 1/4 snapping.
+    pub peak_bpm: f64,
+    /// The average BPM of all streams, weighted by their amount of objects.
+    pub avg_bpm: f64,
+    /// The amount of bursts.
+    pub n_bursts: u32,
+    /// The amount of bursts per minute.
+    pub burst_density: f64,
+}
+
+impl OsuStreamStats {
+    /// The minimum amount of objects of a burst.
+    pub const MIN_BURST_LEN: u32 = 3;
+
+    /// The minimum amount of objects of a stream. Shorter streams are
+    /// considered bursts.
+    pub const MIN_STREAM_LEN: u32 = 9;
+
+    /// The lower bound (inclusive) of the amount of objects of each bucket in
+    /// [`OsuStreamStats::n_streams`].
+    pub const BUCKETS: [u32; 4] = [Self::MIN_STREAM_LEN, 17, 33, 65];
+}
+
+impl ConvertedDifficulty<'_, Osu> {
+    /// Gather statistics about the streams of an [`OsuBeatmap`].
+    pub fn stream_stats(self, map: &OsuBeatmap<'_>) -> OsuStreamStats {
+        stream_stats(self.inner(), map)
+    }
+}
+
+pub fn stream_stats(difficulty: &Difficulty, converted: &OsuBeatmap<'_>) -> OsuStreamStats {
+    with_difficulty_objects(difficulty, converted, |diff_objects| {
+        let mut builder = StreamStatsBuilder::default();
+
+        for curr in diff_objects {
+            builder.process(curr);
+        }
+
+        builder.build()
+    })
+}
+
+/// Consecutive streamed objects.
+#[derive(Copy, Clone, Debug, Default)]
+struct Stream {
+    /// Amount of difficulty objects i.e. the amount of objects minus one.
+    n_diff_objects: u32,
+    duration: f64,
+    strain_time_sum: f64,
+    last_strain_time: f64,
+}
+
+impl Stream {
+    const fn n_objects(&self) -> u32 {
+        self.n_diff_objects + 1
+    }
+
+    fn bpm(&self) -> f64 {
+        15_000.0 * f64::from(self.n_diff_objects) / self.strain_time_sum
+    }
+}
+
+/// Totals of all finished streams.
+#[derive(Clone, Debug, Default)]
+struct StreamTotals {
+    stats: OsuStreamStats,
+    /// Sum of each stream's BPM times its amount of objects.
+    weighted_bpm_sum: f64,
+    n_stream_objects: u32,
+}
+
+impl StreamTotals {
+    fn add(&mut self, stream: Stream) {
+        let n_objects = stream.n_objects();
+
+        if n_objects < OsuStreamStats::MIN_BURST_LEN {
+            return;
+        }
+
+        let stats = &mut self.stats;
+
+        if n_objects > stats.longest_stream {
+            stats.longest_stream = n_objects;
+            stats.longest_stream_ms = stream.duration;
+        }
+
+        if n_objects < OsuStreamStats::MIN_STREAM_LEN {
+            stats.n_bursts += 1;
+
+            return;
+        }
+
+        let bucket = OsuStreamStats::BUCKETS
+            .iter()
+            .rposition(|&min| n_objects >= min)
+            .unwrap_or(0);
+
+        stats.n_streams[bucket] += 1;
+
+        let bpm = stream.bpm();
+        stats.peak_bpm = stats.peak_bpm.max(bpm);
+        self.weighted_bpm_sum += bpm * f64::from(n_objects);
+        self.n_stream_objects += n_objects;
+    }
+}
+
+/// Incrementally gathers [`OsuStreamStats`].
+#[derive(Clone, Debug, Default)]
+pub struct StreamStatsBuilder {
+    totals: StreamTotals,
+    curr: Option<Stream>,
+    first_start_time: Option<f64>,
+    last_start_time: f64,
+}
+
+impl StreamStatsBuilder {
+    pub fn process(&mut self, curr: &OsuDifficultyObject<'_>) {
+        self.first_start_time
+            .get_or_insert(curr.start_time - curr.delta_time);
+        self.last_start_time = curr.start_time;
+
+        if curr.base.is_spinner() || curr.flow < FLOW_THRESHOLD {
+            if let Some(stream) = self.curr.take() {
+                self.totals.add(stream);
+            }
+
+            return;
+        }
+
+        match self.curr {
+            Some(ref mut stream)
+                if pplus::is_roughly_equal(curr.strain_time, stream.last_strain_time) =>
+            {
+                stream.n_diff_objects += 1;
+                stream.duration += curr.delta_time;
+                stream.strain_time_sum += curr.strain_time;
+                stream.last_strain_time = curr.strain_time;
+            }
+            _ => {
+                if let Some(stream) = self.curr.take() {
+                    self.totals.add(stream);
+                }
+
+                self.curr = Some(Stream {
+                    n_diff_objects: 1,
+                    duration: curr.delta_time,
+                    strain_time_sum: curr.strain_time,
+                    last_strain_time: curr.strain_time,
+                });
+            }
+        }
+    }
+
+    /// Create the [`OsuStreamStats`] of all objects processed so far.
+    pub fn build(&self) -> OsuStreamStats {
+        let mut totals = self.totals.clone();
+
+        if let Some(stream) = self.curr {
+            totals.add(stream);
+        }
+
+        let StreamTotals {
+            mut stats,
+            weighted_bpm_sum,
+            n_stream_objects,
+        } = totals;
+
+        if n_stream_objects > 0 {
+            stats.avg_bpm = weighted_bpm_sum / f64::from(n_stream_objects);
+        }
+
+        let minutes = (self.last_start_time - self.first_start_time.unwrap_or(0.0)) / 60_000.0;
+
+        if minutes > 0.0 {
+            stats.burst_density = f64::from(stats.n_bursts) / minutes;
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{any::GradualDifficulty, Beatmap};
+
+    use super::*;
+
+    fn converted() -> OsuBeatmap<'static> {
+        Beatmap::from_path("./resources/2785319.osu")
+            .unwrap()
+            .unchecked_into_converted()
+    }
+
+    #[test]
+    fn stats() {
+        let converted = converted();
+        let stats = Difficulty::new().with_mode().stream_stats(&converted);
+
+        assert!(stats.longest_stream >= OsuStreamStats::MIN_STREAM_LEN);
+        assert!(stats.longest_stream_ms > 0.0);
+        assert!(stats.n_streams.iter().sum::<u32>() > 0);
+        assert!(stats.n_bursts > 0);
+        assert!(stats.peak_bpm >= stats.avg_bpm);
+        assert!(stats.avg_bpm > 0.0);
+    }
+
+    #[test]
+    fn clock_rate() {
+        let converted = converted();
+
+        let nomod = Difficulty::new().with_mode().stream_stats(&converted);
+        let dt = Difficulty::new()
+            .mods(64)
+            .with_mode()
+            .stream_stats(&converted);
+
+        assert!(dt.peak_bpm > nomod.peak_bpm);
+        assert!(dt.avg_bpm > nomod.avg_bpm);
+    }
+
+    #[test]
+    fn empty() {
+        let converted = Beatmap::from_bytes(&[])
+            .unwrap()
+            .unchecked_into_converted::<Osu>();
+
+        let stats = Difficulty::new().with_mode().stream_stats(&converted);
+
+        assert_eq!(stats, OsuStreamStats::default());
+    }
+
+    #[test]
+    fn any_gradual() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new().mods(64);
+
+        let mut gradual = GradualDifficulty::new(difficulty.clone(), &map);
+        let _ = gradual.nth(usize::MAX);
+
+        let expected = difficulty.with_mode().stream_stats(&converted());
+        assert_eq!(gradual.stream_stats(), Some(expected));
+
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let gradual = GradualDifficulty::new(Difficulty::new(), &map);
+        assert_eq!(gradual.stream_stats(), None);
+    }
+}
@@ -1,6 +1,7 @@
 use rosu_map::util::Pos;
 
 use crate::{
+    any::HardestSection,
     model::{
         beatmap::Beatmap,
         mode::{ConvertStatus, IGameMode},
@@ -11,8 +12,13 @@ use crate::{
 pub use self::{
     attributes::{OsuDifficultyAttributes, OsuPerformanceAttributes},
     convert::OsuBeatmap,
-    difficulty::gradual::OsuGradualDifficulty,
-    performance::{gradual::OsuGradualPerformance, OsuPerformance},
+    difficulty::{
+        gradual::OsuGradualDifficulty,
+        patterns::{OsuPatternKind, OsuPatternSection, OsuPatterns},
+        streams::OsuStreamStats,
+    },
+    performance::{batch::OsuPerformanceBatch, gradual::OsuGradualPerformance, OsuPerformance},
+    prepared::OsuPreparedMap,
     score_state::OsuScoreState,
     strains::OsuStrains,
 };
@@ -22,6 +28,7 @@ mod convert;
 mod difficulty;
 mod object;
 mod performance;
+mod prepared;
 mod score_state;
 mod strains;
 
@@ -58,6 +65,14 @@ impl IGameMode for Osu {
         strains::strains(difficulty, converted)
     }
 
+    fn hardest_section(
+        difficulty: &Difficulty,
+        converted: &OsuBeatmap<'_>,
+        duration: f64,
+    ) -> Option<HardestSection> {
+        strains::hardest_section(difficulty, converted, duration)
+    }
+
     fn performance(map: OsuBeatmap<'_>) -> Self::Performance<'_> {
         OsuPerformance::new(map)
     }
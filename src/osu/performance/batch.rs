@@ -0,0 +1,86 @@
+use crate::osu::{OsuDifficultyAttributes, OsuPerformanceAttributes, OsuScoreState};
+
+use super::{calculate_effective_misses, OsuPerformanceInner, OsuPerformanceInvariants};
+
+/// Calculate the performance attributes of many scores on the same
+/// osu!standard map.
+///
+/// All values that only depend on the map and mods are computed once upfront
+/// so this is considerably faster than creating an [`OsuPerformance`] for
+/// each score while producing the same results.
+///
+/// Note that score states are used as is, i.e. unlike [`OsuPerformance`],
+/// missing hitresults are not generated so every state should be complete.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty};
+/// use rosu_pp::osu::{Osu, OsuPerformanceBatch, OsuScoreState};
+///
+/// let converted = Beatmap::from_path("./resources/2785319.osu")
+///     .unwrap()
+///     .unchecked_into_converted::<Osu>();
+///
+/// let mods = 8 + 64; // HDDT
+/// let attrs = Difficulty::new().mods(mods).with_mode().calculate(&converted);
+/// let n_objects = attrs.n_objects();
+///
+/// let states: Vec<_> = (0..10)
+///     .map(|misses| OsuScoreState {
+///         max_combo: attrs.max_combo / (misses + 1),
+///         n300: n_objects - misses,
+///         misses,
+///         ..Default::default()
+///     })
+///     .collect();
+///
+/// let batch = OsuPerformanceBatch::new(attrs, mods);
+///
+/// for performance in batch.calculate(&states) {
+///     println!("PP: {}", performance.pp);
+/// }
+/// ```
+///
+/// [`OsuPerformance`]: crate::osu::OsuPerformance
+pub struct OsuPerformanceBatch {
+    attrs: OsuDifficultyAttributes,
+    mods: u32,
+    invariants: OsuPerformanceInvariants,
+}
+
+impl OsuPerformanceBatch {
+    /// Prepare the batch calculation for the given difficulty attributes.
+    ///
+    /// `mods` must be the same mods that the attributes were calculated with.
+    pub fn new(attrs: OsuDifficultyAttributes, mods: u32) -> Self {
+        let invariants = OsuPerformanceInvariants::new(&attrs, mods);
+
+        Self {
+            attrs,
+            mods,
+            invariants,
+        }
+    }
+
+    /// Calculate the performance attributes for each score state.
+    pub fn calculate(&self, states: &[OsuScoreState]) -> Vec<OsuPerformanceAttributes> {
+        states
+            .iter()
+            .map(|state| self.calculate_single(state))
+            .collect()
+    }
+
+    /// Calculate the performance attributes for a single score state.
+    pub fn calculate_single(&self, state: &OsuScoreState) -> OsuPerformanceAttributes {
+        let inner = OsuPerformanceInner {
+            attrs: self.attrs.clone(),
+            mods: self.mods,
+            acc: state.accuracy(),
+            state: *state,
+            effective_miss_count: calculate_effective_misses(&self.attrs, state),
+        };
+
+        inner.calculate_with(&self.invariants)
+    }
+}
@@ -1,4 +1,6 @@
 use crate::{
+    any::GradualSnapshot,
+    model::hit_object::ObjectTime,
     osu::{OsuBeatmap, OsuGradualDifficulty},
     Difficulty,
 };
@@ -91,6 +93,32 @@ impl OsuGradualPerformance {
         Self { difficulty }
     }
 
+    /// Store a snapshot of the state after every `interval` processed
+    /// objects so that [`seek`] can jump back and forth quickly.
+    ///
+    /// See [`OsuGradualDifficulty::checkpoint_interval`] for more information.
+    ///
+    /// [`seek`]: Self::seek
+    #[must_use]
+    pub fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.difficulty = self.difficulty.checkpoint_interval(interval);
+
+        self
+    }
+
+    /// Specify whether [`advance_to_time`] compares timestamps against the
+    /// start or the end time of objects.
+    ///
+    /// See [`OsuGradualDifficulty::object_time`] for more information.
+    ///
+    /// [`advance_to_time`]: Self::advance_to_time
+    #[must_use]
+    pub fn object_time(mut self, object_time: ObjectTime) -> Self {
+        self.difficulty = self.difficulty.object_time(object_time);
+
+        self
+    }
+
     /// Process the next hit object and calculate the performance attributes
     /// for the resulting score state.
     pub fn next(&mut self, state: OsuScoreState) -> Option<OsuPerformanceAttributes> {
@@ -121,6 +149,60 @@ impl OsuGradualPerformance {
         Some(performance)
     }
 
+    /// Calculate the performance attributes after the hit object at index
+    /// `idx` for the given score state, regardless of how many hit objects
+    /// were processed so far.
+    ///
+    /// See [`OsuGradualDifficulty::seek`] for more information.
+    pub fn seek(&mut self, state: OsuScoreState, idx: usize) -> Option<OsuPerformanceAttributes> {
+        let performance = self
+            .difficulty
+            .seek(idx)?
+            .performance()
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .calculate();
+
+        Some(performance)
+    }
+
+    /// Process all objects whose time is at or before the timestamp `time`
+    /// in milliseconds and calculate the performance attributes for the
+    /// given score state.
+    ///
+    /// See [`OsuGradualDifficulty::advance_to_time`] for more information.
+    pub fn advance_to_time(
+        &mut self,
+        state: OsuScoreState,
+        time: f64,
+    ) -> Option<OsuPerformanceAttributes> {
+        let performance = self
+            .difficulty
+            .advance_to_time(time)?
+            .performance()
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .calculate();
+
+        Some(performance)
+    }
+
+    /// Capture the progress of the calculation in a compact
+    /// [`GradualSnapshot`].
+    pub fn snapshot(&self) -> GradualSnapshot {
+        self.difficulty.snapshot()
+    }
+
+    /// Resume a calculation from a [`GradualSnapshot`].
+    ///
+    /// See [`OsuGradualDifficulty::from_snapshot`] for more information.
+    pub fn from_snapshot(snapshot: &GradualSnapshot, converted: &OsuBeatmap<'_>) -> Option<Self> {
+        OsuGradualDifficulty::from_snapshot(snapshot, converted)
+            .map(|difficulty| Self { difficulty })
+    }
+
     /// Returns the amount of remaining objects.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
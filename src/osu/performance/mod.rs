@@ -1,14 +1,19 @@
 use std::cmp;
 
 use rosu_map::section::general::GameMode;
-use statrs::distribution::{Beta, Normal, ContinuousCDF};
+use statrs::distribution::{Beta, ContinuousCDF, Normal};
 
 use crate::{
     any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance, Performance},
     catch::CatchPerformance,
     mania::ManiaPerformance,
     taiko::TaikoPerformance,
-    util::{float_ext::FloatExt, map_or_attrs::MapOrAttrs, mods::Mods},
+    util::{
+        float_ext::FloatExt,
+        map_or_attrs::MapOrAttrs,
+        mods::Mods,
+        solver::{self, Solvable},
+    },
 };
 
 use super::{
@@ -17,6 +22,7 @@ use super::{
     Osu,
 };
 
+pub mod batch;
 pub mod gradual;
 
 /// Performance calculator on osu!standard maps.
@@ -31,6 +37,8 @@ pub struct OsuPerformance<'map> {
     pub(crate) n100: Option<u32>,
     pub(crate) n50: Option<u32>,
     pub(crate) misses: Option<u32>,
+    pub(crate) spinner_bonus: Option<u32>,
+    pub(crate) spun_out: Option<u32>,
     pub(crate) hitresult_priority: HitResultPriority,
 }
 
@@ -172,6 +180,23 @@ impl<'map> OsuPerformance<'map> {
         self
     }
 
+    /// Specify the amount of bonus spins on spinners of a play.
+    pub const fn spinner_bonus(mut self, spinner_bonus: u32) -> Self {
+        self.spinner_bonus = Some(spinner_bonus);
+
+        self
+    }
+
+    /// Specify the amount of spinners that were completed automatically.
+    ///
+    /// If none is specified, all spinners are considered spun out if the
+    /// SO mod is enabled and none otherwise.
+    pub const fn spun_out(mut self, spun_out: u32) -> Self {
+        self.spun_out = Some(spun_out);
+
+        self
+    }
+
     /// Use the specified settings of the given [`Difficulty`].
     pub const fn difficulty(mut self, difficulty: Difficulty) -> Self {
         self.difficulty = difficulty;
@@ -275,6 +300,8 @@ impl<'map> OsuPerformance<'map> {
             n100,
             n50,
             misses,
+            spinner_bonus,
+            spun_out,
         } = state;
 
         self.combo = Some(max_combo);
@@ -282,6 +309,11 @@ impl<'map> OsuPerformance<'map> {
         self.n100 = Some(n100);
         self.n50 = Some(n50);
         self.misses = Some(misses);
+        self.spinner_bonus = Some(spinner_bonus);
+
+        // States without spun out spinners, e.g. those converted from a
+        // `ScoreState`, keep the default so that SO still applies
+        self.spun_out = if spun_out > 0 { Some(spun_out) } else { None };
 
         self
     }
@@ -469,17 +501,33 @@ impl<'map> OsuPerformance<'map> {
             cmp::min(combo, max_possible_combo)
         });
 
+        let spinner_bonus = self.spinner_bonus.unwrap_or(0);
+
+        let n_spinners = attrs.n_spinners;
+        let spun_out = match self.spun_out {
+            Some(spun_out) => cmp::min(spun_out, n_spinners),
+            None if self.difficulty.get_mods().so() => n_spinners,
+            None => 0,
+        };
+
         OsuScoreState {
             max_combo,
             n300,
             n100,
             n50,
             misses,
+            spinner_bonus,
+            spun_out,
         }
     }
 
     /// Calculate all performance related values, including pp and stars.
-    pub fn calculate(mut self) -> OsuPerformanceAttributes {
+    pub fn calculate(self) -> OsuPerformanceAttributes {
+        self.inner().calculate()
+    }
+
+    /// Generate the score state and pair it with the difficulty attributes.
+    fn inner(mut self) -> OsuPerformanceInner {
         let state = self.generate_state();
 
         let attrs = match self.map_or_attrs {
@@ -489,15 +537,35 @@ impl<'map> OsuPerformance<'map> {
 
         let effective_miss_count = calculate_effective_misses(&attrs, &state);
 
-        let inner = OsuPerformanceInner {
+        OsuPerformanceInner {
             attrs,
             mods: self.difficulty.get_mods(),
             acc: state.accuracy(),
             state,
             effective_miss_count,
-        };
+        }
+    }
 
-        inner.calculate()
+    /// Find the minimal accuracy, in percent, for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Since explicitly specified hitresults take precedence over the
+    /// accuracy, they should generally be left unspecified.
+    ///
+    /// Hitresults are generated according to the specified
+    /// [`HitResultPriority`] for each probed accuracy.
+    ///
+    /// Returns `None` if `target_pp` is not reached even with 100% accuracy.
+    pub fn accuracy_for_pp(self, target_pp: f64) -> Option<f64> {
+        solver::accuracy_for_pp(self, target_pp)
+    }
+
+    /// Find the maximal amount of misses for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Returns `None` if `target_pp` is not reached even without misses.
+    pub fn misses_for_pp(self, target_pp: f64) -> Option<u32> {
+        solver::misses_for_pp(self, target_pp)
     }
 
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Osu>) -> Self {
@@ -510,6 +578,8 @@ impl<'map> OsuPerformance<'map> {
             n100: None,
             n50: None,
             misses: None,
+            spinner_bonus: None,
+            spun_out: None,
             hitresult_priority: HitResultPriority::DEFAULT,
         }
     }
@@ -523,6 +593,39 @@ impl<'map, T: IntoModePerformance<'map, Osu>> From<T> for OsuPerformance<'map> {
 
 pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.12;
 
+impl Solvable for OsuPerformance<'_> {
+    type Invariants = OsuPerformanceInvariants;
+
+    fn invariants(&mut self) -> Self::Invariants {
+        let attrs = match self.map_or_attrs {
+            MapOrAttrs::Map(ref map) => {
+                let attrs = self.difficulty.with_mode().calculate(map);
+
+                self.map_or_attrs.insert_attrs(attrs)
+            }
+            MapOrAttrs::Attrs(ref attrs) => attrs,
+        };
+
+        OsuPerformanceInvariants::new(attrs, self.difficulty.get_mods())
+    }
+
+    fn with_accuracy(self, acc: f64) -> Self {
+        self.accuracy(acc)
+    }
+
+    fn with_misses(self, misses: u32) -> Self {
+        self.misses(misses)
+    }
+
+    fn max_misses(&self) -> u32 {
+        self.clone().misses(u32::MAX).generate_state().misses
+    }
+
+    fn pp(self, invariants: &Self::Invariants) -> f64 {
+        self.inner().calculate_with(invariants).pp
+    }
+}
+
 struct OsuPerformanceInner {
     attrs: OsuDifficultyAttributes,
     mods: u32,
@@ -533,28 +636,39 @@ struct OsuPerformanceInner {
 
 impl OsuPerformanceInner {
     fn calculate(self) -> OsuPerformanceAttributes {
-        let total_hits = self.total_hits();
-
-        let normalised_hit_error = self.compute_normalised_hit_error(total_hits);
-        let miss_weight = self.compute_miss_weight();
-        let aim_weight = self.compute_aim_weight(miss_weight, normalised_hit_error, total_hits);
-        let speed_weight = self.compute_speed_weight(miss_weight, normalised_hit_error);
-        let acc_weight = self.compute_accuracy_weight();
+        let invariants = OsuPerformanceInvariants::new(&self.attrs, self.mods);
 
-        let aim_value = Self::compute_skill_value(self.attrs.aim) * aim_weight;
-        let jump_aim_value = Self::compute_skill_value(self.attrs.jump) * aim_weight;
-        let flow_aim_value = Self::compute_skill_value(self.attrs.flow) * aim_weight;
-        let precision_aim_value = Self::compute_skill_value(self.attrs.precision) * aim_weight;
+        self.calculate_with(&invariants)
+    }
 
-        let speed_value = Self::compute_skill_value(self.attrs.speed) * speed_weight;
-        let stamina_value = Self::compute_skill_value(self.attrs.stamina) * speed_weight;
-        let acc_value = Self::compute_accuracy_value(normalised_hit_error) * self.attrs.accuracy * acc_weight;
+    fn calculate_with(self, invariants: &OsuPerformanceInvariants) -> OsuPerformanceAttributes {
+        let total_hits = self.total_hits();
 
-        let pp = (
-            aim_value.powf(1.1)
-            + speed_value.max(stamina_value).powf(1.1)
-            + acc_value.powf(1.1)
-        ).powf(1.0 / 1.1) * PERFORMANCE_BASE_MULTIPLIER;
+        let normalised_hit_error =
+            self.compute_normalised_hit_error(total_hits, &invariants.normal);
+        let miss_weight = self.compute_miss_weight();
+        let spun_out_weight = self.compute_spun_out_weight(total_hits);
+        let aim_weight =
+            self.compute_aim_weight(invariants, miss_weight, normalised_hit_error, total_hits)
+                * spun_out_weight;
+        let speed_weight = self.compute_speed_weight(invariants, miss_weight, normalised_hit_error)
+            * spun_out_weight;
+        let acc_weight = invariants.acc_weight * spun_out_weight;
+
+        let aim_value = invariants.aim_value * aim_weight;
+        let jump_aim_value = invariants.jump_aim_value * aim_weight;
+        let flow_aim_value = invariants.flow_aim_value * aim_weight;
+        let precision_aim_value = invariants.precision_aim_value * aim_weight;
+
+        let speed_value = invariants.speed_value * speed_weight;
+        let stamina_value = invariants.stamina_value * speed_weight;
+        let acc_value =
+            Self::compute_accuracy_value(normalised_hit_error) * self.attrs.accuracy * acc_weight;
+
+        let pp =
+            (aim_value.powf(1.1) + speed_value.max(stamina_value).powf(1.1) + acc_value.powf(1.1))
+                .powf(1.0 / 1.1)
+                * PERFORMANCE_BASE_MULTIPLIER;
 
         OsuPerformanceAttributes {
             difficulty: self.attrs,
@@ -569,20 +683,28 @@ impl OsuPerformanceInner {
         }
     }
 
-    fn compute_skill_value(skill_diff: f64) -> f64 {
-        skill_diff.powf(3.0) * 3.9
-    }
-
     fn compute_accuracy_value(normalised_hit_error: f64) -> f64 {
-        if normalised_hit_error.is_nan() { 0.0 } else { 560.0 * 0.85_f64.powf(normalised_hit_error) }
+        if normalised_hit_error.is_nan() {
+            0.0
+        } else {
+            560.0 * 0.85_f64.powf(normalised_hit_error)
+        }
     }
 
-    fn compute_normalised_hit_error(&self, total_hits: f64) -> f64 {
-        let circle_300_count = f64::from(self.state.n300) - (total_hits - f64::from(self.attrs.n_circles));
-        if circle_300_count <= 0.0 { return f64::NAN };
+    fn compute_normalised_hit_error(&self, total_hits: f64, normal: &Normal) -> f64 {
+        let circle_300_count =
+            f64::from(self.state.n300) - (total_hits - f64::from(self.attrs.n_circles));
+        if circle_300_count <= 0.0 {
+            return f64::NAN;
+        };
 
-        let probability = Beta::new(circle_300_count, 1.0 + f64::from(self.attrs.n_circles) - circle_300_count).unwrap().inverse_cdf(0.2);
-        let z_value = Normal::new(0.0, 1.0).unwrap().inverse_cdf(probability + (1.0 - probability) / 2.0);
+        let probability = Beta::new(
+            circle_300_count,
+            1.0 + f64::from(self.attrs.n_circles) - circle_300_count,
+        )
+        .unwrap()
+        .inverse_cdf(0.2);
+        let z_value = normal.inverse_cdf(probability + (1.0 - probability) / 2.0);
 
         let hit_window = 79.5 - self.attrs.od * 6.0;
         hit_window / z_value
@@ -592,33 +714,126 @@ impl OsuPerformanceInner {
         0.97_f64.powf(f64::from(self.state.misses))
     }
 
-    fn compute_aim_weight(&self, miss_weight: f64, normalised_hit_error: f64, total_hits: f64) -> f64 {
-        let accuracy_weight = if normalised_hit_error.is_nan() { 0.0 } else { 0.995_f64.powf(normalised_hit_error) * 1.04 };
-        let combo_weight = f64::from(self.state.max_combo).powf(0.8) / f64::from(self.attrs.max_combo).powf(0.8);
-        let fl_length_weight = if self.mods.fl() { 1.0 + (total_hits / 2000.0).atan() } else { 1.0 };
+    fn compute_aim_weight(
+        &self,
+        invariants: &OsuPerformanceInvariants,
+        miss_weight: f64,
+        normalised_hit_error: f64,
+        total_hits: f64,
+    ) -> f64 {
+        let accuracy_weight = if normalised_hit_error.is_nan() {
+            0.0
+        } else {
+            0.995_f64.powf(normalised_hit_error) * 1.04
+        };
+        let combo_weight = f64::from(self.state.max_combo).powf(0.8) / invariants.aim_combo_base;
+        let fl_length_weight = invariants.fl_length_weight(total_hits);
 
         accuracy_weight * combo_weight * miss_weight * fl_length_weight
     }
 
-    fn compute_speed_weight(&self, miss_weight: f64, normalised_hit_error: f64) -> f64 {
-        let accuracy_weight = if normalised_hit_error.is_nan() { 0.0 } else { 0.985_f64.powf(normalised_hit_error) * 1.12 };
-        let combo_weight = f64::from(self.state.max_combo).powf(0.4) / f64::from(self.attrs.max_combo).powf(0.4);
+    fn compute_speed_weight(
+        &self,
+        invariants: &OsuPerformanceInvariants,
+        miss_weight: f64,
+        normalised_hit_error: f64,
+    ) -> f64 {
+        let accuracy_weight = if normalised_hit_error.is_nan() {
+            0.0
+        } else {
+            0.985_f64.powf(normalised_hit_error) * 1.12
+        };
+        let combo_weight = f64::from(self.state.max_combo).powf(0.4) / invariants.speed_combo_base;
 
         accuracy_weight * combo_weight * miss_weight
     }
 
-    fn compute_accuracy_weight(&self) -> f64 {
-        let length_weight = (f64::from(self.attrs.n_circles + 400) / 1050.0).tanh() * 1.2;
+    fn compute_spun_out_weight(&self, total_hits: f64) -> f64 {
+        if self.state.spun_out == 0 || total_hits <= 0.0 {
+            return 1.0;
+        }
+
+        1.0 - (f64::from(self.state.spun_out) / total_hits).powf(0.85)
+    }
+
+    const fn total_hits(&self) -> f64 {
+        self.state.total_hits() as f64
+    }
+}
+
+/// The parts of [`OsuPerformanceInner`] that only depend on the difficulty
+/// attributes and mods so they can be shared between scores.
+pub(crate) struct OsuPerformanceInvariants {
+    aim_value: f64,
+    jump_aim_value: f64,
+    flow_aim_value: f64,
+    precision_aim_value: f64,
+    speed_value: f64,
+    stamina_value: f64,
+    acc_weight: f64,
+    aim_combo_base: f64,
+    speed_combo_base: f64,
+    fl: bool,
+    /// The flashlight length weight for scores that hit all objects.
+    full_fl_length_weight: f64,
+    n_objects: f64,
+    normal: Normal,
+}
+
+impl OsuPerformanceInvariants {
+    fn new(attrs: &OsuDifficultyAttributes, mods: u32) -> Self {
+        let fl = mods.fl();
+        let n_objects = f64::from(attrs.n_objects());
+
+        Self {
+            aim_value: Self::compute_skill_value(attrs.aim),
+            jump_aim_value: Self::compute_skill_value(attrs.jump),
+            flow_aim_value: Self::compute_skill_value(attrs.flow),
+            precision_aim_value: Self::compute_skill_value(attrs.precision),
+            speed_value: Self::compute_skill_value(attrs.speed),
+            stamina_value: Self::compute_skill_value(attrs.stamina),
+            acc_weight: Self::compute_accuracy_weight(attrs, mods),
+            aim_combo_base: f64::from(attrs.max_combo).powf(0.8),
+            speed_combo_base: f64::from(attrs.max_combo).powf(0.4),
+            fl,
+            full_fl_length_weight: Self::compute_fl_length_weight(fl, n_objects),
+            n_objects,
+            normal: Normal::new(0.0, 1.0).unwrap(),
+        }
+    }
+
+    fn compute_skill_value(skill_diff: f64) -> f64 {
+        skill_diff.powf(3.0) * 3.9
+    }
+
+    fn compute_accuracy_weight(attrs: &OsuDifficultyAttributes, mods: u32) -> f64 {
+        let length_weight = (f64::from(attrs.n_circles + 400) / 1050.0).tanh() * 1.2;
 
         let mut mod_weight = 1.0;
-        if self.mods.hd() { mod_weight *= 1.02 };
-        if self.mods.fl() { mod_weight *= 1.04 };
+        if mods.hd() {
+            mod_weight *= 1.02;
+        }
+        if mods.fl() {
+            mod_weight *= 1.04;
+        }
 
         length_weight * mod_weight
     }
 
-    const fn total_hits(&self) -> f64 {
-        self.state.total_hits() as f64
+    fn compute_fl_length_weight(fl: bool, total_hits: f64) -> f64 {
+        if fl {
+            1.0 + (total_hits / 2000.0).atan()
+        } else {
+            1.0
+        }
+    }
+
+    fn fl_length_weight(&self, total_hits: f64) -> f64 {
+        if total_hits.eq(self.n_objects) {
+            self.full_fl_length_weight
+        } else {
+            Self::compute_fl_length_weight(self.fl, total_hits)
+        }
     }
 }
 
@@ -659,7 +874,7 @@ mod test {
     use proptest::prelude::*;
 
     use crate::{
-        any::{DifficultyAttributes, PerformanceAttributes},
+        any::{DifficultyAttributes, Performance, PerformanceAttributes, ScoreState},
         taiko::{Taiko, TaikoDifficultyAttributes, TaikoPerformanceAttributes},
         Beatmap,
     };
@@ -857,6 +1072,8 @@ mod test {
             n100: 20,
             n50: 279,
             misses: 2,
+            spinner_bonus: 0,
+            spun_out: 0,
         };
 
         assert_eq!(state, expected);
@@ -878,6 +1095,8 @@ mod test {
             n100: 289,
             n50: 10,
             misses: 2,
+            spinner_bonus: 0,
+            spun_out: 0,
         };
 
         assert_eq!(state, expected);
@@ -898,6 +1117,8 @@ mod test {
             n100: 589,
             n50: 10,
             misses: 2,
+            spinner_bonus: 0,
+            spun_out: 0,
         };
 
         assert_eq!(state, expected);
@@ -920,11 +1141,73 @@ mod test {
             n100: 50,
             n50: 249,
             misses: 2,
+            spinner_bonus: 0,
+            spun_out: 0,
         };
 
         assert_eq!(state, expected);
     }
 
+    #[test]
+    fn spun_out_spinners() {
+        let state = OsuPerformance::from(attrs()).mods(1 << 12).generate_state();
+        assert_eq!(state.spun_out, 1);
+
+        let state = OsuPerformance::from(attrs()).generate_state();
+        assert_eq!(state.spun_out, 0);
+
+        let state = OsuPerformance::from(attrs()).spun_out(5).generate_state();
+        assert_eq!(state.spun_out, 1);
+
+        let regular = OsuPerformance::from(attrs()).calculate();
+        let spun_out = OsuPerformance::from(attrs()).spun_out(1).calculate();
+        assert!(spun_out.pp < regular.pp);
+
+        // The penalty applies to each skill as well
+        assert!(spun_out.pp_aim < regular.pp_aim);
+        assert!(spun_out.pp_speed < regular.pp_speed);
+        assert!(spun_out.pp_stamina < regular.pp_stamina);
+        assert!(spun_out.pp_accuracy < regular.pp_accuracy);
+    }
+
+    #[test]
+    fn spun_out_through_state() {
+        let state = OsuPerformance::from(attrs()).generate_state();
+        let expected = OsuPerformance::from(attrs())
+            .mods(1 << 12)
+            .state(OsuScoreState {
+                spun_out: 1,
+                ..state
+            })
+            .calculate()
+            .pp;
+
+        let regular = Performance::new(attrs())
+            .state(ScoreState::from(state))
+            .calculate()
+            .pp();
+
+        let spun_out = Performance::new(attrs())
+            .mods(1 << 12)
+            .state(ScoreState::from(state))
+            .calculate()
+            .pp();
+
+        assert!(spun_out < regular);
+        assert_eq!(spun_out, expected);
+    }
+
+    #[test]
+    fn spinner_sps() {
+        let converted = beatmap().unchecked_into_converted::<Osu>();
+
+        let nomod = Difficulty::new().with_mode().calculate(&converted);
+        let dt = Difficulty::new().mods(64).with_mode().calculate(&converted);
+
+        assert!(nomod.spinner_sps > 0.0);
+        assert!(dt.spinner_sps > nomod.spinner_sps);
+    }
+
     #[test]
     fn create() {
         let mut map = beatmap();
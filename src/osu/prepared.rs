@@ -0,0 +1,256 @@
+use std::borrow::Cow;
+
+use crate::{any::Difficulty, util::mods::Mods, Beatmap};
+
+use super::{
+    convert::{apply_stack_offsets, count_objects, create_objects, stack_objects, OsuBeatmap},
+    difficulty::{
+        difficulty_from_values, object::OsuDifficultyNoBase, scaling_factor::ScalingFactor,
+        DifficultyValues, OsuDifficultySetup,
+    },
+    object::OsuObject,
+    OsuDifficultyAttributes,
+};
+
+/// An osu!standard map whose conversion work is shared across difficulty
+/// calculations with different mods.
+///
+/// Converting a map's hit objects, including the generation of sliders'
+/// nested objects, is done only once. On top of that, intermediate results
+/// are cached so that only work that actually differs between mods is redone:
+/// - HR reflects objects vertically which requires restacking
+/// - AR determines the stacking threshold which requires restacking
+/// - CS determines the stack offsets and slider cursor positions
+/// - the clock rate, i.e. DT and HT, determines the difficulty objects
+///
+/// Mods that change none of these, e.g. HD, only require the skills to be
+/// processed again. Each cached result holds a copy of all objects so memory
+/// usage grows with the amount of different mod combinations.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty};
+/// use rosu_pp::osu::{Osu, OsuPreparedMap};
+///
+/// let converted = Beatmap::from_path("./resources/2785319.osu")
+///     .unwrap()
+///     .unchecked_into_converted::<Osu>();
+///
+/// let mut prepared = OsuPreparedMap::new(&converted);
+///
+/// for mods in [0, 8, 16, 64, 8 + 16, 8 + 64, 16 + 64] {
+///     let attrs = prepared.calculate(&Difficulty::new().mods(mods));
+///     println!("Stars with mods {mods}: {}", attrs.stars);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct OsuPreparedMap<'map> {
+    converted: OsuBeatmap<'map>,
+    objects: Box<[OsuObject]>,
+    stacked: Vec<StackedObjects>,
+}
+
+/// Objects after stacking for a combination of HR and AR.
+#[derive(Clone, Debug)]
+struct StackedObjects {
+    hr: bool,
+    time_preempt: f64,
+    objects: Box<[OsuObject]>,
+    positioned: Vec<PositionedObjects>,
+}
+
+/// Stacked objects including stack offsets and slider cursor positions for a
+/// CS value.
+#[derive(Clone, Debug)]
+struct PositionedObjects {
+    radius: f64,
+    objects: Box<[OsuObject]>,
+    diff_objects: Vec<DifficultyObjects>,
+}
+
+/// Difficulty objects of [`PositionedObjects`] for a clock rate.
+#[derive(Clone, Debug)]
+struct DifficultyObjects {
+    clock_rate: f64,
+    values: Box<[OsuDifficultyNoBase]>,
+}
+
+impl<'map> OsuPreparedMap<'map> {
+    /// Prepare the given map by converting its hit objects.
+    pub fn new(converted: &'map OsuBeatmap<'_>) -> Self {
+        Self::from_map(converted)
+    }
+
+    /// Prepare the given map, assuming that its mode is osu!standard.
+    pub(crate) fn from_map(map: &'map Beatmap) -> Self {
+        let converted = OsuBeatmap::new(Cow::Borrowed(map));
+        let objects = create_objects(&converted);
+
+        Self {
+            converted,
+            objects,
+            stacked: Vec::new(),
+        }
+    }
+
+    /// Calculate the difficulty attributes for the given [`Difficulty`].
+    ///
+    /// The results are the same as [`Difficulty::calculate`].
+    pub fn calculate(&mut self, difficulty: &Difficulty) -> OsuDifficultyAttributes {
+        let mut setup = OsuDifficultySetup::new(difficulty, &self.converted);
+        let hr = difficulty.get_mods().hr();
+        let clock_rate = difficulty.get_clock_rate();
+
+        let stacked_idx = self.stacked_idx(hr, setup.time_preempt);
+        let stacked = &mut self.stacked[stacked_idx];
+        let positioned_idx = stacked.positioned_idx(&setup.scaling_factor);
+        let positioned = &mut stacked.positioned[positioned_idx];
+        let diff_idx = positioned.diff_objects_idx(clock_rate, &setup);
+
+        let PositionedObjects {
+            ref objects,
+            ref diff_objects,
+            ..
+        } = *positioned;
+
+        count_objects(objects, difficulty.get_passed_objects(), &mut setup.attrs);
+
+        let diff_objects: Vec<_> = diff_objects[diff_idx]
+            .values
+            .iter()
+            .map(|values| values.with_base(&objects[values.idx + 1]))
+            .collect();
+
+        let values = DifficultyValues::calculate_with_diff_objects(
+            difficulty,
+            setup,
+            objects,
+            &diff_objects,
+        );
+
+        difficulty_from_values(values)
+    }
+
+    /// The amount of cached stacking results.
+    pub const fn n_stacked(&self) -> usize {
+        self.stacked.len()
+    }
+
+    /// The amount of cached difficulty objects across all stacking results
+    /// and CS values.
+    pub fn n_difficulty_objects(&self) -> usize {
+        self.stacked
+            .iter()
+            .flat_map(|stacked| stacked.positioned.iter())
+            .map(|positioned| positioned.diff_objects.len())
+            .sum()
+    }
+
+    fn stacked_idx(&mut self, hr: bool, time_preempt: f64) -> usize {
+        let cached = self.stacked.iter().position(|stacked| {
+            stacked.hr == hr && stacked.time_preempt.to_bits() == time_preempt.to_bits()
+        });
+
+        cached.unwrap_or_else(|| {
+            let mut objects = self.objects.clone();
+            stack_objects(&self.converted, &mut objects, hr, time_preempt);
+
+            self.stacked.push(StackedObjects {
+                hr,
+                time_preempt,
+                objects,
+                positioned: Vec::new(),
+            });
+
+            self.stacked.len() - 1
+        })
+    }
+}
+
+impl StackedObjects {
+    fn positioned_idx(&mut self, scaling_factor: &ScalingFactor) -> usize {
+        let cached = self
+            .positioned
+            .iter()
+            .position(|positioned| positioned.radius.to_bits() == scaling_factor.radius.to_bits());
+
+        cached.unwrap_or_else(|| {
+            let mut objects = self.objects.clone();
+            apply_stack_offsets(&mut objects, scaling_factor);
+            DifficultyValues::compute_slider_cursor_positions(&mut objects, scaling_factor);
+
+            self.positioned.push(PositionedObjects {
+                radius: scaling_factor.radius,
+                objects,
+                diff_objects: Vec::new(),
+            });
+
+            self.positioned.len() - 1
+        })
+    }
+}
+
+impl PositionedObjects {
+    fn diff_objects_idx(&mut self, clock_rate: f64, setup: &OsuDifficultySetup) -> usize {
+        let cached = self
+            .diff_objects
+            .iter()
+            .position(|diff_objects| diff_objects.clock_rate.to_bits() == clock_rate.to_bits());
+
+        cached.unwrap_or_else(|| {
+            let values = DifficultyValues::create_difficulty_objects_from_positioned(
+                clock_rate,
+                &setup.scaling_factor,
+                self.objects.iter(),
+                setup.time_preempt,
+            )
+            .into_iter()
+            .map(OsuDifficultyNoBase::from)
+            .collect();
+
+            self.diff_objects
+                .push(DifficultyObjects { clock_rate, values });
+
+            self.diff_objects.len() - 1
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{osu::Osu, Beatmap};
+
+    use super::*;
+
+    #[test]
+    fn matches_difficulty() {
+        let converted = Beatmap::from_path("./resources/2785319.osu")
+            .unwrap()
+            .unchecked_into_converted::<Osu>();
+
+        let mut prepared = OsuPreparedMap::new(&converted);
+
+        let difficulties = [
+            Difficulty::new(),
+            Difficulty::new().mods(64),
+            Difficulty::new().mods(256),
+            Difficulty::new().mods(16),
+            Difficulty::new().mods(16 + 64),
+            Difficulty::new().mods(2 + 8),
+            Difficulty::new().mods(8 + 1024).passed_objects(200),
+            Difficulty::new().clock_rate(1.25).cs(6.5, false),
+            Difficulty::new().ar(10.0, false),
+        ];
+
+        for difficulty in difficulties {
+            let expected = difficulty.with_mode().calculate(&converted);
+            assert_eq!(prepared.calculate(&difficulty), expected, "{difficulty:?}");
+        }
+
+        // Clock rate and CS don't affect stacking; only HR, EZ, and AR do
+        assert_eq!(prepared.n_stacked(), 4);
+        // HD and FL reuse the difficulty objects of the same clock rate
+        assert_eq!(prepared.n_difficulty_objects(), 8);
+    }
+}
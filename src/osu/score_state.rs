@@ -12,6 +12,19 @@ pub struct OsuScoreState {
     pub n50: u32,
     /// Amount of current misses.
     pub misses: u32,
+    /// Amount of bonus spins on spinners.
+    ///
+    /// Only informational; bonus spins do not affect performance.
+    pub spinner_bonus: u32,
+    /// Amount of spinners that were completed automatically instead of being
+    /// spun by the player, e.g. through the SO mod.
+    ///
+    /// When passing a state with zero spun out spinners to
+    /// [`OsuPerformance::state`], all spinners are still considered spun out
+    /// if the SO mod is enabled.
+    ///
+    /// [`OsuPerformance::state`]: crate::osu::OsuPerformance::state
+    pub spun_out: u32,
 }
 
 impl OsuScoreState {
@@ -23,6 +36,8 @@ impl OsuScoreState {
             n100: 0,
             n50: 0,
             misses: 0,
+            spinner_bonus: 0,
+            spun_out: 0,
         }
     }
 
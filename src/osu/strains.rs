@@ -1,8 +1,14 @@
-use crate::Difficulty;
+use crate::{
+    any::{HardestSection, StrainTimeline},
+    Difficulty,
+};
 
 use super::{
     convert::OsuBeatmap,
-    difficulty::{skills::OsuSkills, DifficultyValues},
+    difficulty::{
+        skills::{strain::OsuStrainSkill, OsuSkills},
+        DifficultyValues, DIFFICULTY_MULTIPLIER,
+    },
 };
 
 /// The result of calculating the strains on a osu! map.
@@ -22,6 +28,30 @@ pub struct OsuStrains {
 impl OsuStrains {
     /// Time between two strains in ms.
     pub const SECTION_LEN: f64 = 400.0;
+
+    /// Turn the strain peaks into a smoothed difficulty curve.
+    ///
+    /// See [`StrainTimeline`] for more information.
+    #[must_use]
+    pub fn timeline(&self, timeline: &StrainTimeline) -> Self {
+        let smooth = |peaks: &[f64]| {
+            timeline.smooth(
+                peaks,
+                Self::SECTION_LEN,
+                OsuStrainSkill::DECAY_WEIGHT,
+                |value| value.sqrt() * DIFFICULTY_MULTIPLIER,
+            )
+        };
+
+        Self {
+            aim: smooth(&self.aim),
+            jump: smooth(&self.jump),
+            flow: smooth(&self.flow),
+            raw: smooth(&self.raw),
+            speed: smooth(&self.speed),
+            stamina: smooth(&self.stamina),
+        }
+    }
 }
 
 pub fn strains(difficulty: &Difficulty, converted: &OsuBeatmap<'_>) -> OsuStrains {
@@ -48,3 +78,42 @@ pub fn strains(difficulty: &Difficulty, converted: &OsuBeatmap<'_>) -> OsuStrain
         stamina: stamina.get_curr_strain_peaks().into_vec(),
     }
 }
+
+pub fn hardest_section(
+    difficulty: &Difficulty,
+    converted: &OsuBeatmap<'_>,
+    duration: f64,
+) -> Option<HardestSection> {
+    let DifficultyValues {
+        skills:
+            OsuSkills {
+                aim,
+                speed,
+                stamina,
+                ..
+            },
+        attrs: _,
+    } = DifficultyValues::calculate(difficulty, converted);
+
+    let section_end = aim.section_end();
+
+    // Combine peaks the same way the star rating combines aim with the
+    // harder of speed and stamina.
+    let peaks: Vec<_> = aim
+        .get_curr_strain_peaks()
+        .iter()
+        .zip(speed.get_curr_strain_peaks().iter())
+        .zip(stamina.get_curr_strain_peaks().iter())
+        .map(|((aim, speed), stamina)| {
+            (aim.powf(1.5) + speed.max(stamina).powf(1.5)).powf(2.0 / 3.0)
+        })
+        .collect();
+
+    HardestSection::new(
+        &peaks,
+        OsuStrains::SECTION_LEN,
+        section_end,
+        difficulty.get_clock_rate(),
+        duration,
+    )
+}
@@ -0,0 +1,304 @@
+use std::cmp::Ordering;
+
+use rosu_map::section::general::GameMode;
+
+use crate::any::PerformanceAttributes;
+
+/// Amount of scores that are considered for weighted pp.
+pub const MAX_WEIGHTED_SCORES: usize = 100;
+
+/// Factor by which the weight decreases for each consecutive score.
+const WEIGHT_DECAY: f64 = 0.95;
+
+/// Aggregate the performance attributes of a player's scores of one mode into
+/// profile totals.
+///
+/// Just like profiles on osu!, a [`Profile`] only considers scores of its
+/// mode; scores of other modes are ignored.
+///
+/// Scores are weighted by `0.95^i` where `i` is the zero-based position of the
+/// score after sorting by pp in descending order, considering only the top
+/// [`MAX_WEIGHTED_SCORES`] scores. On top of that, bonus pp are awarded based
+/// on the amount of ranked scores.
+///
+/// For osu!standard scores, the PP+ skill values are aggregated the same way.
+/// Each skill is sorted on its own so the top scores of one skill are not
+/// necessarily the top scores of another.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Performance};
+/// use rosu_pp::model::mode::GameMode;
+/// use rosu_pp::profile::Profile;
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let scores: Vec<_> = [0, 8, 64, 16 + 64]
+///     .into_iter()
+///     .map(|mods| Performance::new(&map).mods(mods).calculate())
+///     .collect();
+///
+/// let profile = Profile::new(GameMode::Osu)
+///     .scores(&scores)
+///     .ranked_scores(1500)
+///     .calculate();
+///
+/// println!("Total PP: {}", profile.pp);
+/// println!("Aim PP: {}", profile.skills.aim);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct Profile {
+    mode: GameMode,
+    scores: Vec<ProfileScore>,
+    ranked_scores: Option<usize>,
+}
+
+impl Profile {
+    /// Create a new empty profile for the given mode.
+    pub const fn new(mode: GameMode) -> Self {
+        Self {
+            mode,
+            scores: Vec::new(),
+            ranked_scores: None,
+        }
+    }
+
+    /// Add a score's performance attributes.
+    ///
+    /// The score is ignored if its mode differs from the profile's.
+    pub fn score(mut self, attrs: &PerformanceAttributes) -> Self {
+        self.scores.extend(ProfileScore::new(attrs, self.mode));
+
+        self
+    }
+
+    /// Add the performance attributes of multiple scores.
+    ///
+    /// Scores whose mode differs from the profile's are ignored.
+    pub fn scores<'a>(
+        mut self,
+        attrs: impl IntoIterator<Item = &'a PerformanceAttributes>,
+    ) -> Self {
+        let mode = self.mode;
+        let scores = attrs
+            .into_iter()
+            .filter_map(|attrs| ProfileScore::new(attrs, mode));
+
+        self.scores.extend(scores);
+
+        self
+    }
+
+    /// Specify the amount of ranked scores of the player which determines the
+    /// bonus pp.
+    ///
+    /// If not specified, the amount of considered scores will be used.
+    pub const fn ranked_scores(mut self, ranked_scores: usize) -> Self {
+        self.ranked_scores = Some(ranked_scores);
+
+        self
+    }
+
+    /// Calculate the profile totals.
+    pub fn calculate(&self) -> ProfileAttributes {
+        let weighted_pp = weighted_sum(self.scores.iter().map(|score| score.pp));
+        let bonus_pp = bonus_pp(self.ranked_scores.unwrap_or(self.scores.len()));
+
+        let skill = |f: fn(&ProfileSkills) -> f64| {
+            weighted_sum(
+                self.scores
+                    .iter()
+                    .filter_map(|score| score.skills.as_ref().map(f)),
+            )
+        };
+
+        let skills = ProfileSkills {
+            aim: skill(|skills| skills.aim),
+            jump_aim: skill(|skills| skills.jump_aim),
+            flow_aim: skill(|skills| skills.flow_aim),
+            precision: skill(|skills| skills.precision),
+            speed: skill(|skills| skills.speed),
+            stamina: skill(|skills| skills.stamina),
+            accuracy: skill(|skills| skills.accuracy),
+        };
+
+        ProfileAttributes {
+            pp: weighted_pp + bonus_pp,
+            weighted_pp,
+            bonus_pp,
+            skills,
+        }
+    }
+}
+
+/// The result of aggregating scores through [`Profile`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProfileAttributes {
+    /// The total pp, i.e. weighted pp plus bonus pp.
+    pub pp: f64,
+    /// The weighted sum of the top scores' pp.
+    pub weighted_pp: f64,
+    /// The pp awarded for the amount of ranked scores.
+    pub bonus_pp: f64,
+    /// The PP+ skill totals.
+    ///
+    /// Only osu!standard profiles have non-zero values.
+    pub skills: ProfileSkills,
+}
+
+/// Weighted totals of the PP+ skills.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProfileSkills {
+    pub aim: f64,
+    pub jump_aim: f64,
+    pub flow_aim: f64,
+    pub precision: f64,
+    pub speed: f64,
+    pub stamina: f64,
+    pub accuracy: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ProfileScore {
+    pp: f64,
+    skills: Option<ProfileSkills>,
+}
+
+impl ProfileScore {
+    /// Returns `None` if the score's mode is not `mode`.
+    fn new(attrs: &PerformanceAttributes, mode: GameMode) -> Option<Self> {
+        let (score_mode, skills) = match attrs {
+            PerformanceAttributes::Osu(attrs) => {
+                let skills = ProfileSkills {
+                    aim: attrs.pp_aim,
+                    jump_aim: attrs.pp_jump_aim,
+                    flow_aim: attrs.pp_flow_aim,
+                    precision: attrs.pp_precision,
+                    speed: attrs.pp_speed,
+                    stamina: attrs.pp_stamina,
+                    accuracy: attrs.pp_accuracy,
+                };
+
+                (GameMode::Osu, Some(skills))
+            }
+            PerformanceAttributes::Taiko(_) => (GameMode::Taiko, None),
+            PerformanceAttributes::Catch(_) => (GameMode::Catch, None),
+            PerformanceAttributes::Mania(_) => (GameMode::Mania, None),
+        };
+
+        (score_mode == mode).then(|| Self {
+            pp: attrs.pp(),
+            skills,
+        })
+    }
+}
+
+/// Sum up the top [`MAX_WEIGHTED_SCORES`] values, each weighted by `0.95^i`
+/// after sorting in descending order.
+pub fn weighted_sum(values: impl IntoIterator<Item = f64>) -> f64 {
+    let mut values: Vec<_> = values.into_iter().collect();
+    values.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+    values
+        .into_iter()
+        .take(MAX_WEIGHTED_SCORES)
+        .fold((0.0, 1.0), |(sum, weight), value| {
+            (sum + value * weight, weight * WEIGHT_DECAY)
+        })
+        .0
+}
+
+/// The bonus pp for the given amount of ranked scores.
+pub fn bonus_pp(ranked_scores: usize) -> f64 {
+    416.6667 * (1.0 - 0.9994_f64.powf(ranked_scores as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        osu::OsuPerformanceAttributes, taiko::TaikoPerformanceAttributes, util::float_ext::FloatExt,
+    };
+
+    use super::*;
+
+    #[test]
+    fn weighting() {
+        assert!(weighted_sum([100.0, 300.0, 200.0]).eq(300.0 + 200.0 * 0.95 + 100.0 * 0.9025));
+        assert!(weighted_sum([]).eq(0.0));
+
+        let many = weighted_sum(std::iter::repeat(100.0).take(MAX_WEIGHTED_SCORES + 50));
+        let top = weighted_sum(std::iter::repeat(100.0).take(MAX_WEIGHTED_SCORES));
+        assert!(many.eq(top));
+    }
+
+    #[test]
+    fn bonus() {
+        assert!(bonus_pp(0).eq(0.0));
+        assert!(bonus_pp(1000) < bonus_pp(2000));
+        assert!(bonus_pp(usize::MAX) <= 416.6667);
+    }
+
+    #[test]
+    fn skills_sorted_separately() {
+        let osu = |pp, pp_aim, pp_speed| {
+            PerformanceAttributes::Osu(OsuPerformanceAttributes {
+                pp,
+                pp_aim,
+                pp_speed,
+                ..Default::default()
+            })
+        };
+
+        let scores = [
+            osu(300.0, 250.0, 50.0),
+            osu(200.0, 20.0, 180.0),
+            osu(100.0, 10.0, 10.0),
+        ];
+
+        let profile = Profile::new(GameMode::Osu)
+            .scores(&scores)
+            .ranked_scores(0)
+            .calculate();
+
+        assert!(profile
+            .weighted_pp
+            .eq(300.0 + 200.0 * 0.95 + 100.0 * 0.9025));
+        assert!(profile.bonus_pp.eq(0.0));
+        assert!(profile.pp.eq(profile.weighted_pp));
+        assert!(profile.skills.aim.eq(250.0 + 20.0 * 0.95 + 10.0 * 0.9025));
+        assert!(profile.skills.speed.eq(180.0 + 50.0 * 0.95 + 10.0 * 0.9025));
+        assert!(profile.skills.stamina.eq(0.0));
+    }
+
+    #[test]
+    fn other_modes_ignored() {
+        let osu = PerformanceAttributes::Osu(OsuPerformanceAttributes {
+            pp: 300.0,
+            pp_aim: 250.0,
+            ..Default::default()
+        });
+
+        let taiko = PerformanceAttributes::Taiko(TaikoPerformanceAttributes {
+            pp: 400.0,
+            ..Default::default()
+        });
+
+        let profile = Profile::new(GameMode::Taiko)
+            .score(&osu)
+            .score(&taiko)
+            .calculate();
+
+        assert!(profile.weighted_pp.eq(400.0));
+        assert!(profile.bonus_pp.eq(bonus_pp(1)));
+        assert!(profile.skills.aim.eq(0.0));
+
+        let profile = Profile::new(GameMode::Osu)
+            .scores([&osu, &taiko])
+            .calculate();
+
+        assert!(profile.weighted_pp.eq(300.0));
+        assert!(profile.skills.aim.eq(250.0));
+    }
+}
@@ -9,6 +9,17 @@ pub struct TaikoDifficultyAttributes {
     pub rhythm: f64,
     /// The difficulty of the color skill.
     pub color: f64,
+    /// The difficulty of the raw tapping speed, ignoring note colors.
+    pub speed: f64,
+    /// The difficulty of coordinating fingers across note colors at speed.
+    pub finger_control: f64,
+    /// The difficulty of the stamina skill for streams of a single color.
+    pub single_color_stamina: f64,
+    /// The ratio of the single color stamina difficulty to the stamina
+    /// difficulty, to the power of five.
+    ///
+    /// Close to `1.0` on maps that mostly consist of single color streams.
+    pub mono_stamina_factor: f64,
     /// The difficulty of the hardest parts of the map.
     pub peak: f64,
     /// The perceived hit window for an n300 inclusive of rate-adjusting mods (DT/HT/etc)
@@ -17,6 +28,10 @@ pub struct TaikoDifficultyAttributes {
     pub stars: f64,
     /// The maximum combo.
     pub max_combo: u32,
+    /// The amount of drum roll ticks.
+    pub n_drumroll_ticks: u32,
+    /// The amount of swells.
+    pub n_swells: u32,
     /// Whether the [`Beatmap`] was a convert i.e. an osu!standard map.
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
@@ -53,8 +68,24 @@ pub struct TaikoPerformanceAttributes {
     pub pp_acc: f64,
     /// The strain portion of the final pp.
     pub pp_difficulty: f64,
+    /// The color portion of the final pp.
+    pub pp_color: f64,
+    /// The rhythm portion of the final pp.
+    pub pp_rhythm: f64,
+    /// The stamina portion of the final pp.
+    pub pp_stamina: f64,
+    /// The speed portion of the final pp.
+    pub pp_speed: f64,
+    /// The finger control portion of the final pp.
+    pub pp_finger_control: f64,
     /// Scaled miss count based on total hits.
     pub effective_miss_count: f64,
+    /// The unstable rate of the play.
+    ///
+    /// Either the unstable rate that was specified or an upper bound
+    /// estimated through the hitresults and the OD. `None` if there are no
+    /// 300s.
+    pub estimated_unstable_rate: Option<f64>,
 }
 
 impl TaikoPerformanceAttributes {
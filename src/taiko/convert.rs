@@ -169,6 +169,59 @@ fn should_convert_slider_to_taiko_hits(map: &Beatmap, params: &mut SliderParams<
     *tick_spacing > 0.0 && dist / osu_vel * 1000.0 < 2.0 * beat_len
 }
 
+/// The amount of ticks of a slider when played as a drum roll.
+pub fn drum_roll_ticks(
+    map: &Beatmap,
+    start_time: f64,
+    slider: &Slider,
+    bufs: &mut CurveBuffers,
+) -> u32 {
+    let spans = slider.span_count() as f64;
+    let dist = slider.curve(bufs).dist() * spans * f64::from(LEGACY_TAIKO_VELOCITY_MULTIPLIER);
+
+    let timing_beat_len = map
+        .timing_point_at(start_time)
+        .map_or(TimingPoint::DEFAULT_BEAT_LEN, |point| point.beat_len);
+
+    let bpm_multiplier = map
+        .difficulty_point_at(start_time)
+        .map_or(DifficultyPoint::DEFAULT_BPM_MULTIPLIER, |point| {
+            point.bpm_multiplier
+        });
+
+    // Converted maps already had their slider multiplier adjusted
+    let slider_multiplier = if map.is_convert {
+        map.slider_multiplier
+    } else {
+        map.slider_multiplier * f64::from(LEGACY_TAIKO_VELOCITY_MULTIPLIER)
+    };
+
+    let taiko_vel = f64::from(OSU_BASE_SCORING_DIST) * slider_multiplier;
+    let duration = f64::from((dist / taiko_vel * timing_beat_len * bpm_multiplier) as u32);
+
+    let tick_rate = if map.slider_tick_rate.eq(3.0) {
+        3.0
+    } else {
+        4.0
+    };
+
+    let tick_spacing = timing_beat_len / tick_rate;
+
+    if tick_spacing <= 0.0 {
+        return 0;
+    }
+
+    let mut ticks = 0;
+    let mut time = start_time;
+
+    while time < start_time + duration + tick_spacing / 2.0 {
+        ticks += 1;
+        time += tick_spacing;
+    }
+
+    ticks
+}
+
 struct SliderParams<'c> {
     slider: &'c Slider,
     bufs: CurveBuffers,
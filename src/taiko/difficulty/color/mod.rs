@@ -12,7 +12,10 @@ pub mod repeating_hit_patterns;
 
 #[derive(Debug, Default)]
 pub struct TaikoDifficultyColor {
+    /// Only set for the first hit object of the mono streak.
     pub mono_streak: Option<Weak<MonoStreak>>,
+    /// The mono streak that contains the hit object.
+    pub containing_mono_streak: Option<Weak<MonoStreak>>,
     pub alternating_mono_pattern: Option<Weak<AlternatingMonoPattern>>,
     pub repeating_hit_patterns: Option<RefCount<RepeatingHitPatterns>>,
 }
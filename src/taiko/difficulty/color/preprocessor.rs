@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 
 use crate::{
     taiko::difficulty::object::TaikoDifficultyObjects,
-    util::sync::{Ref, RefCount},
+    util::sync::{Ref, RefCount, Weak},
 };
 
 use super::{
@@ -51,7 +51,17 @@ impl ColorDifficultyPreprocessor {
 
                     if let Some(obj) = mono_streak.get().first_hit_object() {
                         obj.get_mut().color.mono_streak = Some(RefCount::downgrade(mono_streak));
-                    };
+                    }
+
+                    for obj in mono_streak
+                        .get()
+                        .hit_objects
+                        .iter()
+                        .filter_map(Weak::upgrade)
+                    {
+                        obj.get_mut().color.containing_mono_streak =
+                            Some(RefCount::downgrade(mono_streak));
+                    }
                 }
             }
         }
@@ -1,9 +1,18 @@
 use std::{cmp, mem, slice::Iter};
 
+use rosu_map::section::{general::GameMode, hit_objects::CurveBuffers};
+
 use crate::{
-    model::{beatmap::HitWindows, hit_object::HitObject},
-    taiko::TaikoBeatmap,
-    util::sync::RefCount,
+    any::GradualSnapshot,
+    model::{
+        beatmap::HitWindows,
+        hit_object::{HitObject, ObjectTime},
+    },
+    taiko::{
+        object::{BonusCount, BonusKind},
+        TaikoBeatmap,
+    },
+    util::{checkpoints::Checkpoints, object_times::ObjectTimes, sync::RefCount},
     Difficulty,
 };
 
@@ -56,6 +65,20 @@ pub struct TaikoGradualDifficulty {
     peaks: Peaks,
     total_hits: usize,
     first_combos: FirstTwoCombos,
+    bonuses: Box<[BonusKind]>,
+    bonus: BonusCount,
+    checkpoints: Checkpoints<Checkpoint>,
+    times: ObjectTimes,
+}
+
+/// The part of [`TaikoGradualDifficulty`] that changes while processing
+/// objects.
+#[derive(Clone)]
+struct Checkpoint {
+    attrs: TaikoDifficultyAttributes,
+    diff_objects_iter: Iter<'static, RefCount<TaikoDifficultyObject>>,
+    peaks: Peaks,
+    bonus: BonusCount,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -82,17 +105,27 @@ impl TaikoGradualDifficulty {
             (Some(true), Some(true)) => FirstTwoCombos::Both,
         };
 
+        let mut bufs = CurveBuffers::default();
+
+        let bonuses = converted
+            .hit_objects
+            .iter()
+            .map(|h| BonusKind::new(h, converted, &mut bufs))
+            .collect();
+
         let HitWindows { od: hit_window, .. } =
             converted.attributes().difficulty(&difficulty).hit_windows();
 
         let mut n_diff_objects = 0;
         let mut max_combo = 0;
 
+        // The bonus is counted while processing objects
         let diff_objects = DifficultyValues::create_difficulty_objects(
             converted,
             take as u32,
             clock_rate,
             &mut max_combo,
+            None,
             &mut n_diff_objects,
         );
 
@@ -112,6 +145,22 @@ impl TaikoGradualDifficulty {
 
         let diff_objects_iter = extend_lifetime(diff_objects.iter());
 
+        let times = ObjectTimes::new(
+            converted
+                .hit_objects
+                .iter()
+                .filter(|h| h.is_circle())
+                .map(|h| (h.start_time, h.start_time)),
+            clock_rate,
+        );
+
+        let checkpoints = Checkpoints::new(Checkpoint {
+            attrs: attrs.clone(),
+            diff_objects_iter: diff_objects_iter.clone(),
+            peaks: peaks.clone(),
+            bonus: BonusCount::default(),
+        });
+
         Self {
             idx: 0,
             difficulty,
@@ -121,21 +170,130 @@ impl TaikoGradualDifficulty {
             attrs,
             total_hits,
             first_combos,
+            bonuses,
+            bonus: BonusCount::default(),
+            checkpoints,
+            times,
         }
     }
-}
 
-fn extend_lifetime(
-    iter: Iter<'_, RefCount<TaikoDifficultyObject>>,
-) -> Iter<'static, RefCount<TaikoDifficultyObject>> {
-    // SAFETY: The underlying data will never be moved.
-    unsafe { mem::transmute(iter) }
-}
+    /// Store a snapshot of the state after every `interval` processed hit
+    /// objects so that [`seek`] can jump back and forth quickly.
+    ///
+    /// Smaller intervals make seeking faster but require more memory.
+    /// Without an interval, no snapshots are stored and seeking backwards
+    /// restarts from the first object.
+    ///
+    /// [`seek`]: Self::seek
+    #[must_use]
+    pub fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
 
-impl Iterator for TaikoGradualDifficulty {
-    type Item = TaikoDifficultyAttributes;
+    /// Specify whether [`advance_to_time`] compares timestamps against the
+    /// start or the end time of circles.
+    ///
+    /// Defaults to [`ObjectTime::Start`].
+    ///
+    /// [`advance_to_time`]: Self::advance_to_time
+    #[must_use]
+    pub const fn object_time(mut self, object_time: ObjectTime) -> Self {
+        self.times.set_object_time(object_time);
+
+        self
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Calculate the difficulty attributes after the circle at index `idx`,
+    /// regardless of how many circles were processed so far.
+    ///
+    /// The result is the same as calling `nth(idx)` on a new iterator and
+    /// afterwards, iteration continues with the circle at index `idx + 1`.
+    /// Returns `None` if `idx` is out of bounds.
+    pub fn seek(&mut self, idx: usize) -> Option<TaikoDifficultyAttributes> {
+        if idx >= self.idx + self.len() {
+            return None;
+        }
+
+        if let Some((checkpoint_idx, checkpoint)) = self.checkpoints.closest(self.idx, idx) {
+            let Checkpoint {
+                attrs,
+                diff_objects_iter,
+                peaks,
+                bonus,
+            } = checkpoint.clone();
+
+            self.idx = checkpoint_idx;
+            self.attrs = attrs;
+            self.diff_objects_iter = diff_objects_iter;
+            self.peaks = peaks;
+            self.bonus = bonus;
+        }
+
+        self.nth(idx - self.idx)
+    }
+
+    /// Process all circles whose time is at or before the timestamp `time`
+    /// in milliseconds and return the resulting difficulty attributes.
+    ///
+    /// The timestamp is adjusted by the clock rate so it should be the
+    /// elapsed time of a play, e.g. with DT an object at `1500` ms in the
+    /// map is reached after `1000` ms.
+    ///
+    /// Returns `None` if no new object was processed.
+    pub fn advance_to_time(&mut self, time: f64) -> Option<TaikoDifficultyAttributes> {
+        let count = self.times.count_until(time);
+
+        if count <= self.idx {
+            return None;
+        }
+
+        self.nth(count - self.idx - 1)
+    }
+
+    /// Capture the progress of the calculation in a compact
+    /// [`GradualSnapshot`].
+    pub fn snapshot(&self) -> GradualSnapshot {
+        GradualSnapshot::new(
+            GameMode::Taiko,
+            &self.difficulty,
+            self.idx + self.len(),
+            self.idx,
+            self.times.object_time(),
+            self.checkpoints.interval(),
+        )
+    }
+
+    /// Resume a calculation from a [`GradualSnapshot`].
+    ///
+    /// Returns `None` if the snapshot was created for a different mode or if
+    /// the map's amount of objects differs from the snapshot's.
+    pub fn from_snapshot(snapshot: &GradualSnapshot, converted: &TaikoBeatmap<'_>) -> Option<Self> {
+        if snapshot.mode() != GameMode::Taiko {
+            return None;
+        }
+
+        let mut gradual =
+            Self::new(snapshot.difficulty().clone(), converted).object_time(snapshot.object_time());
+
+        if gradual.len() != snapshot.n_objects() {
+            return None;
+        }
+
+        if let Some(interval) = snapshot.checkpoint_interval() {
+            gradual = gradual.checkpoint_interval(interval);
+        }
+
+        for _ in 0..snapshot.n_processed() {
+            gradual.advance()?;
+        }
+
+        Some(gradual)
+    }
+
+    /// Process the next circle without evaluating the skills.
+    fn advance(&mut self) -> Option<()> {
         // The first difficulty object belongs to the third note since each
         // difficulty object requires the current, the last, and the second to
         // last note. Hence, if we're still on the first or second object, we
@@ -145,6 +303,8 @@ impl Iterator for TaikoGradualDifficulty {
                 let curr = self.diff_objects_iter.next()?;
                 let borrowed = curr.get();
                 PeaksSkill::new(&mut self.peaks, &self.diff_objects).process(&borrowed);
+                // The first two objects have no difficulty object
+                self.bonus.add(self.bonuses[borrowed.idx + 2]);
 
                 if borrowed.base_hit_type.is_hit() {
                     self.attrs.max_combo += 1;
@@ -155,6 +315,8 @@ impl Iterator for TaikoGradualDifficulty {
         } else if self.diff_objects.is_empty() {
             return None;
         } else {
+            self.bonus.add(self.bonuses[self.idx]);
+
             match self.first_combos {
                 FirstTwoCombos::OnlyFirst => self.attrs.max_combo = 1,
                 FirstTwoCombos::OnlySecond if self.idx == 1 => self.attrs.max_combo = 1,
@@ -166,14 +328,50 @@ impl Iterator for TaikoGradualDifficulty {
 
         self.idx += 1;
 
+        self.checkpoints.save(self.idx, || Checkpoint {
+            attrs: self.attrs.clone(),
+            diff_objects_iter: self.diff_objects_iter.clone(),
+            peaks: self.peaks.clone(),
+            bonus: self.bonus,
+        });
+
+        Some(())
+    }
+}
+
+fn extend_lifetime(
+    iter: Iter<'_, RefCount<TaikoDifficultyObject>>,
+) -> Iter<'static, RefCount<TaikoDifficultyObject>> {
+    // SAFETY: The underlying data will never be moved.
+    unsafe { mem::transmute(iter) }
+}
+
+impl Iterator for TaikoGradualDifficulty {
+    type Item = TaikoDifficultyAttributes;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance()?;
+
         let color = self.peaks.color_difficulty_value();
         let rhythm = self.peaks.rhythm_difficulty_value();
         let stamina = self.peaks.stamina_difficulty_value();
+        let speed = self.peaks.speed_difficulty_value();
+        let single_color_stamina = self.peaks.single_color_stamina_difficulty_value();
         let combined = self.peaks.clone().difficulty_value();
 
         let mut attrs = self.attrs.clone();
-
-        DifficultyValues::eval(&mut attrs, color, rhythm, stamina, combined);
+        attrs.n_drumroll_ticks = self.bonus.n_drumroll_ticks;
+        attrs.n_swells = self.bonus.n_swells;
+
+        DifficultyValues::eval(
+            &mut attrs,
+            color,
+            rhythm,
+            stamina,
+            speed,
+            single_color_stamina,
+            combined,
+        );
 
         Some(attrs)
     }
@@ -185,61 +383,8 @@ impl Iterator for TaikoGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let mut take = cmp::min(n, self.len().saturating_sub(1));
-
-        // The first two notes have no difficulty object but might add to combo
-        match (take, self.idx) {
-            (_, 2..) | (0, _) => {}
-            (1, 0) => {
-                take -= 1;
-                self.idx += 1;
-
-                match self.first_combos {
-                    FirstTwoCombos::None => {}
-                    FirstTwoCombos::OnlyFirst => self.attrs.max_combo = 1,
-                    FirstTwoCombos::OnlySecond => {}
-                    FirstTwoCombos::Both => self.attrs.max_combo = 1,
-                }
-            }
-            (_, 0) => {
-                take -= 2;
-                self.idx += 2;
-
-                match self.first_combos {
-                    FirstTwoCombos::None => {}
-                    FirstTwoCombos::OnlyFirst => self.attrs.max_combo = 1,
-                    FirstTwoCombos::OnlySecond => self.attrs.max_combo = 1,
-                    FirstTwoCombos::Both => self.attrs.max_combo = 2,
-                }
-            }
-            (_, 1) => {
-                take -= 1;
-                self.idx += 1;
-
-                match self.first_combos {
-                    FirstTwoCombos::None => {}
-                    FirstTwoCombos::OnlyFirst => self.attrs.max_combo = 1,
-                    FirstTwoCombos::OnlySecond => self.attrs.max_combo = 1,
-                    FirstTwoCombos::Both => self.attrs.max_combo = 2,
-                }
-            }
-        }
-
-        let mut peaks = PeaksSkill::new(&mut self.peaks, &self.diff_objects);
-
-        for _ in 0..take {
-            loop {
-                let curr = self.diff_objects_iter.next()?;
-                let borrowed = curr.get();
-                peaks.process(&borrowed);
-
-                if borrowed.base_hit_type.is_hit() {
-                    self.attrs.max_combo += 1;
-                    self.idx += 1;
-
-                    break;
-                }
-            }
+        for _ in 0..cmp::min(n, self.len().saturating_sub(1)) {
+            self.advance()?;
         }
 
         self.next()
@@ -314,4 +459,25 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn seek() {
+        let converted = Beatmap::from_path("./resources/1028484.osu")
+            .unwrap()
+            .unchecked_into_converted();
+
+        let difficulty = Difficulty::new().mods(64);
+        let mut gradual =
+            TaikoGradualDifficulty::new(difficulty.clone(), &converted).checkpoint_interval(16);
+        let len = gradual.len();
+
+        for idx in [100, 20, 150, 151, 0, len - 1, 31, 32, 33] {
+            let mut expected = TaikoGradualDifficulty::new(difficulty.clone(), &converted);
+
+            assert_eq!(gradual.seek(idx), expected.nth(idx), "idx={idx}");
+            assert_eq!(gradual.next(), expected.next(), "idx={idx}");
+        }
+
+        assert!(gradual.seek(len).is_none());
+    }
 }
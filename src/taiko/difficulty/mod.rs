@@ -1,3 +1,5 @@
+use rosu_map::section::hit_objects::CurveBuffers;
+
 use crate::{
     taiko::{
         difficulty::{
@@ -5,7 +7,7 @@ use crate::{
             object::{TaikoDifficultyObject, TaikoDifficultyObjects},
             skills::peaks::PeaksSkill,
         },
-        object::TaikoObject,
+        object::{BonusCount, BonusKind, TaikoObject},
     },
     Difficulty,
 };
@@ -18,9 +20,9 @@ mod color;
 pub mod gradual;
 mod object;
 mod rhythm;
-mod skills;
+pub mod skills;
 
-const DIFFICULTY_MULTIPLIER: f64 = 1.35;
+pub const DIFFICULTY_MULTIPLIER: f64 = 1.35;
 
 pub fn difficulty(
     difficulty: &Difficulty,
@@ -32,11 +34,16 @@ pub fn difficulty(
         .hit_windows()
         .od;
 
-    let DifficultyValues { peaks, max_combo } = DifficultyValues::calculate(difficulty, converted);
+    let mut bonus = BonusCount::default();
+
+    let DifficultyValues { peaks, max_combo } =
+        DifficultyValues::calculate(difficulty, converted, Some(&mut bonus));
 
     let mut attrs = TaikoDifficultyAttributes {
         hit_window,
         max_combo,
+        n_drumroll_ticks: bonus.n_drumroll_ticks,
+        n_swells: bonus.n_swells,
         is_convert: converted.is_convert,
         ..Default::default()
     };
@@ -44,6 +51,8 @@ pub fn difficulty(
     let color_rating = peaks.color_difficulty_value();
     let rhythm_rating = peaks.rhythm_difficulty_value();
     let stamina_rating = peaks.stamina_difficulty_value();
+    let speed_rating = peaks.speed_difficulty_value();
+    let single_color_stamina_rating = peaks.single_color_stamina_difficulty_value();
     let combined_rating = peaks.difficulty_value();
 
     DifficultyValues::eval(
@@ -51,13 +60,15 @@ pub fn difficulty(
         color_rating,
         rhythm_rating,
         stamina_rating,
+        speed_rating,
+        single_color_stamina_rating,
         combined_rating,
     );
 
     attrs
 }
 
-fn rescale(stars: f64) -> f64 {
+pub fn rescale(stars: f64) -> f64 {
     if stars < 0.0 {
         stars
     } else {
@@ -71,7 +82,13 @@ pub struct DifficultyValues {
 }
 
 impl DifficultyValues {
-    pub fn calculate(difficulty: &Difficulty, converted: &TaikoBeatmap<'_>) -> Self {
+    /// Process all passed objects and, if `bonus` is specified, count their
+    /// bonus objects.
+    pub fn calculate(
+        difficulty: &Difficulty,
+        converted: &TaikoBeatmap<'_>,
+        bonus: Option<&mut BonusCount>,
+    ) -> Self {
         let take = difficulty.get_passed_objects();
         let clock_rate = difficulty.get_clock_rate();
 
@@ -83,6 +100,7 @@ impl DifficultyValues {
             take as u32,
             clock_rate,
             &mut max_combo,
+            bonus,
             &mut n_diff_objects,
         );
 
@@ -107,13 +125,31 @@ impl DifficultyValues {
         color_difficulty_value: f64,
         rhythm_difficulty_value: f64,
         stamina_difficulty_value: f64,
+        speed_difficulty_value: f64,
+        single_color_stamina_difficulty_value: f64,
         peaks_difficulty_value: f64,
     ) {
         let color_rating = color_difficulty_value * DIFFICULTY_MULTIPLIER;
         let rhythm_rating = rhythm_difficulty_value * DIFFICULTY_MULTIPLIER;
         let stamina_rating = stamina_difficulty_value * DIFFICULTY_MULTIPLIER;
+        let speed_rating = speed_difficulty_value * DIFFICULTY_MULTIPLIER;
+        let single_color_stamina_rating =
+            single_color_stamina_difficulty_value * DIFFICULTY_MULTIPLIER;
         let combined_rating = peaks_difficulty_value * DIFFICULTY_MULTIPLIER;
 
+        let mono_stamina_factor = if stamina_rating.abs() < f64::EPSILON {
+            1.0
+        } else {
+            (single_color_stamina_rating / stamina_rating).powf(5.0)
+        };
+
+        // Ignoring colors, the tapping speed can only be harder than stamina.
+        // The difference is the part that depends on coordinating fingers
+        // across colors.
+        let finger_control_rating = (speed_rating.powi(2) - stamina_rating.powi(2))
+            .max(0.0)
+            .sqrt();
+
         let mut star_rating = rescale(combined_rating * 1.4);
 
         // * TODO: This is temporary measure as we don't detect abuse of multiple-input
@@ -129,6 +165,10 @@ impl DifficultyValues {
         }
 
         attrs.stamina = stamina_rating;
+        attrs.speed = speed_rating;
+        attrs.finger_control = finger_control_rating;
+        attrs.single_color_stamina = single_color_stamina_rating;
+        attrs.mono_stamina_factor = mono_stamina_factor;
         attrs.rhythm = rhythm_rating;
         attrs.color = color_rating;
         attrs.peak = combined_rating;
@@ -140,19 +180,29 @@ impl DifficultyValues {
         take: u32,
         clock_rate: f64,
         max_combo: &mut u32,
+        mut bonus: Option<&mut BonusCount>,
         n_diff_objects: &mut usize,
     ) -> TaikoDifficultyObjects {
+        let mut bufs = CurveBuffers::default();
+
         let mut hit_objects_iter = converted
             .hit_objects
             .iter()
             .zip(converted.hit_sounds.iter())
-            .map(|(h, s)| TaikoObject::new(h, *s))
-            .inspect(|h| {
+            .map(|(h, s)| (h, TaikoObject::new(h, *s)))
+            .inspect(|(h, taiko_object)| {
                 if *max_combo < take {
                     *n_diff_objects += 1;
-                    *max_combo += u32::from(h.is_hit());
+                    *max_combo += u32::from(taiko_object.is_hit());
+
+                    // Drum roll ticks require the slider's curve so bonus
+                    // kinds are only created when they're counted
+                    if let Some(ref mut bonus) = bonus {
+                        bonus.add(BonusKind::new(h, converted, &mut bufs));
+                    }
                 }
-            });
+            })
+            .map(|(_, taiko_object)| taiko_object);
 
         let Some((mut last_last, mut last)) = hit_objects_iter.next().zip(hit_objects_iter.next())
         else {
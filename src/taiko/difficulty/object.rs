@@ -141,6 +141,14 @@ impl TaikoDifficultyObjects {
             .checked_sub(backwards_idx + 1)
             .and_then(|idx| self.note_objects.get(idx))
     }
+
+    pub fn next_note(
+        &self,
+        curr: &TaikoDifficultyObject,
+        forwards_idx: usize,
+    ) -> Option<&RefCount<TaikoDifficultyObject>> {
+        self.note_objects.get(curr.note_idx + (forwards_idx + 1))
+    }
 }
 
 #[rustfmt::skip]
@@ -50,6 +50,11 @@ impl Color {
         self.inner.get_curr_strain_peaks()
     }
 
+    /// The end time of the current strain section.
+    pub const fn section_end(&self) -> f64 {
+        self.inner.inner.curr_section_end
+    }
+
     pub fn as_difficulty_value(&self) -> f64 {
         self.inner
             .clone()
@@ -7,9 +7,9 @@ use crate::{
 
 use super::{color::Color, rhythm::Rhythm, stamina::Stamina};
 
-const RHYTHM_SKILL_MULTIPLIER: f64 = 0.2 * FINAL_MULTIPLIER;
-const COLOR_SKILL_MULTIPLIER: f64 = 0.375 * FINAL_MULTIPLIER;
-const STAMINA_SKILL_MULTIPLIER: f64 = 0.375 * FINAL_MULTIPLIER;
+pub const RHYTHM_SKILL_MULTIPLIER: f64 = 0.2 * FINAL_MULTIPLIER;
+pub const COLOR_SKILL_MULTIPLIER: f64 = 0.375 * FINAL_MULTIPLIER;
+pub const STAMINA_SKILL_MULTIPLIER: f64 = 0.375 * FINAL_MULTIPLIER;
 
 const FINAL_MULTIPLIER: f64 = 0.0625;
 
@@ -18,6 +18,11 @@ pub struct Peaks {
     pub color: Color,
     pub rhythm: Rhythm,
     pub stamina: Stamina,
+    /// Stamina without considering note colors. Not part of the combined
+    /// difficulty.
+    pub speed: Stamina,
+    /// Stamina of single color streams. Not part of the combined difficulty.
+    pub single_color_stamina: Stamina,
 }
 
 impl Peaks {
@@ -25,7 +30,9 @@ impl Peaks {
         Self {
             color: Color::default(),
             rhythm: Rhythm::default(),
-            stamina: Stamina::default(),
+            stamina: Stamina::new(false, false),
+            speed: Stamina::new(true, false),
+            single_color_stamina: Stamina::new(false, true),
         }
     }
 
@@ -41,6 +48,14 @@ impl Peaks {
         self.stamina.as_difficulty_value() * STAMINA_SKILL_MULTIPLIER
     }
 
+    pub fn speed_difficulty_value(&self) -> f64 {
+        self.speed.as_difficulty_value() * STAMINA_SKILL_MULTIPLIER
+    }
+
+    pub fn single_color_stamina_difficulty_value(&self) -> f64 {
+        self.single_color_stamina.as_difficulty_value() * STAMINA_SKILL_MULTIPLIER
+    }
+
     fn norm(p: f64, values: impl IntoIterator<Item = f64>) -> f64 {
         values
             .into_iter()
@@ -48,6 +63,17 @@ impl Peaks {
             .powf(p.recip())
     }
 
+    /// Combine the strain peaks of all skills for a single section.
+    pub fn combined_peak(color_peak: f64, rhythm_peak: f64, stamina_peak: f64) -> f64 {
+        let color_peak = color_peak * COLOR_SKILL_MULTIPLIER;
+        let rhythm_peak = rhythm_peak * RHYTHM_SKILL_MULTIPLIER;
+        let stamina_peak = stamina_peak * STAMINA_SKILL_MULTIPLIER;
+
+        let peak = Self::norm(1.5, [color_peak, stamina_peak]);
+
+        Self::norm(2.0, [peak, rhythm_peak])
+    }
+
     pub fn difficulty_value(self) -> f64 {
         let color_peaks = self.color.get_curr_strain_peaks();
         let rhythm_peaks = self.rhythm.get_curr_strain_peaks();
@@ -64,13 +90,8 @@ impl Peaks {
             .zip(rhythm_peaks.iter())
             .zip(stamina_peaks.iter());
 
-        for ((mut color_peak, mut rhythm_peak), mut stamina_peak) in zip {
-            color_peak *= COLOR_SKILL_MULTIPLIER;
-            rhythm_peak *= RHYTHM_SKILL_MULTIPLIER;
-            stamina_peak *= STAMINA_SKILL_MULTIPLIER;
-
-            let mut peak = Self::norm(1.5, [color_peak, stamina_peak]);
-            peak = Self::norm(2.0, [peak, rhythm_peak]);
+        for ((color_peak, rhythm_peak), stamina_peak) in zip {
+            let peak = Self::combined_peak(color_peak, rhythm_peak, stamina_peak);
 
             if peak > 0.0 {
                 peaks.push(peak);
@@ -95,6 +116,8 @@ pub struct PeaksSkill<'a> {
     pub color: Skill<'a, Color>,
     pub rhythm: Skill<'a, Rhythm>,
     pub stamina: Skill<'a, Stamina>,
+    pub speed: Skill<'a, Stamina>,
+    pub single_color_stamina: Skill<'a, Stamina>,
 }
 
 impl<'a> PeaksSkill<'a> {
@@ -103,6 +126,8 @@ impl<'a> PeaksSkill<'a> {
             color: Skill::new(&mut peaks.color, diff_objects),
             rhythm: Skill::new(&mut peaks.rhythm, diff_objects),
             stamina: Skill::new(&mut peaks.stamina, diff_objects),
+            speed: Skill::new(&mut peaks.speed, diff_objects),
+            single_color_stamina: Skill::new(&mut peaks.single_color_stamina, diff_objects),
         }
     }
 
@@ -110,5 +135,7 @@ impl<'a> PeaksSkill<'a> {
         self.rhythm.process(curr);
         self.color.process(curr);
         self.stamina.process(curr);
+        self.speed.process(curr);
+        self.single_color_stamina.process(curr);
     }
 }
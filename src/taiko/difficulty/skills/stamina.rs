@@ -4,10 +4,13 @@ use crate::{
         skills::{strain_decay, ISkill, Skill, StrainDecaySkill},
     },
     taiko::{
-        difficulty::object::{TaikoDifficultyObject, TaikoDifficultyObjects},
+        difficulty::object::{MonoIndex, TaikoDifficultyObject, TaikoDifficultyObjects},
         object::HitType,
     },
-    util::strains_vec::StrainsVec,
+    util::{
+        strains_vec::StrainsVec,
+        sync::{RefCount, Weak},
+    },
 };
 
 const SKILL_MULTIPLIER: f64 = 1.1;
@@ -16,9 +19,24 @@ const STRAIN_DECAY_BASE: f64 = 0.4;
 #[derive(Clone, Default)]
 pub struct Stamina {
     inner: StrainDecaySkill,
+    speed: bool,
+    single_color: bool,
 }
 
 impl Stamina {
+    /// If `speed` is `true`, the color of notes is ignored so that only the
+    /// raw tapping speed is considered.
+    ///
+    /// If `single_color` is `true`, only notes deep within long streams of a
+    /// single color are considered.
+    pub fn new(speed: bool, single_color: bool) -> Self {
+        Self {
+            inner: StrainDecaySkill::default(),
+            speed,
+            single_color,
+        }
+    }
+
     pub fn get_curr_strain_peaks(self) -> StrainsVec {
         self.inner.get_curr_strain_peaks()
     }
@@ -75,7 +93,14 @@ impl Skill<'_, Stamina> {
 
         while curr.start_time > self.curr_section_end() {
             self.inner.inner.save_curr_peak();
-            let initial_strain = self.calculate_initial_strain(self.curr_section_end(), curr);
+
+            let initial_strain = if self.inner.single_color {
+                // * Safely prevents previous strains from shifting as new notes are added.
+                0.0
+            } else {
+                self.calculate_initial_strain(self.curr_section_end(), curr)
+            };
+
             self.inner.inner.start_new_section_from(initial_strain);
             *self.curr_section_end_mut() += StrainDecaySkill::SECTION_LEN;
         }
@@ -88,11 +113,39 @@ impl Skill<'_, Stamina> {
         *self.curr_strain_mut() *= strain_decay(curr.delta_time, STRAIN_DECAY_BASE);
         *self.curr_strain_mut() += self.strain_value_of(curr) * SKILL_MULTIPLIER;
 
-        self.curr_strain()
+        if self.inner.single_color {
+            let idx = mono_streak_idx(curr) as f64;
+
+            // * Only notes deep within a single color stream are considered.
+            self.curr_strain() / (1.0 + (-(idx - 10.0) / 2.0).exp())
+        } else {
+            self.curr_strain()
+        }
     }
 
     fn strain_value_of(&self, curr: &TaikoDifficultyObject) -> f64 {
-        StaminaEvaluator::evaluate_diff_of(curr, self.diff_objects)
+        StaminaEvaluator::evaluate_diff_of(curr, self.diff_objects, self.inner.speed)
+    }
+}
+
+/// The index of the note within its mono streak.
+fn mono_streak_idx(curr: &TaikoDifficultyObject) -> usize {
+    let Some(first) = curr
+        .color
+        .containing_mono_streak
+        .as_ref()
+        .and_then(Weak::upgrade)
+        .and_then(|mono_streak| mono_streak.get().first_hit_object())
+    else {
+        return 0;
+    };
+
+    let first = first.get();
+
+    match (&curr.mono_idx, &first.mono_idx) {
+        (MonoIndex::Center(curr), MonoIndex::Center(first))
+        | (MonoIndex::Rim(curr), MonoIndex::Rim(first)) => curr.saturating_sub(*first),
+        _ => 0,
     }
 }
 
@@ -108,14 +161,68 @@ impl StaminaEvaluator {
         30.0 / interval
     }
 
-    fn evaluate_diff_of(curr: &TaikoDifficultyObject, hit_objects: &TaikoDifficultyObjects) -> f64 {
+    /// The amount of fingers that can be used to hit the current note.
+    fn available_fingers_for(
+        hit_object: &TaikoDifficultyObject,
+        hit_objects: &TaikoDifficultyObjects,
+    ) -> usize {
+        let Some(mono_streak) = hit_object
+            .color
+            .containing_mono_streak
+            .as_ref()
+            .and_then(Weak::upgrade)
+        else {
+            return 4;
+        };
+
+        let mono_streak = mono_streak.get();
+
+        let prev_color_change = mono_streak.first_hit_object().and_then(|first| {
+            hit_objects
+                .previous_note(&first.get(), 0)
+                .map(RefCount::clone)
+        });
+
+        if prev_color_change
+            .is_some_and(|prev| hit_object.start_time - prev.get().start_time < 300.0)
+        {
+            return 2;
+        }
+
+        let next_color_change = mono_streak
+            .last_hit_object()
+            .and_then(|last| hit_objects.next_note(&last.get(), 0).map(RefCount::clone));
+
+        if next_color_change
+            .is_some_and(|next| next.get().start_time - hit_object.start_time < 300.0)
+        {
+            return 2;
+        }
+
+        4
+    }
+
+    fn evaluate_diff_of(
+        curr: &TaikoDifficultyObject,
+        hit_objects: &TaikoDifficultyObjects,
+        speed: bool,
+    ) -> f64 {
         if matches!(curr.base_hit_type, HitType::NonHit) {
             return 0.0;
         }
 
-        // * Find the previous hit object hit by the current key, which is two notes of the same colour prior.
+        // * Find the previous hit object hit by the current finger, which is n notes prior, n being the number of available fingers.
         let taiko_curr = curr;
-        let key_prev = hit_objects.previous_mono(taiko_curr, 1);
+
+        // When only considering speed, every note is treated as the same
+        // color i.e. the current key was hit two notes prior.
+        let key_prev = if speed {
+            hit_objects.previous_note(taiko_curr, 1)
+        } else {
+            let available_fingers = Self::available_fingers_for(taiko_curr, hit_objects);
+
+            hit_objects.previous_mono(taiko_curr, available_fingers - 1)
+        };
 
         if let Some(key_prev) = key_prev {
             // * Add a base strain to all objects
@@ -126,3 +233,58 @@ impl StaminaEvaluator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{taiko::Taiko, Beatmap, Difficulty};
+
+    fn map(hit_sounds: impl Iterator<Item = u8>) -> Beatmap {
+        let mut content = String::from(
+            "osu file format v14\n\n[General]\nMode: 1\n\n[Difficulty]\nOverallDifficulty:5\n\
+            SliderMultiplier:1.4\nSliderTickRate:1\n\n[TimingPoints]\n0,300,4,1,0,100,1,0\n\n\
+            [HitObjects]\n",
+        );
+
+        for (i, hit_sound) in hit_sounds.enumerate() {
+            content.push_str(&format!(
+                "256,192,{},1,{hit_sound},0:0:0:0:\n",
+                1000 + i * 75
+            ));
+        }
+
+        Beatmap::from_bytes(content.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn mono_stamina_factor() {
+        let mono = map((0..200).map(|_| 0));
+        let alternating = map((0..200).map(|i| if i % 2 == 0 { 0 } else { 8 }));
+
+        let calculate = |map: &Beatmap| {
+            Difficulty::new()
+                .with_mode::<Taiko>()
+                .calculate(&map.unchecked_as_converted())
+        };
+
+        let mono = calculate(&mono);
+        let alternating = calculate(&alternating);
+
+        assert!(mono.single_color_stamina > 0.0);
+        assert!(mono.mono_stamina_factor > 0.5);
+        assert!(alternating.mono_stamina_factor < 0.01);
+    }
+
+    #[test]
+    fn available_fingers() {
+        let map = Beatmap::from_path("./resources/1028484.osu")
+            .unwrap()
+            .unchecked_into_converted::<Taiko>();
+
+        let attrs = Difficulty::new().with_mode().calculate(&map);
+
+        // Limiting the fingers for notes close to color changes lowers
+        // stamina compared to always using all four fingers.
+        assert_eq!(attrs.stamina, 1.3991746883284404);
+        assert_eq!(attrs.stars, 2.9145897001804406);
+    }
+}
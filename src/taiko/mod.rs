@@ -1,4 +1,5 @@
 use crate::{
+    any::HardestSection,
     model::{
         beatmap::Beatmap,
         mode::{ConvertStatus, IGameMode},
@@ -10,7 +11,9 @@ pub use self::{
     attributes::{TaikoDifficultyAttributes, TaikoPerformanceAttributes},
     convert::TaikoBeatmap,
     difficulty::gradual::TaikoGradualDifficulty,
-    performance::{gradual::TaikoGradualPerformance, TaikoPerformance},
+    performance::{
+        batch::TaikoPerformanceBatch, gradual::TaikoGradualPerformance, TaikoPerformance,
+    },
     score_state::TaikoScoreState,
     strains::TaikoStrains,
 };
@@ -54,6 +57,14 @@ impl IGameMode for Taiko {
         strains::strains(difficulty, converted)
     }
 
+    fn hardest_section(
+        difficulty: &Difficulty,
+        converted: &TaikoBeatmap<'_>,
+        duration: f64,
+    ) -> Option<HardestSection> {
+        strains::hardest_section(difficulty, converted, duration)
+    }
+
     fn performance(map: TaikoBeatmap<'_>) -> Self::Performance<'_> {
         TaikoPerformance::new(map)
     }
@@ -1,6 +1,11 @@
-use rosu_map::section::hit_objects::hit_samples::HitSoundType;
+use rosu_map::section::hit_objects::{hit_samples::HitSoundType, CurveBuffers};
 
-use crate::model::hit_object::HitObject;
+use crate::model::{
+    beatmap::Beatmap,
+    hit_object::{HitObject, HitObjectKind},
+};
+
+use super::convert::drum_roll_ticks;
 
 pub struct TaikoObject {
     pub start_time: f64,
@@ -38,3 +43,47 @@ impl HitType {
         !matches!(self, Self::NonHit)
     }
 }
+
+/// Objects that don't add to the combo but give bonus score.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BonusKind {
+    None,
+    /// A drum roll i.e. a slider with the given amount of ticks.
+    DrumRoll {
+        ticks: u32,
+    },
+    /// A swell i.e. a spinner.
+    Swell,
+}
+
+impl BonusKind {
+    /// The bonus kind of `h`.
+    ///
+    /// `bufs` is only used for drum rolls and can be reused across objects.
+    pub fn new(h: &HitObject, map: &Beatmap, bufs: &mut CurveBuffers) -> Self {
+        match h.kind {
+            HitObjectKind::Circle => Self::None,
+            HitObjectKind::Slider(ref slider) => Self::DrumRoll {
+                ticks: drum_roll_ticks(map, h.start_time, slider, bufs),
+            },
+            HitObjectKind::Spinner(_) | HitObjectKind::Hold(_) => Self::Swell,
+        }
+    }
+}
+
+/// Amount of bonus objects.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BonusCount {
+    pub n_drumroll_ticks: u32,
+    pub n_swells: u32,
+}
+
+impl BonusCount {
+    pub const fn add(&mut self, bonus: BonusKind) {
+        match bonus {
+            BonusKind::None => {}
+            BonusKind::DrumRoll { ticks } => self.n_drumroll_ticks += ticks,
+            BonusKind::Swell => self.n_swells += 1,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::taiko::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoScoreState};
+
+use super::{TaikoPerformanceInner, TaikoPerformanceInvariants};
+
+/// Calculate the performance attributes of many scores on the same osu!taiko
+/// map.
+///
+/// All values that only depend on the map and mods are computed once upfront
+/// so this is considerably faster than creating a [`TaikoPerformance`] for
+/// each score while producing the same results.
+///
+/// Note that score states are used as is, i.e. unlike [`TaikoPerformance`],
+/// missing hitresults are not generated so every state should be complete.
+/// Since score states contain no unstable rate, it is always estimated.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty};
+/// use rosu_pp::taiko::{Taiko, TaikoPerformanceBatch, TaikoScoreState};
+///
+/// let converted = Beatmap::from_path("./resources/1028484.osu")
+///     .unwrap()
+///     .unchecked_into_converted::<Taiko>();
+///
+/// let mods = 8 + 64; // HDDT
+/// let attrs = Difficulty::new().mods(mods).with_mode().calculate(&converted);
+///
+/// let states: Vec<_> = (0..10)
+///     .map(|misses| TaikoScoreState {
+///         max_combo: attrs.max_combo / (misses + 1),
+///         n300: attrs.max_combo - misses,
+///         misses,
+///         ..Default::default()
+///     })
+///     .collect();
+///
+/// let batch = TaikoPerformanceBatch::new(attrs, mods);
+///
+/// for performance in batch.calculate(&states) {
+///     println!("PP: {}", performance.pp);
+/// }
+/// ```
+///
+/// [`TaikoPerformance`]: crate::taiko::TaikoPerformance
+pub struct TaikoPerformanceBatch {
+    attrs: TaikoDifficultyAttributes,
+    mods: u32,
+    invariants: TaikoPerformanceInvariants,
+}
+
+impl TaikoPerformanceBatch {
+    /// Prepare the batch calculation for the given difficulty attributes.
+    ///
+    /// `mods` must be the same mods that the attributes were calculated with.
+    pub fn new(attrs: TaikoDifficultyAttributes, mods: u32) -> Self {
+        let invariants = TaikoPerformanceInvariants::new(&attrs, mods);
+
+        Self {
+            attrs,
+            mods,
+            invariants,
+        }
+    }
+
+    /// Calculate the performance attributes for each score state.
+    pub fn calculate(&self, states: &[TaikoScoreState]) -> Vec<TaikoPerformanceAttributes> {
+        states
+            .iter()
+            .map(|state| self.calculate_single(state))
+            .collect()
+    }
+
+    /// Calculate the performance attributes for a single score state.
+    pub fn calculate_single(&self, state: &TaikoScoreState) -> TaikoPerformanceAttributes {
+        let inner = TaikoPerformanceInner {
+            attrs: self.attrs.clone(),
+            mods: self.mods,
+            state: *state,
+            unstable_rate: None,
+        };
+
+        inner.calculate_with(&self.invariants)
+    }
+}
@@ -1,20 +1,32 @@
-use std::cmp;
+use std::{cmp, f64::consts::SQRT_2};
+
+use statrs::function::erf::{erf, erf_inv};
 
 use crate::{
     any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance},
     osu::OsuPerformance,
-    util::{map_or_attrs::MapOrAttrs, mods::Mods},
+    util::{
+        float_ext::FloatExt,
+        map_or_attrs::MapOrAttrs,
+        mods::Mods,
+        solver::{self, Solvable},
+    },
     Performance,
 };
 
 use super::{
     attributes::{TaikoDifficultyAttributes, TaikoPerformanceAttributes},
+    difficulty::rescale,
     score_state::TaikoScoreState,
     Taiko,
 };
 
+pub mod batch;
 pub mod gradual;
 
+/// Specified unstable rates are clamped to at least this value.
+const MIN_UNSTABLE_RATE: f64 = 10.0;
+
 /// Performance calculator on osu!taiko maps.
 #[derive(Clone, Debug, PartialEq)]
 #[must_use]
@@ -27,6 +39,9 @@ pub struct TaikoPerformance<'map> {
     n300: Option<u32>,
     n100: Option<u32>,
     misses: Option<u32>,
+    drumroll_ticks: Option<u32>,
+    swells: Option<u32>,
+    unstable_rate: Option<f64>,
 }
 
 impl<'map> TaikoPerformance<'map> {
@@ -115,6 +130,24 @@ impl<'map> TaikoPerformance<'map> {
         self
     }
 
+    /// Specify the amount of hit drum roll ticks of a play.
+    ///
+    /// If none is specified, all drum roll ticks are considered hit.
+    pub const fn drumroll_ticks(mut self, drumroll_ticks: u32) -> Self {
+        self.drumroll_ticks = Some(drumroll_ticks);
+
+        self
+    }
+
+    /// Specify the amount of completed swells of a play.
+    ///
+    /// If none is specified, all swells are considered completed.
+    pub const fn swells(mut self, swells: u32) -> Self {
+        self.swells = Some(swells);
+
+        self
+    }
+
     /// Specify the accuracy of a play between `0.0` and `100.0`.
     /// This will be used to generate matching hitresults.
     pub fn accuracy(mut self, acc: f64) -> Self {
@@ -123,6 +156,19 @@ impl<'map> TaikoPerformance<'map> {
         self
     }
 
+    /// Specify the unstable rate of a play.
+    ///
+    /// If none is specified, it will be estimated based on the hitresults and
+    /// the OD. Values below `10.0` are clamped and non-finite values are
+    /// ignored.
+    pub const fn unstable_rate(mut self, unstable_rate: f64) -> Self {
+        if unstable_rate.is_finite() {
+            self.unstable_rate = Some(unstable_rate.max(MIN_UNSTABLE_RATE));
+        }
+
+        self
+    }
+
     /// Use the specified settings of the given [`Difficulty`].
     pub const fn difficulty(mut self, difficulty: Difficulty) -> Self {
         self.difficulty = difficulty;
@@ -195,12 +241,16 @@ impl<'map> TaikoPerformance<'map> {
             n300,
             n100,
             misses,
+            drumroll_ticks,
+            swells,
         } = state;
 
         self.combo = Some(max_combo);
         self.n300 = Some(n300);
         self.n100 = Some(n100);
         self.misses = Some(misses);
+        self.drumroll_ticks = Some(drumroll_ticks);
+        self.swells = Some(swells);
 
         self
     }
@@ -284,16 +334,31 @@ impl<'map> TaikoPerformance<'map> {
             cmp::min(combo, max_possible_combo)
         });
 
+        let drumroll_ticks = self.drumroll_ticks.map_or(attrs.n_drumroll_ticks, |n| {
+            cmp::min(n, attrs.n_drumroll_ticks)
+        });
+
+        let swells = self
+            .swells
+            .map_or(attrs.n_swells, |n| cmp::min(n, attrs.n_swells));
+
         TaikoScoreState {
             max_combo,
             n300,
             n100,
             misses,
+            drumroll_ticks,
+            swells,
         }
     }
 
     /// Calculate all performance related values, including pp and stars.
-    pub fn calculate(mut self) -> TaikoPerformanceAttributes {
+    pub fn calculate(self) -> TaikoPerformanceAttributes {
+        self.inner().calculate()
+    }
+
+    /// Generate the score state and pair it with the difficulty attributes.
+    fn inner(mut self) -> TaikoPerformanceInner {
         let state = self.generate_state();
 
         let attrs = match self.map_or_attrs {
@@ -301,13 +366,34 @@ impl<'map> TaikoPerformance<'map> {
             MapOrAttrs::Attrs(attrs) => attrs,
         };
 
-        let inner = TaikoPerformanceInner {
+        TaikoPerformanceInner {
             mods: self.difficulty.get_mods(),
             state,
             attrs,
-        };
+            unstable_rate: self.unstable_rate,
+        }
+    }
 
-        inner.calculate()
+    /// Find the minimal accuracy, in percent, for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Since explicitly specified hitresults take precedence over the
+    /// accuracy, they should generally be left unspecified.
+    ///
+    /// Hitresults are generated according to the specified
+    /// [`HitResultPriority`] for each probed accuracy.
+    ///
+    /// Returns `None` if `target_pp` is not reached even with 100% accuracy.
+    pub fn accuracy_for_pp(self, target_pp: f64) -> Option<f64> {
+        solver::accuracy_for_pp(self, target_pp)
+    }
+
+    /// Find the maximal amount of misses for which the pp value reaches
+    /// `target_pp` while all other parameters stay as specified.
+    ///
+    /// Returns `None` if `target_pp` is not reached even without misses.
+    pub fn misses_for_pp(self, target_pp: f64) -> Option<u32> {
+        solver::misses_for_pp(self, target_pp)
     }
 
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Taiko>) -> Self {
@@ -319,6 +405,9 @@ impl<'map> TaikoPerformance<'map> {
             misses: None,
             n300: None,
             n100: None,
+            drumroll_ticks: None,
+            swells: None,
+            unstable_rate: None,
             hitresult_priority: HitResultPriority::DEFAULT,
         }
     }
@@ -355,6 +444,8 @@ impl<'map> TryFrom<OsuPerformance<'map>> for TaikoPerformance<'map> {
             n100,
             n50: _,
             misses,
+            spinner_bonus: _,
+            spun_out: _,
             hitresult_priority,
         } = osu;
 
@@ -367,6 +458,9 @@ impl<'map> TryFrom<OsuPerformance<'map>> for TaikoPerformance<'map> {
             n300,
             n100,
             misses,
+            drumroll_ticks: None,
+            swells: None,
+            unstable_rate: None,
         })
     }
 }
@@ -377,14 +471,54 @@ impl<'map, T: IntoModePerformance<'map, Taiko>> From<T> for TaikoPerformance<'ma
     }
 }
 
+impl Solvable for TaikoPerformance<'_> {
+    type Invariants = TaikoPerformanceInvariants;
+
+    fn invariants(&mut self) -> Self::Invariants {
+        let attrs = match self.map_or_attrs {
+            MapOrAttrs::Map(ref map) => {
+                let attrs = self.difficulty.with_mode().calculate(map);
+
+                self.map_or_attrs.insert_attrs(attrs)
+            }
+            MapOrAttrs::Attrs(ref attrs) => attrs,
+        };
+
+        TaikoPerformanceInvariants::new(attrs, self.difficulty.get_mods())
+    }
+
+    fn with_accuracy(self, acc: f64) -> Self {
+        self.accuracy(acc)
+    }
+
+    fn with_misses(self, misses: u32) -> Self {
+        self.misses(misses)
+    }
+
+    fn max_misses(&self) -> u32 {
+        self.clone().misses(u32::MAX).generate_state().misses
+    }
+
+    fn pp(self, invariants: &Self::Invariants) -> f64 {
+        self.inner().calculate_with(invariants).pp
+    }
+}
+
 struct TaikoPerformanceInner {
     attrs: TaikoDifficultyAttributes,
     mods: u32,
     state: TaikoScoreState,
+    unstable_rate: Option<f64>,
 }
 
 impl TaikoPerformanceInner {
     fn calculate(self) -> TaikoPerformanceAttributes {
+        let invariants = TaikoPerformanceInvariants::new(&self.attrs, self.mods);
+
+        self.calculate_with(&invariants)
+    }
+
+    fn calculate_with(self, invariants: &TaikoPerformanceInvariants) -> TaikoPerformanceAttributes {
         // * The effectiveMissCount is calculated by gaining a ratio for totalSuccessfulHits
         // * and increasing the miss penalty for shorter object counts lower than 1000.
         let total_successful_hits = self.total_successful_hits();
@@ -395,38 +529,49 @@ impl TaikoPerformanceInner {
             0.0
         };
 
-        let mut multiplier = 1.13;
+        let estimated_unstable_rate = self
+            .unstable_rate
+            .or_else(|| self.compute_deviation_upper_bound().map(|dev| dev * 10.0));
 
-        if self.mods.hd() {
-            multiplier *= 1.075;
-        }
+        let [diff_value, color_value, rhythm_value, stamina_value, speed_value, finger_control_value] =
+            invariants.diff_values.map(|diff_value| {
+                self.compute_difficulty_value(
+                    invariants,
+                    diff_value,
+                    effective_miss_count,
+                    estimated_unstable_rate,
+                )
+            });
 
-        if self.mods.ez() {
-            multiplier *= 0.975;
-        }
-
-        let diff_value = self.compute_difficulty_value(effective_miss_count);
-        let acc_value = self.compute_accuracy_value();
+        let acc_value = self.compute_accuracy_value(invariants, estimated_unstable_rate);
 
-        let pp = (diff_value.powf(1.1) + acc_value.powf(1.1)).powf(1.0 / 1.1) * multiplier;
+        let pp =
+            (diff_value.powf(1.1) + acc_value.powf(1.1)).powf(1.0 / 1.1) * invariants.multiplier;
 
         TaikoPerformanceAttributes {
             difficulty: self.attrs,
             pp,
             pp_acc: acc_value,
             pp_difficulty: diff_value,
+            pp_color: color_value,
+            pp_rhythm: rhythm_value,
+            pp_stamina: stamina_value,
+            pp_speed: speed_value,
+            pp_finger_control: finger_control_value,
             effective_miss_count,
+            estimated_unstable_rate,
         }
     }
 
-    fn compute_difficulty_value(&self, effective_miss_count: f64) -> f64 {
-        let attrs = &self.attrs;
-        let exp_base = 5.0 * (attrs.stars / 0.115).max(1.0) - 4.0;
-        let mut diff_value = exp_base.powf(2.25) / 1150.0;
-
-        let len_bonus = 1.0 + 0.1 * (f64::from(attrs.max_combo) / 1500.0).min(1.0);
-        diff_value *= len_bonus;
-
+    /// Apply the score-dependent factors to a difficulty value of
+    /// [`TaikoPerformanceInvariants`].
+    fn compute_difficulty_value(
+        &self,
+        invariants: &TaikoPerformanceInvariants,
+        mut diff_value: f64,
+        effective_miss_count: f64,
+        estimated_unstable_rate: Option<f64>,
+    ) -> f64 {
         diff_value *= 0.986_f64.powf(effective_miss_count);
 
         if self.mods.ez() {
@@ -442,25 +587,33 @@ impl TaikoPerformanceInner {
         }
 
         if self.mods.fl() {
-            diff_value *= 1.05 * len_bonus;
+            diff_value *= 1.05 * invariants.len_bonus;
         }
 
-        let acc = self.custom_accuracy();
+        let Some(estimated_unstable_rate) = estimated_unstable_rate else {
+            return 0.0;
+        };
 
-        diff_value * acc.powf(2.0)
+        diff_value
+            * erf(invariants.acc_scaling_shift / (SQRT_2 * estimated_unstable_rate))
+                .powf(invariants.acc_scaling_exp)
     }
 
-    fn compute_accuracy_value(&self) -> f64 {
-        if self.attrs.hit_window <= 0.0 {
+    fn compute_accuracy_value(
+        &self,
+        invariants: &TaikoPerformanceInvariants,
+        estimated_unstable_rate: Option<f64>,
+    ) -> f64 {
+        let Some(estimated_unstable_rate) =
+            estimated_unstable_rate.filter(|_| self.attrs.hit_window > 0.0)
+        else {
             return 0.0;
-        }
+        };
 
-        let mut acc_value = (60.0 / self.attrs.hit_window).powf(1.1)
-            * self.custom_accuracy().powf(8.0)
-            * self.attrs.stars.powf(0.4)
-            * 27.0;
+        let mut acc_value =
+            (70.0 / estimated_unstable_rate).powf(1.1) * invariants.acc_stars_factor * 100.0;
 
-        let len_bonus = (self.total_hits() / 1500.0).powf(0.3).min(1.15);
+        let len_bonus = invariants.acc_len_bonus(self.total_hits());
         acc_value *= len_bonus;
 
         // * Slight HDFL Bonus for accuracy. A clamp is used to prevent against negative values
@@ -471,6 +624,29 @@ impl TaikoPerformanceInner {
         acc_value
     }
 
+    /// Computes an upper bound on the player's tap deviation based on the OD,
+    /// number of 300s and 100s, and the hit window.
+    fn compute_deviation_upper_bound(&self) -> Option<f64> {
+        // * 99% critical value for the normal distribution (one-tailed).
+        const Z: f64 = 2.326_347_874_04;
+
+        if self.state.n300 == 0 || self.attrs.hit_window <= 0.0 {
+            return None;
+        }
+
+        let n = self.total_hits();
+
+        // * Proportion of greats hit.
+        let p = f64::from(self.state.n300) / n;
+
+        // * We can be 99% confident that p is at least this value.
+        let p_lower_bound = (n * p + Z * Z / 2.0) / (n + Z * Z)
+            - Z / (n + Z * Z) * (n * p * (1.0 - p) + Z * Z / 4.0).sqrt();
+
+        // * We can be 99% confident that the deviation is not higher than:
+        Some(self.attrs.hit_window / (SQRT_2 * erf_inv(p_lower_bound)))
+    }
+
     const fn total_hits(&self) -> f64 {
         self.state.total_hits() as f64
     }
@@ -478,18 +654,82 @@ impl TaikoPerformanceInner {
     const fn total_successful_hits(&self) -> u32 {
         self.state.n300 + self.state.n100
     }
+}
+
+/// The parts of [`TaikoPerformanceInner`] that only depend on the difficulty
+/// attributes and mods so they can be shared between scores.
+pub(crate) struct TaikoPerformanceInvariants {
+    multiplier: f64,
+    len_bonus: f64,
+    /// Difficulty values of the star rating, color, rhythm, stamina, speed,
+    /// and finger control before applying score-dependent factors.
+    diff_values: [f64; 6],
+    acc_scaling_exp: f64,
+    acc_scaling_shift: f64,
+    acc_stars_factor: f64,
+    /// The accuracy length bonus for scores that hit all circles.
+    full_acc_len_bonus: f64,
+    n_circles: f64,
+}
 
-    fn custom_accuracy(&self) -> f64 {
-        let total_hits = self.state.total_hits();
+impl TaikoPerformanceInvariants {
+    fn new(attrs: &TaikoDifficultyAttributes, mods: u32) -> Self {
+        let mut multiplier = 1.13;
 
-        if total_hits == 0 {
-            return 0.0;
+        if mods.hd() {
+            multiplier *= 1.075;
         }
 
-        let numerator = self.state.n300 * 300 + self.state.n100 * 150;
-        let denominator = total_hits * 300;
+        if mods.ez() {
+            multiplier *= 0.975;
+        }
+
+        let len_bonus = 1.0 + 0.1 * (f64::from(attrs.max_combo) / 1500.0).min(1.0);
+
+        let diff_value = |stars: f64| {
+            let exp_base = 5.0 * (stars / 0.115).max(1.0) - 4.0;
+            let mut diff_value = exp_base.powf(2.25) / 1150.0;
+            diff_value *= len_bonus;
+
+            diff_value
+        };
+
+        // * The strain portion of the pp if the map's difficulty consisted
+        // * only of the given skill rating.
+        let skill_value = |rating: f64| diff_value(rescale(rating * 1.4));
+
+        let n_circles = f64::from(attrs.max_combo);
 
-        f64::from(numerator) / f64::from(denominator)
+        Self {
+            multiplier,
+            len_bonus,
+            diff_values: [
+                diff_value(attrs.stars),
+                skill_value(attrs.color),
+                skill_value(attrs.rhythm),
+                skill_value(attrs.stamina),
+                skill_value(attrs.speed),
+                skill_value(attrs.finger_control),
+            ],
+            // * Scale accuracy more harshly on nearly-completely mono (single coloured) speed maps.
+            acc_scaling_exp: 2.0 + attrs.mono_stamina_factor,
+            acc_scaling_shift: 500.0 - 100.0 * (attrs.mono_stamina_factor * 3.0),
+            acc_stars_factor: attrs.stars.powf(0.4),
+            full_acc_len_bonus: Self::compute_acc_len_bonus(n_circles),
+            n_circles,
+        }
+    }
+
+    fn compute_acc_len_bonus(total_hits: f64) -> f64 {
+        (total_hits / 1500.0).powf(0.3).min(1.15)
+    }
+
+    fn acc_len_bonus(&self, total_hits: f64) -> f64 {
+        if total_hits.eq(self.n_circles) {
+            self.full_acc_len_bonus
+        } else {
+            Self::compute_acc_len_bonus(total_hits)
+        }
     }
 }
 
@@ -522,6 +762,8 @@ mod test {
     static ATTRS: OnceLock<TaikoDifficultyAttributes> = OnceLock::new();
 
     const MAX_COMBO: u32 = 289;
+    const N_DRUMROLL_TICKS: u32 = 36;
+    const N_SWELLS: u32 = 2;
 
     fn beatmap() -> Beatmap {
         Beatmap::from_path("./resources/1028484.osu").unwrap()
@@ -534,6 +776,8 @@ mod test {
                 let attrs = Difficulty::new().with_mode().calculate(&converted);
 
                 assert_eq!(MAX_COMBO, attrs.max_combo);
+                assert_eq!(N_DRUMROLL_TICKS, attrs.n_drumroll_ticks);
+                assert_eq!(N_SWELLS, attrs.n_swells);
 
                 attrs
             })
@@ -644,6 +888,8 @@ mod test {
                 best_case,
             );
             expected.max_combo = MAX_COMBO.saturating_sub(n_misses.unwrap_or(0));
+            expected.drumroll_ticks = N_DRUMROLL_TICKS;
+            expected.swells = N_SWELLS;
 
             assert_eq!(state, expected);
         }
@@ -663,6 +909,8 @@ mod test {
             n300: 150,
             n100: 137,
             misses: 2,
+            drumroll_ticks: N_DRUMROLL_TICKS,
+            swells: N_SWELLS,
         };
 
         assert_eq!(state, expected);
@@ -681,11 +929,117 @@ mod test {
             n300: 287,
             n100: 0,
             misses: 2,
+            drumroll_ticks: N_DRUMROLL_TICKS,
+            swells: N_SWELLS,
         };
 
         assert_eq!(state, expected);
     }
 
+    #[test]
+    fn bonus_results() {
+        let state = TaikoPerformance::from(attrs())
+            .drumroll_ticks(N_DRUMROLL_TICKS + 10)
+            .swells(1)
+            .generate_state();
+
+        assert_eq!(state.drumroll_ticks, N_DRUMROLL_TICKS);
+        assert_eq!(state.swells, 1);
+        assert_eq!(
+            state.bonus_score(),
+            N_DRUMROLL_TICKS * TaikoScoreState::DRUMROLL_TICK_SCORE + TaikoScoreState::SWELL_SCORE
+        );
+
+        let full = TaikoPerformance::from(attrs()).calculate();
+        let partial = TaikoPerformance::from(attrs()).state(state).calculate();
+
+        assert_eq!(full.pp, partial.pp);
+    }
+
+    #[test]
+    fn skill_breakdown() {
+        let attrs = attrs();
+
+        assert!(attrs.speed >= attrs.stamina);
+        assert!(attrs.finger_control > 0.0);
+
+        let fc = TaikoPerformance::from(attrs.clone()).calculate();
+        let misses = TaikoPerformance::from(attrs).misses(5).calculate();
+
+        let skills = |attrs: &TaikoPerformanceAttributes| {
+            [
+                attrs.pp_color,
+                attrs.pp_rhythm,
+                attrs.pp_stamina,
+                attrs.pp_speed,
+                attrs.pp_finger_control,
+            ]
+        };
+
+        for (fc, misses) in skills(&fc).into_iter().zip(skills(&misses)) {
+            assert!(fc > 0.0);
+            assert!(fc > misses);
+        }
+
+        assert!(fc.pp_speed >= fc.pp_stamina);
+    }
+
+    #[test]
+    fn estimated_unstable_rate() {
+        let attrs = attrs();
+
+        let ss = TaikoPerformance::from(attrs.clone()).calculate();
+        let n100 = TaikoPerformance::from(attrs.clone()).n100(20).calculate();
+        let no_n300 = TaikoPerformance::from(attrs.clone())
+            .n300(0)
+            .n100(MAX_COMBO)
+            .calculate();
+
+        let ss_ur = ss.estimated_unstable_rate.unwrap();
+        let n100_ur = n100.estimated_unstable_rate.unwrap();
+
+        assert!(ss_ur < n100_ur);
+        assert!(ss.pp_acc > n100.pp_acc);
+        assert!(ss.pp_difficulty > n100.pp_difficulty);
+
+        assert_eq!(no_n300.estimated_unstable_rate, None);
+        assert_eq!(no_n300.pp_acc, 0.0);
+        assert_eq!(no_n300.pp_difficulty, 0.0);
+
+        let given = TaikoPerformance::from(attrs)
+            .unstable_rate(ss_ur * 2.0)
+            .calculate();
+
+        assert_eq!(given.estimated_unstable_rate, Some(ss_ur * 2.0));
+        assert!(given.pp < ss.pp);
+    }
+
+    #[test]
+    fn invalid_unstable_rate() {
+        let attrs = attrs();
+
+        let estimated = TaikoPerformance::from(attrs.clone()).calculate();
+
+        let nan = TaikoPerformance::from(attrs.clone())
+            .unstable_rate(f64::NAN)
+            .calculate();
+
+        assert_eq!(nan, estimated);
+
+        let zero = TaikoPerformance::from(attrs.clone())
+            .unstable_rate(0.0)
+            .calculate();
+
+        let min = TaikoPerformance::from(attrs)
+            .unstable_rate(MIN_UNSTABLE_RATE)
+            .calculate();
+
+        assert_eq!(zero.estimated_unstable_rate, Some(MIN_UNSTABLE_RATE));
+        assert!(zero.pp.is_finite());
+        assert!(zero.pp_acc.is_finite());
+        assert_eq!(zero, min);
+    }
+
     #[test]
     fn create() {
         let mut map = beatmap();
@@ -10,9 +10,23 @@ pub struct TaikoScoreState {
     pub n100: u32,
     /// Amount of current misses.
     pub misses: u32,
+    /// Amount of hit drum roll ticks.
+    ///
+    /// Only informational; drum roll ticks do not affect performance.
+    pub drumroll_ticks: u32,
+    /// Amount of completed swells.
+    ///
+    /// Only informational; swells do not affect performance.
+    pub swells: u32,
 }
 
 impl TaikoScoreState {
+    /// The bonus score of a hit drum roll tick.
+    pub const DRUMROLL_TICK_SCORE: u32 = 10;
+
+    /// The bonus score of a completed swell.
+    pub const SWELL_SCORE: u32 = 50;
+
     /// Create a new empty score state.
     pub const fn new() -> Self {
         Self {
@@ -20,6 +34,8 @@ impl TaikoScoreState {
             n300: 0,
             n100: 0,
             misses: 0,
+            drumroll_ticks: 0,
+            swells: 0,
         }
     }
 
@@ -28,6 +44,12 @@ impl TaikoScoreState {
         self.n300 + self.n100 + self.misses
     }
 
+    /// Return the score of the bonus results i.e. drum roll ticks and
+    /// swells, as it is added onto the total score on osu!lazer.
+    pub const fn bonus_score(&self) -> u32 {
+        Self::DRUMROLL_TICK_SCORE * self.drumroll_ticks + Self::SWELL_SCORE * self.swells
+    }
+
     /// Calculate the accuracy between `0.0` and `1.0` for this state.
     pub fn accuracy(&self) -> f64 {
         let total_hits = self.total_hits();
@@ -1,4 +1,13 @@
-use crate::{taiko::difficulty::DifficultyValues, Difficulty};
+use crate::{
+    any::{difficulty::skills::StrainDecaySkill, HardestSection, StrainTimeline},
+    taiko::difficulty::{
+        skills::peaks::{
+            Peaks, COLOR_SKILL_MULTIPLIER, RHYTHM_SKILL_MULTIPLIER, STAMINA_SKILL_MULTIPLIER,
+        },
+        DifficultyValues, DIFFICULTY_MULTIPLIER,
+    },
+    Difficulty,
+};
 
 use super::convert::TaikoBeatmap;
 
@@ -13,19 +22,79 @@ pub struct TaikoStrains {
     pub rhythm: Vec<f64>,
     /// Strain peaks of the stamina skill.
     pub stamina: Vec<f64>,
+    /// Strain peaks of the stamina skill for single color streams.
+    pub single_color_stamina: Vec<f64>,
 }
 
 impl TaikoStrains {
     /// Time between two strains in ms.
     pub const SECTION_LEN: f64 = 400.0;
+
+    /// Turn the strain peaks into a smoothed difficulty curve.
+    ///
+    /// See [`StrainTimeline`] for more information.
+    #[must_use]
+    pub fn timeline(&self, timeline: &StrainTimeline) -> Self {
+        let smooth = |peaks: &[f64], multiplier: f64| {
+            timeline.smooth(
+                peaks,
+                Self::SECTION_LEN,
+                StrainDecaySkill::DECAY_WEIGHT,
+                |value| value * multiplier * DIFFICULTY_MULTIPLIER,
+            )
+        };
+
+        Self {
+            color: smooth(&self.color, COLOR_SKILL_MULTIPLIER),
+            rhythm: smooth(&self.rhythm, RHYTHM_SKILL_MULTIPLIER),
+            stamina: smooth(&self.stamina, STAMINA_SKILL_MULTIPLIER),
+            single_color_stamina: smooth(&self.single_color_stamina, STAMINA_SKILL_MULTIPLIER),
+        }
+    }
 }
 
 pub fn strains(difficulty: &Difficulty, converted: &TaikoBeatmap<'_>) -> TaikoStrains {
-    let values = DifficultyValues::calculate(difficulty, converted);
+    let values = DifficultyValues::calculate(difficulty, converted, None);
 
     TaikoStrains {
         color: values.peaks.color.get_curr_strain_peaks().into_vec(),
         rhythm: values.peaks.rhythm.get_curr_strain_peaks().into_vec(),
         stamina: values.peaks.stamina.get_curr_strain_peaks().into_vec(),
+        single_color_stamina: values
+            .peaks
+            .single_color_stamina
+            .get_curr_strain_peaks()
+            .into_vec(),
     }
 }
+
+pub fn hardest_section(
+    difficulty: &Difficulty,
+    converted: &TaikoBeatmap<'_>,
+    duration: f64,
+) -> Option<HardestSection> {
+    let Peaks {
+        color,
+        rhythm,
+        stamina,
+        ..
+    } = DifficultyValues::calculate(difficulty, converted, None).peaks;
+
+    let section_end = color.section_end();
+
+    let peaks: Vec<_> = color
+        .get_curr_strain_peaks()
+        .iter()
+        .zip(rhythm.get_curr_strain_peaks().iter())
+        .zip(stamina.get_curr_strain_peaks().iter())
+        .map(|((color, rhythm), stamina)| Peaks::combined_peak(color, rhythm, stamina))
+        .collect();
+
+    HardestSection::new(
+        &peaks,
+        TaikoStrains::SECTION_LEN,
+        section_end,
+        difficulty.get_clock_rate(),
+        duration,
+    )
+}
@@ -0,0 +1,100 @@
+use std::cmp;
+
+/// Snapshots of a gradual calculation's state after every `interval`
+/// processed objects.
+///
+/// No snapshots are stored until an interval is specified. The snapshot for
+/// `0` processed objects is always available so that calculations can be
+/// rewound to their start.
+#[derive(Clone, Debug)]
+pub struct Checkpoints<T> {
+    interval: Option<usize>,
+    /// The `i`th checkpoint is the state after `i * interval` objects.
+    states: Vec<T>,
+}
+
+impl<T: Clone> Checkpoints<T> {
+    pub fn new(initial: T) -> Self {
+        Self {
+            interval: None,
+            states: vec![initial],
+        }
+    }
+
+    pub const fn interval(&self) -> Option<usize> {
+        self.interval
+    }
+
+    /// Change the interval of checkpoints.
+    ///
+    /// All checkpoints except for the initial one will be discarded.
+    pub fn set_interval(&mut self, interval: usize) {
+        self.interval = Some(interval.max(1));
+        self.states.truncate(1);
+    }
+
+    /// Store the state after `idx` processed objects if it's due.
+    ///
+    /// `state` is only called if the state will be stored.
+    pub fn save(&mut self, idx: usize, state: impl FnOnce() -> T) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+
+        if idx.is_multiple_of(interval) && idx / interval == self.states.len() {
+            self.states.push(state());
+        }
+    }
+
+    /// Find the checkpoint that is best suited to reach `target` processed
+    /// objects from `curr` processed objects.
+    ///
+    /// Returns `None` if no checkpoint is closer to `target` than `curr`.
+    pub fn closest(&self, curr: usize, target: usize) -> Option<(usize, &T)> {
+        let Some(interval) = self.interval else {
+            return (target < curr).then(|| (0, &self.states[0]));
+        };
+
+        let i = cmp::min(target / interval, self.states.len() - 1);
+        let idx = i * interval;
+
+        (target < curr || idx > curr).then(|| (idx, &self.states[i]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest() {
+        let mut checkpoints = Checkpoints::new(0);
+        checkpoints.set_interval(10);
+
+        for idx in 1..=35 {
+            checkpoints.save(idx, || idx);
+        }
+
+        assert_eq!(checkpoints.closest(35, 40), None);
+        assert_eq!(checkpoints.closest(35, 25), Some((20, &20)));
+        assert_eq!(checkpoints.closest(5, 25), Some((20, &20)));
+        assert_eq!(checkpoints.closest(22, 25), None);
+        assert_eq!(checkpoints.closest(22, 3), Some((0, &0)));
+
+        checkpoints.set_interval(4);
+        assert_eq!(checkpoints.closest(35, 25), Some((0, &0)));
+    }
+
+    #[test]
+    fn opt_in() {
+        let mut checkpoints = Checkpoints::new(0);
+
+        for idx in 1..=35 {
+            checkpoints.save(idx, || idx);
+        }
+
+        assert_eq!(checkpoints.states.len(), 1);
+        assert_eq!(checkpoints.closest(5, 25), None);
+        assert_eq!(checkpoints.closest(35, 25), Some((0, &0)));
+    }
+}
@@ -1,10 +1,14 @@
+pub mod checkpoints;
 pub mod float_ext;
 pub mod generic_fmt;
 pub mod limited_queue;
 pub mod map_or_attrs;
 pub mod mods;
+pub mod object_times;
 pub mod random;
+pub mod solver;
 pub mod sort;
 pub mod strains_vec;
 pub mod sync;
+pub mod tags;
 pub mod pplus;
@@ -9,6 +9,8 @@ pub trait Mods: Copy {
     fn ht(self) -> bool;
     fn fl(self) -> bool;
     fn so(self) -> bool;
+    fn co(self) -> bool;
+    fn key_count(self) -> Option<u8>;
 
     fn clock_rate(self) -> f64 {
         if self.dt() {
@@ -50,4 +52,24 @@ impl Mods for u32 {
     impl_mods_fn!(ht, 1 << 8);
     impl_mods_fn!(fl, 1 << 10);
     impl_mods_fn!(so, 1 << 12);
+    impl_mods_fn!(co, 1 << 25);
+
+    fn key_count(self) -> Option<u8> {
+        const KEY_MODS: [(u32, u8); 9] = [
+            (1 << 26, 1),
+            (1 << 28, 2),
+            (1 << 27, 3),
+            (1 << 15, 4),
+            (1 << 16, 5),
+            (1 << 17, 6),
+            (1 << 18, 7),
+            (1 << 19, 8),
+            (1 << 24, 9),
+        ];
+
+        KEY_MODS
+            .iter()
+            .find(|(bits, _)| self & bits != 0)
+            .map(|(_, key_count)| *key_count)
+    }
 }
@@ -0,0 +1,72 @@
+use crate::model::hit_object::ObjectTime;
+
+/// Start and end times of the objects that a gradual calculation steps
+/// through, adjusted by the clock rate.
+#[derive(Clone, Debug)]
+pub struct ObjectTimes {
+    start_times: Box<[f64]>,
+    /// The latest end time of all objects up to each index so that the
+    /// times are sorted even if an object ends after the next one starts.
+    end_times: Box<[f64]>,
+    object_time: ObjectTime,
+}
+
+impl ObjectTimes {
+    /// Create [`ObjectTimes`] from the unadjusted start and end times of
+    /// objects in order.
+    pub fn new(times: impl Iterator<Item = (f64, f64)>, clock_rate: f64) -> Self {
+        let mut latest_end_time = f64::NEG_INFINITY;
+
+        let (start_times, end_times) = times
+            .map(|(start_time, end_time)| {
+                latest_end_time = latest_end_time.max(end_time / clock_rate);
+
+                (start_time / clock_rate, latest_end_time)
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        Self {
+            start_times: start_times.into_boxed_slice(),
+            end_times: end_times.into_boxed_slice(),
+            object_time: ObjectTime::default(),
+        }
+    }
+
+    pub const fn object_time(&self) -> ObjectTime {
+        self.object_time
+    }
+
+    pub const fn set_object_time(&mut self, object_time: ObjectTime) {
+        self.object_time = object_time;
+    }
+
+    /// The amount of leading objects whose time is at or before `time`.
+    pub fn count_until(&self, time: f64) -> usize {
+        let times = match self.object_time {
+            ObjectTime::Start => &self.start_times,
+            ObjectTime::End => &self.end_times,
+        };
+
+        times.partition_point(|&object_time| object_time <= time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_until() {
+        let times = [(0.0, 0.0), (100.0, 400.0), (200.0, 200.0), (500.0, 500.0)];
+        let mut object_times = ObjectTimes::new(times.into_iter(), 2.0);
+
+        assert_eq!(object_times.count_until(-1.0), 0);
+        assert_eq!(object_times.count_until(50.0), 2);
+        assert_eq!(object_times.count_until(100.0), 3);
+        assert_eq!(object_times.count_until(250.0), 4);
+
+        object_times.set_object_time(ObjectTime::End);
+        assert_eq!(object_times.count_until(100.0), 1);
+        assert_eq!(object_times.count_until(200.0), 3);
+    }
+}
@@ -0,0 +1,127 @@
+/// Maximum amount of bisection steps for continuous searches.
+const MAX_ITERATIONS: usize = 64;
+
+/// Continuous searches stop once the interval is smaller than this.
+const TOLERANCE: f64 = 1e-6;
+
+/// A performance calculator whose pp can be probed by the solvers.
+pub(crate) trait Solvable: Clone {
+    /// Values that only depend on the difficulty attributes and mods.
+    type Invariants;
+
+    /// Calculate the difficulty attributes, store them so that they don't
+    /// need to be recalculated for each probe, and compute the invariants.
+    fn invariants(&mut self) -> Self::Invariants;
+
+    /// Specify the accuracy in percent.
+    fn with_accuracy(self, acc: f64) -> Self;
+
+    /// Specify the amount of misses.
+    fn with_misses(self, misses: u32) -> Self;
+
+    /// The maximum amount of misses.
+    fn max_misses(&self) -> u32;
+
+    /// Calculate the pp value through the given invariants.
+    fn pp(self, invariants: &Self::Invariants) -> f64;
+}
+
+/// Find the minimal accuracy, in percent, for which the pp value of
+/// `performance` reaches `target_pp`.
+pub fn accuracy_for_pp<P: Solvable>(mut performance: P, target_pp: f64) -> Option<f64> {
+    let invariants = performance.invariants();
+
+    min_reaching(0.0, 100.0, target_pp, |acc| {
+        performance.clone().with_accuracy(acc).pp(&invariants)
+    })
+}
+
+/// Find the maximal amount of misses for which the pp value of `performance`
+/// reaches `target_pp`.
+pub fn misses_for_pp<P: Solvable>(mut performance: P, target_pp: f64) -> Option<u32> {
+    let invariants = performance.invariants();
+    let max_misses = performance.max_misses();
+
+    max_reaching(max_misses, target_pp, |misses| {
+        performance.clone().with_misses(misses).pp(&invariants)
+    })
+}
+
+/// Find the smallest `x` in `[min, max]` for which `f(x)` reaches `target`.
+///
+/// `f` must be non-decreasing. Returns `None` if even `f(max)` does not reach
+/// `target`.
+pub fn min_reaching(min: f64, max: f64, target: f64, mut f: impl FnMut(f64) -> f64) -> Option<f64> {
+    if f(max) < target {
+        return None;
+    } else if f(min) >= target {
+        return Some(min);
+    }
+
+    let (mut lo, mut hi) = (min, max);
+
+    for _ in 0..MAX_ITERATIONS {
+        if hi - lo < TOLERANCE {
+            break;
+        }
+
+        let mid = lo + (hi - lo) / 2.0;
+
+        if f(mid) >= target {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    Some(hi)
+}
+
+/// Find the largest `n` in `[0, max]` for which `f(n)` reaches `target`.
+///
+/// `f` must be non-increasing. Returns `None` if even `f(0)` does not reach
+/// `target`.
+pub fn max_reaching(max: u32, target: f64, mut f: impl FnMut(u32) -> f64) -> Option<u32> {
+    if f(0) < target {
+        return None;
+    }
+
+    // `lo` always reaches the target, `hi` never does
+    let (mut lo, mut hi) = (0, u64::from(max) + 1);
+
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+
+        if f(mid as u32) >= target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Some(lo as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn continuous() {
+        let x = min_reaching(0.0, 100.0, 42.0, |x| x * x).unwrap();
+        assert!((x - 42_f64.sqrt()).abs() < TOLERANCE);
+
+        assert_eq!(min_reaching(0.0, 100.0, 20_000.0, |x| x * x), None);
+        assert_eq!(min_reaching(5.0, 100.0, 1.0, |x| x * x), Some(5.0));
+    }
+
+    #[test]
+    fn discrete() {
+        let f = |n| 100.0 - f64::from(n) * 7.0;
+
+        assert_eq!(max_reaching(50, 30.0, f), Some(10));
+        assert_eq!(max_reaching(50, 101.0, f), None);
+        assert_eq!(max_reaching(5, 30.0, f), Some(5));
+        assert_eq!(max_reaching(u32::MAX, -1.0, |_| 0.0), Some(u32::MAX));
+    }
+}
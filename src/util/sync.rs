@@ -159,4 +159,16 @@ mod tests {
             let _ = gradual.next();
         });
     }
+
+    #[test]
+    fn gradual_send_sync() {
+        const fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<crate::GradualDifficulty>();
+        assert_send_sync::<crate::GradualPerformance>();
+        assert_send_sync::<crate::osu::OsuGradualPerformance>();
+        assert_send_sync::<crate::taiko::TaikoGradualPerformance>();
+        assert_send_sync::<crate::catch::CatchGradualPerformance>();
+        assert_send_sync::<crate::mania::ManiaGradualPerformance>();
+    }
 }
@@ -0,0 +1,29 @@
+/// The kinds whose summed counts make up at least `min_share` of the total,
+/// ordered by their share.
+pub fn tags<K: Copy + PartialEq>(
+    sections: impl Iterator<Item = (K, usize)>,
+    min_share: f64,
+) -> Vec<K> {
+    let mut counts: Vec<(K, usize)> = Vec::new();
+
+    for (kind, n) in sections {
+        match counts.iter_mut().find(|(k, _)| *k == kind) {
+            Some((_, count)) => *count += n,
+            None => counts.push((kind, n)),
+        }
+    }
+
+    let total: usize = counts.iter().map(|(_, count)| count).sum();
+
+    if total == 0 {
+        return Vec::new();
+    }
+
+    counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    counts
+        .into_iter()
+        .take_while(|(_, count)| *count as f64 / total as f64 >= min_share)
+        .map(|(kind, _)| kind)
+        .collect()
+}
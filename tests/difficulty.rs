@@ -79,6 +79,7 @@ macro_rules! test_cases {
             stars: $stars,
             max_combo: $max_combo,
             is_convert: $is_convert,
+            ..Default::default()
         }
     };
     ( @Catch {
@@ -96,6 +97,7 @@ macro_rules! test_cases {
             n_droplets: $n_droplets,
             n_tiny_droplets: $n_tiny_droplets,
             is_convert: $is_convert,
+            ..Default::default()
         }
     };
     ( @Mania {
@@ -111,6 +113,7 @@ macro_rules! test_cases {
             n_objects: $n_objects,
             max_combo: $max_combo,
             is_convert: $is_convert,
+            ..Default::default()
         }
     }
 }
@@ -314,32 +317,32 @@ fn basic_taiko() {
     test_cases! {
         Taiko: TAIKO {
             NM => {
-                stamina: 1.4528845068865617,
+                stamina: 1.3991746883284404,
                 rhythm: 0.20130047251681948,
                 color: 1.0487315549761433,
-                peak: 1.8881824429738323,
+                peak: 1.8422453377400803,
                 hit_window: 35.0,
-                stars: 2.9778030386845606,
+                stars: 2.9145897001804406,
                 max_combo: 289,
                 is_convert: false,
             };
             HR => {
-                stamina: 1.4528845068865617,
+                stamina: 1.3991746883284404,
                 rhythm: 0.20130047251681948,
                 color: 1.0487315549761433,
-                peak: 1.8881824429738323,
+                peak: 1.8422453377400803,
                 hit_window: 29.0,
-                stars: 2.9778030386845606,
+                stars: 2.9145897001804406,
                 max_combo: 289,
                 is_convert: false,
             };
             DT => {
-                stamina: 2.054617838297644,
+                stamina: 2.0358868555131586,
                 rhythm: 0.4448175371191029,
                 color: 1.3637624960988888,
-                peak: 2.6393434317991886,
+                peak: 2.625066421324458,
                 hit_window: 23.333333333333332,
-                stars: 3.9605501866340607,
+                stars: 3.942709244618132,
                 max_combo: 289,
                 is_convert: false,
             };
@@ -352,32 +355,32 @@ fn convert_taiko() {
     test_cases! {
         Taiko: OSU {
             NM => {
-                stamina: 2.947896529153566,
+                stamina: 2.9127139214411444,
                 rhythm: 1.4696991260446617,
                 color: 2.3032281729649067,
-                peak: 4.130240422926277,
+                peak: 4.117779264387738,
                 hit_window: 23.59999942779541,
-                stars: 5.247857660585606,
+                stars: 5.235637844901627,
                 max_combo: 908,
                 is_convert: true,
             };
             HR => {
-                stamina: 2.947896529153566,
+                stamina: 2.9127139214411444,
                 rhythm: 1.4696991260446617,
                 color: 2.3032281729649067,
-                peak: 4.130240422926277,
+                peak: 4.117779264387738,
                 hit_window: 20.0,
-                stars: 5.247857660585606,
+                stars: 5.235637844901627,
                 max_combo: 908,
                 is_convert: true,
             };
             DT => {
-                stamina: 4.412382708370478,
+                stamina: 4.379782453136822,
                 rhythm: 2.002843919169095,
                 color: 3.1864894777399986,
-                peak: 6.107962386775966,
+                peak: 6.103209631166692,
                 hit_window: 15.733332951863607,
-                stars: 7.0140481946324815,
+                stars: 7.010168846394128,
                 max_combo: 908,
                 is_convert: true,
             };